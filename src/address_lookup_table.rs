@@ -0,0 +1,110 @@
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+/// The Address Lookup Table program ID
+pub const ADDRESS_LOOKUP_TABLE_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("AddressLookupTab1e1111111111111111111111111");
+
+/// Addresses per extend instruction, leaving room in the transaction for the
+/// signatures and the other accounts
+pub const MAX_ADDRESSES_PER_EXTEND: usize = 20;
+
+/// Bincode enum variant indices of `ProgramInstruction`
+const CREATE_LOOKUP_TABLE: u32 = 0;
+const EXTEND_LOOKUP_TABLE: u32 = 2;
+
+/// Derives the lookup table address owned by `authority` for `recent_slot`
+pub fn derive_lookup_table_address(authority: &Pubkey, recent_slot: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[authority.as_ref(), &recent_slot.to_le_bytes()],
+        &ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+    )
+}
+
+/// Creates an empty lookup table; `recent_slot` must still be in the
+/// SlotHashes sysvar when the transaction lands
+///
+/// # Returns
+/// The create instruction and the new table's address
+pub fn create_lookup_table(
+    authority: &Pubkey,
+    payer: &Pubkey,
+    recent_slot: u64,
+) -> (Instruction, Pubkey) {
+    let (lookup_table, bump_seed) = derive_lookup_table_address(authority, recent_slot);
+
+    let mut data = CREATE_LOOKUP_TABLE.to_le_bytes().to_vec();
+    data.extend_from_slice(&recent_slot.to_le_bytes());
+    data.push(bump_seed);
+
+    let instruction = Instruction::new_with_bytes(
+        ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(lookup_table, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+    );
+    (instruction, lookup_table)
+}
+
+/// Appends `new_addresses` to a lookup table, with `payer` covering the
+/// extra rent
+pub fn extend_lookup_table(
+    lookup_table: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    new_addresses: &[Pubkey],
+) -> Instruction {
+    let mut data = EXTEND_LOOKUP_TABLE.to_le_bytes().to_vec();
+    data.extend_from_slice(&(new_addresses.len() as u64).to_le_bytes());
+    for address in new_addresses {
+        data.extend_from_slice(address.as_ref());
+    }
+
+    Instruction::new_with_bytes(
+        ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(*lookup_table, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_lookup_table_data() {
+        let authority = Pubkey::new_unique();
+        let (instruction, lookup_table) = create_lookup_table(&authority, &authority, 42);
+        let (expected_table, bump) = derive_lookup_table_address(&authority, 42);
+
+        assert_eq!(lookup_table, expected_table);
+        assert_eq!(instruction.accounts[0].pubkey, lookup_table);
+        assert_eq!(&instruction.data[..4], &[0, 0, 0, 0]);
+        assert_eq!(&instruction.data[4..12], &42u64.to_le_bytes());
+        assert_eq!(instruction.data[12], bump);
+        assert_eq!(instruction.data.len(), 13);
+    }
+
+    #[test]
+    fn test_extend_lookup_table_data() {
+        let lookup_table = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let addresses = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let instruction = extend_lookup_table(&lookup_table, &authority, &authority, &addresses);
+
+        assert_eq!(&instruction.data[..4], &[2, 0, 0, 0]);
+        assert_eq!(&instruction.data[4..12], &2u64.to_le_bytes());
+        assert_eq!(&instruction.data[12..44], addresses[0].as_ref());
+        assert_eq!(&instruction.data[44..76], addresses[1].as_ref());
+        assert_eq!(instruction.data.len(), 76);
+    }
+}
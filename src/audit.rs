@@ -0,0 +1,261 @@
+use crate::squads::{
+    CLOSE_TRANSACTION_ACCOUNTS_DISCRIMINATOR, CREATE_MULTISIG_V2_DISCRIMINATOR,
+    CREATE_PROPOSAL_DISCRIMINATOR, CREATE_TRANSACTION_DISCRIMINATOR,
+    EXECUTE_TRANSACTION_DISCRIMINATOR, PROPOSAL_ACTIVATE_DISCRIMINATOR,
+    PROPOSAL_APPROVE_DISCRIMINATOR, PROPOSAL_REJECT_DISCRIMINATOR,
+};
+use crate::utils::get_config_path;
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use solana_message::VersionedMessage;
+use solana_transaction::versioned::VersionedTransaction;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// One transaction the tool submitted, appended as a line of `audit.jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// When the outcome was known, RFC 3339 in UTC
+    pub timestamp: String,
+    /// Subcommand that sent the transaction
+    pub command: String,
+    /// RPC URL it was sent to
+    pub network: String,
+    pub signers: Vec<String>,
+    /// Squads instruction names (`proposal_approve`, ...), or the program
+    /// invoked for anything else. Compute budget instructions are left out.
+    pub instructions: Vec<String>,
+    pub multisigs: Vec<String>,
+    pub proposals: Vec<String>,
+    pub signature: Option<String>,
+    pub error: Option<String>,
+    /// SHA-256 of the previous line of the log, so an edited or removed entry
+    /// breaks the chain
+    pub prev_hash: Option<String>,
+}
+
+/// Recorded as every entry's `command`; set once in `main`
+static AUDIT_COMMAND: Mutex<Option<String>> = Mutex::new(None);
+
+/// Squads instructions by discriminator, with the positions of their
+/// multisig and proposal accounts
+const SQUADS_INSTRUCTIONS: [(&[u8], &str, usize, Option<usize>); 8] = [
+    (CREATE_MULTISIG_V2_DISCRIMINATOR, "multisig_create_v2", 2, None),
+    (CREATE_TRANSACTION_DISCRIMINATOR, "vault_transaction_create", 0, None),
+    (CREATE_PROPOSAL_DISCRIMINATOR, "proposal_create", 0, Some(1)),
+    (PROPOSAL_APPROVE_DISCRIMINATOR, "proposal_approve", 0, Some(2)),
+    (PROPOSAL_REJECT_DISCRIMINATOR, "proposal_reject", 0, Some(2)),
+    (PROPOSAL_ACTIVATE_DISCRIMINATOR, "proposal_activate", 0, Some(2)),
+    (EXECUTE_TRANSACTION_DISCRIMINATOR, "vault_transaction_execute", 0, Some(1)),
+    (CLOSE_TRANSACTION_ACCOUNTS_DISCRIMINATOR, "vault_transaction_accounts_close", 0, Some(1)),
+];
+
+/// Names the command recorded with each audit entry
+pub fn set_command(command: &str) {
+    *AUDIT_COMMAND.lock().unwrap_or_else(|e| e.into_inner()) = Some(command.to_string());
+}
+
+/// `~/.feature-gate-multisig-tool/audit.jsonl`, next to the config
+pub fn audit_path() -> Result<PathBuf> {
+    Ok(get_config_path()?.with_file_name("audit.jsonl"))
+}
+
+impl AuditEntry {
+    /// The entry for `transaction`, sent to `network`, that landed with a
+    /// signature or failed with an error
+    pub fn new(
+        transaction: &VersionedTransaction,
+        network: &str,
+        outcome: std::result::Result<&str, String>,
+    ) -> Self {
+        let message = &transaction.message;
+        let keys = message.static_account_keys();
+        let num_signers = usize::from(message.header().num_required_signatures);
+        let (instructions, multisigs, proposals) = describe_instructions(message);
+        let command = AUDIT_COMMAND.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        let (signature, error) = match outcome {
+            Ok(signature) => (Some(signature.to_string()), None),
+            Err(error) => (None, Some(error)),
+        };
+
+        Self {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            command: command.unwrap_or_else(|| "unknown".to_string()),
+            network: network.to_string(),
+            signers: keys.iter().take(num_signers).map(|key| key.to_string()).collect(),
+            instructions,
+            multisigs,
+            proposals,
+            signature,
+            error,
+            prev_hash: None,
+        }
+    }
+}
+
+/// Instruction names and the distinct multisig and proposal addresses the
+/// message's Squads instructions act on
+fn describe_instructions(message: &VersionedMessage) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let keys = message.static_account_keys();
+    let mut names = Vec::new();
+    let mut multisigs: Vec<String> = Vec::new();
+    let mut proposals: Vec<String> = Vec::new();
+    let add = |addresses: &mut Vec<String>, address: Option<String>| {
+        if let Some(address) = address.filter(|address| !addresses.contains(address)) {
+            addresses.push(address);
+        }
+    };
+
+    for instruction in message.instructions() {
+        let program_id = keys.get(usize::from(instruction.program_id_index));
+        if program_id == Some(&solana_compute_budget_interface::ID) {
+            continue;
+        }
+        let account_at = |position: usize| {
+            instruction
+                .accounts
+                .get(position)
+                .and_then(|index| keys.get(usize::from(*index)))
+                .map(|key| key.to_string())
+        };
+        match SQUADS_INSTRUCTIONS
+            .iter()
+            .find(|(discriminator, ..)| instruction.data.starts_with(discriminator))
+        {
+            Some((_, name, multisig_at, proposal_at)) => {
+                names.push(name.to_string());
+                add(&mut multisigs, account_at(*multisig_at));
+                add(&mut proposals, proposal_at.and_then(account_at));
+            }
+            None if program_id == Some(&solana_system_interface::program::ID) => {
+                names.push("system".to_string());
+            }
+            None => names.push(match program_id {
+                Some(program_id) => format!("program {}", program_id),
+                None => "unknown".to_string(),
+            }),
+        }
+    }
+    (names, multisigs, proposals)
+}
+
+fn line_hash(line: &str) -> String {
+    Sha256::digest(line.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Appends `entry` as one JSON line chained to the last one, creating the
+/// file if needed
+pub fn append_audit(path: &Path, mut entry: AuditEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let previous = match fs::read_to_string(path) {
+        Ok(contents) => contents.lines().rev().find(|line| !line.trim().is_empty()).map(line_hash),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(eyre::eyre!("Failed to read {}: {}", path.display(), e)),
+    };
+    entry.prev_hash = previous;
+
+    let line = serde_json::to_string(&entry)?;
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)?;
+    Ok(())
+}
+
+/// Records a sent transaction's outcome in the audit log. The transaction
+/// has already been sent, so failing to write it only warns.
+pub fn record(
+    transaction: &VersionedTransaction,
+    network: &str,
+    outcome: std::result::Result<&str, String>,
+) {
+    let entry = AuditEntry::new(transaction, network, outcome);
+    if let Err(e) = audit_path().and_then(|path| append_audit(&path, entry)) {
+        crate::output::Output::warning(&format!("Could not write the audit log: {}", e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::squads::{MultisigVoteOnProposalAccounts, SQUADS_MULTISIG_PROGRAM_ID};
+    use solana_instruction::Instruction;
+    use solana_message::v0::Message;
+    use solana_pubkey::Pubkey;
+    use solana_signature::Signature;
+
+    fn approve_transaction(
+        member: Pubkey,
+        multisig: Pubkey,
+        proposal: Pubkey,
+    ) -> VersionedTransaction {
+        let accounts = MultisigVoteOnProposalAccounts {
+            multisig,
+            member,
+            proposal,
+        };
+        let instructions = [
+            solana_compute_budget_interface::ComputeBudgetInstruction::set_compute_unit_price(1),
+            Instruction::new_with_bytes(
+                SQUADS_MULTISIG_PROGRAM_ID,
+                &[PROPOSAL_APPROVE_DISCRIMINATOR, &[0]].concat(),
+                accounts.to_account_metas(),
+            ),
+        ];
+        let blockhash = solana_hash::Hash::default();
+        let message = Message::try_compile(&member, &instructions, &[], blockhash).unwrap();
+        VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::V0(message),
+        }
+    }
+
+    #[test]
+    fn test_audit_entry_describes_squads_instructions() {
+        let (member, multisig, proposal) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let transaction = approve_transaction(member, multisig, proposal);
+
+        let entry = AuditEntry::new(&transaction, "https://api.devnet.solana.com", Ok("sig"));
+        assert_eq!(entry.signers, vec![member.to_string()]);
+        assert_eq!(entry.instructions, vec!["proposal_approve"]);
+        assert_eq!(entry.multisigs, vec![multisig.to_string()]);
+        assert_eq!(entry.proposals, vec![proposal.to_string()]);
+        assert_eq!(entry.signature.as_deref(), Some("sig"));
+        assert_eq!(entry.error, None);
+    }
+
+    #[test]
+    fn test_audit_log_is_hash_chained() {
+        let dir = std::env::temp_dir().join(format!("audit-test-{}", Pubkey::new_unique()));
+        let path = dir.join("audit.jsonl");
+        let transaction =
+            approve_transaction(Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        for outcome in [Ok("first"), Err("rejected".to_string()), Ok("third")] {
+            append_audit(&path, AuditEntry::new(&transaction, "http://localhost", outcome))
+                .unwrap();
+        }
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        let entries: Vec<AuditEntry> =
+            lines.iter().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(entries[0].prev_hash, None);
+        assert_eq!(entries[1].prev_hash, Some(line_hash(lines[0])));
+        assert_eq!(entries[2].prev_hash, Some(line_hash(lines[1])));
+        assert_eq!(entries[1].error.as_deref(), Some("rejected"));
+        // Editing an entry changes the hash the next one recorded
+        assert_ne!(
+            entries[2].prev_hash,
+            Some(line_hash(&lines[1].replace("rejected", "approved")))
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,83 @@
+use crate::error::ToolError;
+use crate::network::explorer_tx_url;
+use crate::output::Output;
+use crate::provision::{
+    create_activate_proposal_message, create_rpc_client, fetch_multisig,
+    get_account_data_with_retry, send_and_confirm_transaction, SendStrategy,
+};
+use crate::squads::{
+    get_proposal_pda, Permission, Proposal, ProposalStatus, SQUADS_MULTISIG_PROGRAM_ID,
+};
+use crate::utils::*;
+use borsh::BorshDeserialize;
+use eyre::Result;
+use solana_message::VersionedMessage;
+use solana_signer::Signer;
+use solana_transaction::versioned::VersionedTransaction;
+
+/// Opens voting on a proposal created with `--draft`
+pub async fn activate_proposal_command(
+    config: &Config,
+    address: String,
+    transaction_index: u64,
+    rpc_url: Option<String>,
+    keypair_path: Option<String>,
+) -> Result<()> {
+    let multisig_address = config.resolve_multisig(&address)?;
+    let rpc_url = match rpc_url {
+        Some(url) => validate_rpc_url(&url)?,
+        None => choose_network_from_config(config)?,
+    };
+    let rpc_client = create_rpc_client(&rpc_url);
+
+    let member = load_fee_payer_keypair(config, keypair_path)?.ok_or_else(|| {
+        eyre::eyre!("A member keypair is required to activate a proposal; pass --keypair")
+    })?;
+
+    let multisig = fetch_multisig(&rpc_client, &multisig_address)?;
+    if !multisig.has_permission(&member.pubkey(), Permission::Initiate) {
+        Output::warning(&format!(
+            "{} cannot initiate transactions on this multisig; activation will fail",
+            member.pubkey()
+        ));
+    }
+
+    let proposal_pda = get_proposal_pda(&multisig_address, transaction_index, None).0;
+    let data = get_account_data_with_retry(&rpc_client, &proposal_pda)
+        .map_err(|e| eyre::eyre!("Failed to fetch proposal {}: {}", transaction_index, e))?;
+    if data.len() < 8 {
+        return Err(eyre::eyre!("Proposal account data too small"));
+    }
+    let proposal = Proposal::deserialize(&mut &data[8..])
+        .map_err(|e| eyre::eyre!("Failed to deserialize proposal: {}", e))?;
+    if !matches!(proposal.status, ProposalStatus::Draft { .. }) {
+        return Err(eyre::eyre!(
+            "Proposal {} is {}, only draft proposals can be activated",
+            transaction_index,
+            proposal.status.name()
+        ));
+    }
+
+    let blockhash = rpc_client
+        .get_latest_blockhash()
+        .map_err(|e| ToolError::Network(format!("Failed to get recent blockhash: {}", e)))?;
+    let message = create_activate_proposal_message(
+        &SQUADS_MULTISIG_PROGRAM_ID,
+        &multisig_address,
+        transaction_index,
+        &member.pubkey(),
+        &member.pubkey(),
+        blockhash,
+    )?;
+    let transaction = VersionedTransaction::try_new(VersionedMessage::V0(message), &[&member])?;
+    let signature =
+        send_and_confirm_transaction(&transaction, &rpc_client, SendStrategy::default())?;
+
+    Output::success(&format!(
+        "Proposal {} is now active and open for voting: {}",
+        transaction_index, signature
+    ));
+    Output::field("Explorer", &explorer_tx_url(&signature, &rpc_url));
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use crate::error::ToolError;
+use crate::network::NetworkCluster;
+use crate::output::Output;
+use crate::provision::create_nonblocking_rpc_client;
+use crate::utils::*;
+use eyre::Result;
+use std::time::{Duration, Instant};
+use tabled::Tabled;
+
+/// Times `get_latest_blockhash` over `samples` round trips and checks
+/// `get_health` for each saved network (or just `rpc_url`), listing them
+/// fastest first. Read-only, so safe to run before any deployment.
+pub async fn bench_command(config: &Config, samples: usize, rpc_url: Option<String>) -> Result<()> {
+    if samples == 0 {
+        return Err(eyre::eyre!("--samples must be at least 1"));
+    }
+    let networks = match rpc_url {
+        Some(url) => vec![validate_rpc_url(&url)?],
+        None if config.networks.is_empty() => {
+            return Err(ToolError::Config(
+                "No networks configured; pass --url <RPC_URL>".to_string(),
+            )
+            .into())
+        }
+        None => config.networks.clone(),
+    };
+
+    Output::header(&format!(
+        "⏱️ Benchmarking {} network(s) over {} sample(s)",
+        networks.len(),
+        samples
+    ));
+
+    // Networks are measured concurrently; samples within one run back to back
+    let mut benches = tokio::task::JoinSet::new();
+    for rpc_url in networks {
+        benches.spawn(async move {
+            let rpc_client = create_nonblocking_rpc_client(&rpc_url);
+            let health = match rpc_client.get_health().await {
+                Ok(()) => "healthy".to_string(),
+                Err(e) => format!("unhealthy: {}", e),
+            };
+            let mut latencies = Vec::with_capacity(samples);
+            let mut failures = 0;
+            for _ in 0..samples {
+                let start = Instant::now();
+                match rpc_client.get_latest_blockhash().await {
+                    Ok(_) => latencies.push(start.elapsed()),
+                    Err(_) => failures += 1,
+                }
+            }
+            (rpc_url, health, latencies, failures)
+        });
+    }
+
+    #[derive(Tabled)]
+    struct BenchRow {
+        #[tabled(rename = "Network")]
+        network: String,
+        #[tabled(rename = "Cluster")]
+        cluster: &'static str,
+        #[tabled(rename = "Health")]
+        health: String,
+        #[tabled(rename = "Min (ms)")]
+        min: String,
+        #[tabled(rename = "Avg (ms)")]
+        avg: String,
+        #[tabled(rename = "Max (ms)")]
+        max: String,
+        #[tabled(rename = "Failed")]
+        failed: String,
+    }
+
+    let mut results = Vec::new();
+    while let Some(result) = benches.join_next().await {
+        results.push(result?);
+    }
+    // Fastest average first; networks with no successful sample last
+    results.sort_by_key(|(_, _, latencies, _)| average(latencies).unwrap_or(Duration::MAX));
+
+    let as_ms = |latency: Option<Duration>| {
+        latency.map_or_else(|| "-".to_string(), |d| d.as_millis().to_string())
+    };
+    let rows: Vec<BenchRow> = results
+        .into_iter()
+        .map(|(rpc_url, health, latencies, failures)| BenchRow {
+            cluster: NetworkCluster::from_url(&rpc_url).label(),
+            network: rpc_url,
+            health,
+            min: as_ms(latencies.iter().min().copied()),
+            avg: as_ms(average(&latencies)),
+            max: as_ms(latencies.iter().max().copied()),
+            failed: format!("{}/{}", failures, samples),
+        })
+        .collect();
+
+    Output::table(&rows);
+    Output::hint(
+        "Deployments follow the order of `networks` in ~/.feature-gate-multisig-tool/config.json",
+    );
+    Ok(())
+}
+
+fn average(latencies: &[Duration]) -> Option<Duration> {
+    let count = u32::try_from(latencies.len()).ok().filter(|count| *count > 0)?;
+    Some(latencies.iter().sum::<Duration>() / count)
+}
@@ -0,0 +1,124 @@
+use crate::error::ToolError;
+use crate::network::explorer_tx_url;
+use crate::output::Output;
+use crate::provision::{
+    create_close_transaction_message, create_rpc_client, fetch_multisig,
+    get_account_data_with_retry, send_and_confirm_transaction, SendStrategy,
+};
+use crate::squads::{get_proposal_pda, Proposal, SQUADS_MULTISIG_PROGRAM_ID};
+use crate::utils::*;
+use borsh::BorshDeserialize;
+use eyre::Result;
+use inquire::Confirm;
+use solana_message::VersionedMessage;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::versioned::VersionedTransaction;
+
+/// Closes the vault transaction and proposal accounts at `transaction_index`
+/// once the proposal is finished (or stale), returning their rent
+pub async fn close_proposal_command(
+    config: &Config,
+    address: String,
+    transaction_index: u64,
+    rpc_url: Option<String>,
+    keypair_path: Option<String>,
+    rent_collector_override: Option<Pubkey>,
+    yes: bool,
+) -> Result<()> {
+    let multisig_address = config.resolve_multisig(&address)?;
+    let rpc_url = match rpc_url {
+        Some(url) => validate_rpc_url(&url)?,
+        None => choose_network_from_config(config)?,
+    };
+    let rpc_client = create_rpc_client(&rpc_url);
+
+    let fee_payer = load_fee_payer_keypair(config, keypair_path)?.ok_or_else(|| {
+        eyre::eyre!("A keypair is required to pay for the close transaction; pass --keypair")
+    })?;
+
+    let multisig = fetch_multisig(&rpc_client, &multisig_address)?;
+
+    // The program only ever pays rent to the collector configured on the multisig
+    let rent_collector = match (multisig.rent_collector, rent_collector_override) {
+        (None, _) => {
+            return Err(eyre::eyre!(
+                "Multisig {} has no rent collector, so the Squads program won't close its accounts",
+                multisig_address
+            ))
+        }
+        (Some(configured), Some(requested)) if configured != requested => {
+            return Err(eyre::eyre!(
+                "--rent-collector {} doesn't match the multisig's rent collector {}; \
+                 the Squads program only returns rent to the configured collector",
+                requested,
+                configured
+            ))
+        }
+        (Some(configured), _) => configured,
+    };
+
+    let proposal_pda = get_proposal_pda(&multisig_address, transaction_index, None).0;
+    let data = get_account_data_with_retry(&rpc_client, &proposal_pda)
+        .map_err(|e| eyre::eyre!("Failed to fetch proposal {}: {}", transaction_index, e))?;
+    if data.len() < 8 {
+        return Err(eyre::eyre!("Proposal account data too small"));
+    }
+    let proposal = Proposal::deserialize(&mut &data[8..])
+        .map_err(|e| eyre::eyre!("Failed to deserialize proposal: {}", e))?;
+
+    let is_stale = multisig.is_stale(transaction_index);
+    if !proposal.status.is_closeable(is_stale) {
+        return Err(eyre::eyre!(
+            "Proposal {} is {}{} (stale transaction index is {}); only executed, rejected, \
+             cancelled or stale unapproved proposals can be closed, and an approved one can \
+             still be executed",
+            transaction_index,
+            proposal.status.name(),
+            if is_stale { " and stale" } else { " and not stale" },
+            multisig.stale_transaction_index
+        ));
+    }
+
+    Output::header("🧹 Close Proposal");
+    Output::field("Multisig", &multisig_address.to_string());
+    Output::field("Transaction Index", &transaction_index.to_string());
+    Output::field(
+        "Status",
+        &format!(
+            "{}{}",
+            proposal.status.name(),
+            if is_stale { " (stale)" } else { "" }
+        ),
+    );
+    Output::field("Rent Collector", &rent_collector.to_string());
+
+    if !yes {
+        let proceed = Confirm::new("Close the transaction and proposal accounts?")
+            .with_default(false)
+            .prompt()?;
+        if !proceed {
+            return Err(ToolError::Cancelled("User aborted".to_string()).into());
+        }
+    }
+
+    let blockhash = rpc_client
+        .get_latest_blockhash()
+        .map_err(|e| ToolError::Network(format!("Failed to get recent blockhash: {}", e)))?;
+    let message = create_close_transaction_message(
+        &SQUADS_MULTISIG_PROGRAM_ID,
+        &multisig_address,
+        transaction_index,
+        &rent_collector,
+        &fee_payer.pubkey(),
+        blockhash,
+    )?;
+    let transaction = VersionedTransaction::try_new(VersionedMessage::V0(message), &[&fee_payer])?;
+    let signature =
+        send_and_confirm_transaction(&transaction, &rpc_client, SendStrategy::default())?;
+
+    Output::success(&format!("Closed proposal {}: {}", transaction_index, signature));
+    Output::field("Explorer", &explorer_tx_url(&signature, &rpc_url));
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use crate::utils::*;
 use crate::output::Output;
 use eyre::Result;
+use inquire::Confirm;
+use std::fs;
+use std::path::Path;
 
 pub async fn config_command(config: &Config) -> Result<()> {
     let config_path = get_config_path()?;
@@ -15,8 +18,29 @@ pub async fn config_command(config: &Config) -> Result<()> {
         }
     }
 
+    let default_permissions = decode_permissions(config.default_member_permissions);
+    Output::field(
+        "Default member permissions",
+        &format!(
+            "{} ({})",
+            config.default_member_permissions,
+            if default_permissions.is_empty() {
+                "none".to_string()
+            } else {
+                default_permissions.join(", ")
+            }
+        ),
+    );
+
     Output::separator();
     Output::field("Threshold", &config.threshold.to_string());
+    Output::field(
+        "RPC rate limit",
+        &config
+            .rps
+            .map(|rps| format!("{} requests/s", rps))
+            .unwrap_or_else(|| "unlimited".to_string()),
+    );
 
     // Display fee payer path
     Output::separator();
@@ -37,3 +61,71 @@ pub async fn config_command(config: &Config) -> Result<()> {
 
     Ok(())
 }
+
+/// Writes the config for sharing with a team, without the machine-specific
+/// fee payer path
+pub async fn export_config_command(config: &Config, path: &Path) -> Result<()> {
+    let mut shared = config.clone();
+    shared.fee_payer_path = None;
+    let contents = serde_json::to_string_pretty(&shared)
+        .map_err(|e| eyre::eyre!("Failed to serialize config: {}", e))?;
+    fs::write(path, contents)
+        .map_err(|e| eyre::eyre!("Failed to write {}: {}", path.display(), e))?;
+
+    Output::success(&format!("Exported config to {}", path.display()));
+    Output::hint("The fee payer path was left out; each participant keeps their own");
+    Ok(())
+}
+
+/// Merges a config exported by a teammate into the local one, asking which
+/// side wins wherever the two disagree
+pub async fn import_config_command(config: &mut Config, path: &Path) -> Result<()> {
+    let imported = read_config_file(path)?;
+
+    let mut prompt_error = None;
+    let merged = merge_config(config, imported, |conflict| {
+        if prompt_error.is_some() {
+            return false;
+        }
+        let question = match conflict {
+            ConfigConflict::MemberPermissions { member, local, imported } => format!(
+                "Member {} has permissions {} locally but {} in the import. Use the imported permissions?",
+                member, local, imported
+            ),
+            ConfigConflict::Label { label, local, imported } => format!(
+                "Label '{}' names {} locally but {} in the import. Move the label to the imported multisig?",
+                label, local, imported
+            ),
+        };
+        Confirm::new(&question)
+            .with_default(false)
+            .prompt()
+            .unwrap_or_else(|e| {
+                prompt_error = Some(e);
+                false
+            })
+    });
+    if let Some(e) = prompt_error {
+        return Err(e.into());
+    }
+
+    Output::header(&format!("📥 Importing {}", path.display()));
+    Output::field(
+        "Members",
+        &format!("{} -> {}", config.members.len(), merged.members.len()),
+    );
+    Output::field(
+        "Networks",
+        &format!("{} -> {}", config.networks.len(), merged.networks.len()),
+    );
+    Output::field(
+        "Saved multisigs",
+        &format!("{} -> {}", config.multisigs.len(), merged.multisigs.len()),
+    );
+    Output::field("Threshold", &format!("{} -> {}", config.threshold, merged.threshold));
+
+    save_config(&merged)?;
+    *config = merged;
+    Output::success("Config updated");
+    Ok(())
+}
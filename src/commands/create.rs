@@ -1,118 +1,1081 @@
 use crate::output::Output;
-use crate::provision::create_multisig;
-use crate::squads::{get_proposal_pda, get_vault_pda, Member, Permissions};
+use crate::constants::LAMPORTS_PER_SOL;
+use crate::error::ToolError;
+use crate::network::{explorer_tx_url, NetworkCluster};
+use crate::plan::CreationPlan;
+use crate::history::{append_history, history_path, HistoryEntry};
+use crate::spec::{MultisigSpec, ValidatedSpec};
+use crate::provision::{
+    create_multisig, create_rpc_client, ensure_feature_funding, fetch_multisig,
+    needs_mainnet_confirmation, prefund_vault, verify_created_multisig, verify_program,
+    RetryBudget, TransactionOptions,
+};
+use crate::squads::{
+    get_proposal_pda, get_vault_pda, Member, MultisigCreateArgsV2, Permission, Permissions,
+};
 use crate::utils::*;
 use colored::*;
-use eyre::Result;
+use eyre::{Result, WrapErr};
 use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
 use solana_signer::Signer;
+use std::path::{Path, PathBuf};
+use tabled::Tabled;
+
+/// How the result of a deployment is reported
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum SummaryFormat {
+    /// Full human-readable summary
+    #[default]
+    Pretty,
+    /// One tab-separated line per deployment
+    Compact,
+    /// Deployment and member rows as CSV
+    Csv,
+}
+
+impl SummaryFormat {
+    /// The format actually printed: `--quiet` reduces the pretty summary to
+    /// the compact one
+    fn resolved(self) -> Self {
+        if self == SummaryFormat::Pretty && Output::is_quiet() {
+            SummaryFormat::Compact
+        } else {
+            self
+        }
+    }
+}
+
+/// Optional behaviour for `create_command` that is only reachable via CLI flags
+#[derive(Debug, Clone, Default)]
+pub struct CreateOptions {
+    /// Top up the default vault to this many SOL after the multisig is created
+    pub prefund_vault_sol: Option<f64>,
+    pub transaction: TransactionOptions,
+    /// Hex SHA-256 the target Squads program's executable must match
+    pub expected_program_hash: Option<String>,
+    /// Accept a non-canonical program without `expected_program_hash` off
+    /// localnet
+    pub allow_unverified_program: bool,
+    /// Write the full creation plan here before sending anything
+    pub plan_out: Option<PathBuf>,
+    /// Stop after writing `plan_out`
+    pub plan_only: bool,
+    /// Execute a previously written plan instead of collecting parameters
+    pub plan_in: Option<PathBuf>,
+    /// Skip confirmation prompts
+    pub yes: bool,
+    /// Human-readable name saved in the config alongside the created multisig
+    pub label: Option<String>,
+    /// Stop after creating the multisig; proposals are added later with
+    /// `create-proposals`
+    pub no_proposals: bool,
+    /// Skip the activation proposal; the revocation takes index 1
+    pub no_activation: bool,
+    /// Skip the revocation proposal
+    pub no_revocation: bool,
+    /// Directory of fee payer keypairs, used round-robin across networks
+    /// instead of a single `--keypair`
+    pub fee_payer_dir: Option<PathBuf>,
+    /// How the deployments are summarized once sent
+    pub summary_format: SummaryFormat,
+    /// Create this many independent multisigs with the same members,
+    /// threshold and networks (one when unset)
+    pub count: Option<usize>,
+    /// Use this keypair as the create key instead of a fresh one, so the
+    /// multisig address is known in advance
+    pub create_key_path: Option<PathBuf>,
+    /// Derive the create key from this seed (see `derive_keypair_from_seed`),
+    /// so the same seed always gives the same multisig address
+    pub create_key_seed: Option<String>,
+    /// Also derive the contributor (setup member) from `create_key_seed`, so
+    /// it can be rebuilt to initiate later proposals
+    pub deterministic_contributor: bool,
+    /// Add the fee payer as a full member in place of the throwaway setup
+    /// key, so it both pays and votes
+    pub fee_payer_is_member: bool,
+    /// Copy the members (and, without `--threshold`, the threshold) of this
+    /// existing multisig instead of collecting them
+    pub members_from_multisig: Option<String>,
+    /// Give every member copied by `members_from_multisig` this permission
+    /// mask instead of the one it has in the source multisig
+    pub member_permissions: Option<u8>,
+    /// Like `members_from_multisig`, also copying the time lock and config
+    /// authority of this existing multisig
+    pub template: Option<String>,
+    /// Show members sorted by pubkey instead of in entry order. Squads sorts
+    /// them on-chain regardless, so only the displayed order changes
+    pub members_sort: bool,
+    /// Read each created multisig back and fail unless its members and
+    /// threshold are exactly the ones requested
+    pub verify_after_create: bool,
+    /// Take threshold, members, networks and governance settings from this
+    /// spec file instead of prompting for them
+    pub spec: Option<PathBuf>,
+    /// Leave the saved config (default threshold and members, saved
+    /// multisigs) untouched after deploying
+    pub no_save_config: bool,
+}
+
+/// Multisig settings beyond members and threshold, copied from a `--template`
+/// or read from a `--spec`
+#[derive(Debug, Clone, Copy, Default)]
+struct GovernanceSettings {
+    time_lock: u32,
+    config_authority: Option<Pubkey>,
+    rent_collector: Option<Pubkey>,
+}
+
+impl CreateOptions {
+    /// Whether the create key comes from the user, and so may already have
+    /// been used for a multisig
+    fn reuses_create_key(&self) -> bool {
+        self.create_key_path.is_some() || self.create_key_seed.is_some()
+    }
+
+    /// The feature gate proposals to create after the multisig, in index order
+    fn proposal_types(&self) -> Vec<TransactionType> {
+        if self.no_proposals {
+            return Vec::new();
+        }
+        feature_gate_proposal_types(!self.no_activation, !self.no_revocation)
+    }
+}
 
 pub async fn create_command(
     config: &mut Config,
     threshold: Option<u16>,
     _sub_multisigs: Vec<String>,
     keypair_path: Option<String>,
+    options: &CreateOptions,
 ) -> Result<()> {
-    println!(
+    Output::plain(&format!(
         "{}",
         "🚀 Creating feature gate multisig configuration"
             .bright_cyan()
             .bold()
-    );
+    ));
+
+    let spec = options.spec.as_deref().map(MultisigSpec::load).transpose()?;
+    // A spec's label and create key seed apply unless given as flags
+    let spec_options = spec.as_ref().map(|spec| CreateOptions {
+        label: options.label.clone().or_else(|| spec.label.clone()),
+        create_key_seed: options.create_key_seed.clone().or_else(|| spec.create_key_seed.clone()),
+        ..options.clone()
+    });
+    let options = spec_options.as_ref().unwrap_or(options);
+
+    if let Some(label) = &options.label {
+        check_label(config, label)?;
+        // A batch saves each multisig under a numbered label instead
+        for number in 1..=options.count.filter(|count| *count > 1).unwrap_or(0) {
+            check_label(config, &batch_label(label, number))?;
+        }
+    }
+
+    if let Some(plan_path) = &options.plan_in {
+        if !options.yes {
+            ensure_interactive("pass --yes to skip confirmation prompts")?;
+        }
+        return execute_plan(plan_path, config, keypair_path, options).await;
+    }
+    if spec.is_none() || !options.yes {
+        ensure_interactive(
+            "use --plan-in <PATH> or --spec <PATH> with --yes to create a multisig without prompts",
+        )?;
+    }
 
     // Collect configuration and members
-    let (final_threshold, mut members) = review_and_collect_configuration(config, threshold)?;
+    let (final_threshold, mut members, governance) = match (
+        &spec,
+        &options.template,
+        &options.members_from_multisig,
+    ) {
+        (Some(spec), _, _) => (
+            spec.threshold,
+            spec.members.clone(),
+            GovernanceSettings {
+                time_lock: spec.time_lock,
+                config_authority: spec.config_authority,
+                rent_collector: spec.rent_collector,
+            },
+        ),
+        (None, Some(source), _) => {
+            import_members_from_multisig(config, source, threshold, true, None)?
+        }
+        (None, None, Some(source)) => import_members_from_multisig(
+            config,
+            source,
+            threshold,
+            false,
+            options.member_permissions,
+        )?,
+        (None, None, None) => {
+            let (threshold, members) = review_and_collect_configuration(config, threshold)?;
+            (threshold, members, GovernanceSettings::default())
+        }
+    };
 
-    // Load fee payer keypair from CLI arg or config
-    let fee_payer_keypair = load_fee_payer_keypair(config, keypair_path)?;
+    // Load fee payer keypair(s) from CLI args or config
+    let fee_payers = load_fee_payers(config, keypair_path, options)?;
 
-    // Create setup keypair (always separate from fee payer)
-    let setup_keypair = Keypair::new();
+    if let Some(count) = options.count.filter(|count| *count > 1) {
+        return create_batch(
+            config,
+            count,
+            (final_threshold, governance),
+            members,
+            spec.map(|spec| spec.networks),
+            &fee_payers,
+            options,
+        )
+        .await;
+    }
+
+    // Create setup keypair (separate from the fee payer unless it is to be a member)
+    let setup_keypair = match &options.create_key_seed {
+        _ if options.fee_payer_is_member => member_fee_payer(&fee_payers, &members)?,
+        Some(seed) if options.deterministic_contributor => {
+            let contributor = derive_keypair_from_seed(CONTRIBUTOR_SEED_DOMAIN, seed);
+            Output::info(&format!(
+                "Using contributor {} derived from the create key seed",
+                contributor.pubkey()
+            ));
+            contributor
+        }
+        _ => Keypair::new(),
+    };
     let setup_pubkey = setup_keypair.pubkey();
 
     // Create a persistent create_key for all deployments
-    let create_key = Keypair::new();
+    let create_key = match (&options.create_key_path, &options.create_key_seed) {
+        (Some(path), _) => load_create_key(path, options)?,
+        (None, Some(seed)) => derive_create_key(seed, options),
+        (None, None) => Keypair::new(),
+    };
 
-    // Add contributor as a member with permission 1 (bitmask for Initiate only)
-    members.insert(
-        0,
-        Member {
-            key: setup_pubkey,
-            permissions: Permissions { mask: 1 },
-        },
+    // Add contributor as a member: Initiate only, or every permission when it
+    // is the fee payer
+    members.insert(0, setup_member(setup_pubkey, options.fee_payer_is_member));
+    if options.members_sort {
+        sort_members(&mut members);
+    }
+
+    display_final_configuration(
+        &setup_pubkey,
+        &create_key.pubkey(),
+        &options
+            .transaction
+            .program_id
+            .unwrap_or(crate::squads::SQUADS_MULTISIG_PROGRAM_ID),
+        &fee_payers,
+        final_threshold,
+        &members,
     );
+    if !options.reuses_create_key() && options.plan_out.is_none() {
+        Output::warning(
+            "The create key is generated for this run only and not saved, so this address \
+             can't be deployed to more networks later; pass --create-key-path or \
+             --create-key-seed to keep it",
+        );
+    }
 
-    // // Display final configuration
-    // display_final_configuration(
-    //     &contributor_pubkey,
-    //     &create_key.pubkey(),
-    //     &fee_payer_keypair,
-    //     final_threshold,
-    //     &members,
-    // );
+    let args = MultisigCreateArgsV2 {
+        config_authority: governance.config_authority,
+        threshold: final_threshold,
+        members,
+        time_lock: governance.time_lock,
+        rent_collector: governance.rent_collector,
+        memo: None,
+    };
 
     // Determine network deployment mode and deploy
-    let (use_saved_networks, saved_networks) = choose_network_mode(config, true)?;
+    let networks = match spec {
+        Some(spec) => Some(spec.networks),
+        None => {
+            let (use_saved_networks, saved_networks) = choose_network_mode(config, true)?;
 
-    // Check fee payer balance on all networks before deployment
-    if use_saved_networks && !saved_networks.is_empty() {
-        let fee_payer_pubkey = fee_payer_keypair
-            .as_ref()
-            .map(|kp| kp.pubkey())
-            .unwrap_or_else(|| setup_keypair.pubkey());
+            // A plan needs every network up front, so collect manual ones before deploying
+            if use_saved_networks && !saved_networks.is_empty() {
+                Some(saved_networks)
+            } else if options.plan_out.is_some() {
+                Some(collect_networks_interactively(config)?)
+            } else {
+                None
+            }
+        }
+    };
 
-        check_fee_payer_balance_on_networks(&fee_payer_pubkey, &saved_networks, 0.05).await?;
+    if let Some(plan_path) = &options.plan_out {
+        let program_id = options
+            .transaction
+            .program_id
+            .unwrap_or(crate::squads::SQUADS_MULTISIG_PROGRAM_ID);
+        let plan = CreationPlan::new(
+            &program_id,
+            &create_key.pubkey(),
+            &args,
+            networks.as_deref().unwrap_or_default(),
+        )?;
+        let keys_path = plan.save(plan_path, &create_key, &setup_keypair)?;
+        Output::success(&format!("Creation plan written to {}", plan_path.display()));
+        Output::warning(&format!(
+            "{} holds the create and setup secret keys; keep it with the plan but don't publish it",
+            keys_path.display()
+        ));
+
+        if options.plan_only {
+            Output::hint(&format!(
+                "Review the plan, then run: feature-gate-multisig-tool create --plan-in {}",
+                plan_path.display()
+            ));
+            return Ok(());
+        }
     }
 
-    let deployments = if use_saved_networks && !saved_networks.is_empty() {
-        deploy_to_saved_networks(
-            &saved_networks,
-            &create_key,
-            &setup_keypair,
-            &fee_payer_keypair,
-            &members,
-            final_threshold,
-        )
-        .await?
-    } else {
-        deploy_to_manual_networks(
-            config,
-            &create_key,
-            &setup_keypair,
-            &fee_payer_keypair,
-            &members,
-            final_threshold,
-        )
-        .await?
+    let deployments = match networks {
+        Some(networks) => {
+            // Check fee payer balance on all networks before deployment
+            // Only the paying key needs SOL; the setup member just signs
+            let required_sol = 0.05 + options.prefund_vault_sol.unwrap_or(0.0);
+            check_fee_payer_balance_on_networks(
+                &paying_pubkeys(&fee_payers, &setup_keypair),
+                &networks,
+                required_sol,
+            )
+            .await?;
+
+            deploy_to_saved_networks(
+                &networks,
+                &create_key,
+                &setup_keypair,
+                &fee_payers,
+                &args,
+                options,
+            )
+            .await?
+        }
+        None => {
+            deploy_to_manual_networks(
+                config,
+                &create_key,
+                &setup_keypair,
+                &fee_payers,
+                &args,
+                options,
+            )
+            .await?
+        }
     };
 
     // Print summary table
-    print_deployment_summary(
-        &deployments,
-        &members,
-        final_threshold,
-        &create_key.pubkey(),
-    );
+    match options.summary_format.resolved() {
+        SummaryFormat::Compact => print_compact_summary(&deployments),
+        SummaryFormat::Csv => print_csv_summary(&deployments, &args.members),
+        SummaryFormat::Pretty => print_deployment_summary(
+            &deployments,
+            &args.members,
+            args.threshold,
+            options.label.as_deref(),
+            options.transaction.program_id.as_ref(),
+            &options.proposal_types(),
+        ),
+    }
 
     // Save updated configuration (excluding contributor key)
     if !deployments.is_empty() {
-        config.threshold = final_threshold;
-        config.members = members
+        remember_deployments(config, &deployments, options.label.as_deref());
+        record_history(&deployments, &args.members, args.threshold, options.label.as_deref());
+        if options.no_save_config {
+            Output::info("Configuration not saved (--no-save-config)");
+            return Ok(());
+        }
+        let members: Vec<Member> = args
+            .members
             .iter()
-            .skip(1) // Skip contributor (index 0)
-            .map(|member| member.key.to_string())
+            .filter(|member| member.key != setup_pubkey) // Skip contributor
+            .cloned()
             .collect();
-
-        save_config(config)?;
-        println!(
+        save_deployment_config(config, args.threshold, &members, options)?;
+        Output::plain(&format!(
             "\n{} Configuration saved for future use",
             "💾".bright_green()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Deploys exactly what a `--plan-out` file describes, after checking the
+/// program and treasury on every network still match it
+async fn execute_plan(
+    plan_path: &Path,
+    config: &mut Config,
+    keypair_path: Option<String>,
+    options: &CreateOptions,
+) -> Result<()> {
+    let (plan, create_key, setup_keypair) = CreationPlan::load(plan_path)?;
+    let program_id = plan.program_id()?;
+    if let Some(requested) = options.transaction.program_id {
+        if requested != program_id {
+            return Err(eyre::eyre!(
+                "--program-id {} doesn't match the plan's program {}",
+                requested,
+                program_id
+            ));
+        }
+    }
+    let args = plan.create_args()?;
+    let networks = plan.network_urls();
+
+    Output::info(&format!("Executing creation plan {}", plan_path.display()));
+    Output::field("Multisig", &plan.multisig);
+    Output::field("Feature Gate ID", &plan.vault);
+    Output::field("Networks", &networks.join(", "));
+    Output::separator();
+
+    plan.verify_on_chain()?;
+    Output::success("On-chain program config matches the plan");
+    Output::separator();
+
+    let options = CreateOptions {
+        transaction: TransactionOptions {
+            program_id: Some(program_id),
+            ..options.transaction
+        },
+        ..options.clone()
+    };
+
+    let fee_payers = load_fee_payers(config, keypair_path, &options)?;
+    let required_sol = 0.05 + options.prefund_vault_sol.unwrap_or(0.0);
+    check_fee_payer_balance_on_networks(
+        &paying_pubkeys(&fee_payers, &setup_keypair),
+        &networks,
+        required_sol,
+    )
+    .await?;
+
+    let deployments = deploy_to_saved_networks(
+        &networks,
+        &create_key,
+        &setup_keypair,
+        &fee_payers,
+        &args,
+        &options,
+    )
+    .await?;
+
+    match options.summary_format.resolved() {
+        SummaryFormat::Compact => print_compact_summary(&deployments),
+        SummaryFormat::Csv => print_csv_summary(&deployments, &args.members),
+        SummaryFormat::Pretty => print_deployment_summary(
+            &deployments,
+            &args.members,
+            args.threshold,
+            options.label.as_deref(),
+            Some(&program_id),
+            &options.proposal_types(),
+        ),
+    }
+
+    if !deployments.is_empty() {
+        remember_deployments(config, &deployments, options.label.as_deref());
+        record_history(&deployments, &args.members, args.threshold, options.label.as_deref());
+        if !options.no_save_config {
+            save_config(config)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates `count` independent multisigs, each with its own create and setup
+/// keys, deploying them one after another to the same networks. The keys are
+/// saved so each multisig's setup member can still initiate transactions.
+/// `networks` are prompted for when not given.
+async fn create_batch(
+    config: &mut Config,
+    count: usize,
+    (threshold, governance): (u16, GovernanceSettings),
+    members: Vec<Member>,
+    networks: Option<Vec<String>>,
+    fee_payers: &[Option<Keypair>],
+    options: &CreateOptions,
+) -> Result<()> {
+    let networks = match networks {
+        Some(networks) => networks,
+        None => {
+            let (use_saved_networks, saved_networks) = choose_network_mode(config, true)?;
+            if use_saved_networks && !saved_networks.is_empty() {
+                saved_networks
+            } else {
+                collect_networks_interactively(config)?
+            }
+        }
+    };
+
+    let keys_dir = get_config_path()?.with_file_name("keys");
+    std::fs::create_dir_all(&keys_dir)
+        .map_err(|e| eyre::eyre!("Failed to create {}: {}", keys_dir.display(), e))?;
+
+    Output::info(&format!(
+        "Each of the {} multisigs gets a freshly generated create key and setup keypair, \
+         saved in {}",
+        count,
+        keys_dir.display()
+    ));
+
+    // Every multisig pays its own creation and proposal fees
+    let required_sol = (0.05 + options.prefund_vault_sol.unwrap_or(0.0)) * count as f64;
+    let setup_keypairs: Vec<Keypair> = (0..count).map(|_| Keypair::new()).collect();
+    check_fee_payer_balance_on_networks(
+        &paying_pubkeys(fee_payers, &setup_keypairs[0]),
+        &networks,
+        required_sol,
+    )
+    .await?;
+
+    #[derive(Tabled)]
+    struct BatchRow {
+        #[tabled(rename = "#")]
+        number: usize,
+        #[tabled(rename = "Network")]
+        network: &'static str,
+        #[tabled(rename = "Multisig")]
+        multisig: String,
+        #[tabled(rename = "Feature Gate ID")]
+        feature_gate_id: String,
+    }
+
+    let mut all_deployments = Vec::new();
+    let mut rows = Vec::new();
+    let mut failures = Vec::new();
+    for (i, setup_keypair) in setup_keypairs.iter().enumerate() {
+        let create_key = Keypair::new();
+        let mut members = members.clone();
+        members.insert(
+            0,
+            Member {
+                key: setup_keypair.pubkey(),
+                permissions: Permissions::from_actions(&[Permission::Initiate]),
+            },
         );
+        if options.members_sort {
+            sort_members(&mut members);
+        }
+        let args = MultisigCreateArgsV2 {
+            config_authority: governance.config_authority,
+            threshold,
+            members,
+            time_lock: governance.time_lock,
+            rent_collector: governance.rent_collector,
+            memo: None,
+        };
+
+        let program_id = options
+            .transaction
+            .program_id
+            .unwrap_or(crate::squads::SQUADS_MULTISIG_PROGRAM_ID);
+        let multisig = crate::squads::get_multisig_pda(&create_key.pubkey(), Some(&program_id)).0;
+        let keys_path = keys_dir.join(format!("{}.keys.json", multisig));
+        crate::plan::save_keys(&keys_path, &create_key, setup_keypair)?;
+
+        Output::separator();
+        Output::header(&format!("🏭 Multisig {} of {}: {}", i + 1, count, multisig));
+        let deployments = match deploy_to_saved_networks(
+            &networks,
+            &create_key,
+            setup_keypair,
+            fee_payers,
+            &args,
+            options,
+        )
+        .await
+        {
+            Ok(deployments) => deployments,
+            Err(e) => {
+                Output::error(&format!("Multisig {} failed: {}", i + 1, format!("{:#}", e).red()));
+                failures.push(i + 1);
+                continue;
+            }
+        };
+        if deployments.len() < networks.len() {
+            failures.push(i + 1);
+        }
+
+        let label = options.label.as_ref().map(|label| batch_label(label, i + 1));
+        remember_deployments(config, &deployments, label.as_deref());
+        record_history(&deployments, &args.members, threshold, label.as_deref());
+        for deployment in &deployments {
+            rows.push(BatchRow {
+                number: i + 1,
+                network: NetworkCluster::from_url(&deployment.rpc_url).label(),
+                multisig: deployment.multisig_address.to_string(),
+                feature_gate_id: deployment.vault_address.to_string(),
+            });
+        }
+        all_deployments.extend(deployments);
+    }
+
+    match options.summary_format.resolved() {
+        SummaryFormat::Compact => print_compact_summary(&all_deployments),
+        SummaryFormat::Csv => print_csv_summary(&all_deployments, &members),
+        SummaryFormat::Pretty => {
+            Output::separator();
+            Output::header("👀 Batch Deployment Complete");
+            Output::table(&rows);
+            Output::field("Keys", &keys_dir.display().to_string());
+            Output::warning("The keys files hold each multisig's create and setup secret keys");
+        }
+    }
+
+    if !all_deployments.is_empty() && !options.no_save_config {
+        save_deployment_config(config, threshold, &members, options)?;
+    }
+
+    if failures.is_empty() {
+        return Ok(());
+    }
+    let failed: Vec<String> = failures.iter().map(usize::to_string).collect();
+    Err(eyre::eyre!(
+        "{} of {} multisigs failed on at least one network (#{})",
+        failures.len(),
+        count,
+        failed.join(", #")
+    ))
+}
+
+/// Creates every multisig in a `--manifest`, each on its own networks,
+/// carrying on past failures and reporting how many succeeded at the end
+pub async fn batch_create_command(
+    config: &mut Config,
+    manifest: &Path,
+    keypair_path: Option<String>,
+    options: &CreateOptions,
+) -> Result<()> {
+    let specs = MultisigSpec::load_manifest(manifest)?;
+    for label in specs.iter().filter_map(|spec| spec.label.as_deref()) {
+        check_label(config, label)?;
+    }
+    Output::plain(&format!(
+        "{}",
+        format!("🚀 Creating {} feature gate multisigs from {}", specs.len(), manifest.display())
+            .bright_cyan()
+            .bold()
+    ));
+
+    let fee_payers = load_fee_payers(config, keypair_path, options)?;
+    let keys_dir = get_config_path()?.with_file_name("keys");
+    std::fs::create_dir_all(&keys_dir)
+        .map_err(|e| eyre::eyre!("Failed to create {}: {}", keys_dir.display(), e))?;
+
+    #[derive(Tabled)]
+    struct ManifestRow {
+        #[tabled(rename = "#")]
+        number: usize,
+        #[tabled(rename = "Label")]
+        label: String,
+        #[tabled(rename = "Network")]
+        network: &'static str,
+        #[tabled(rename = "Multisig")]
+        multisig: String,
+        #[tabled(rename = "Feature Gate ID")]
+        feature_gate_id: String,
+    }
+
+    let mut all_deployments = Vec::new();
+    let mut rows = Vec::new();
+    let mut failures = Vec::new();
+    for (i, spec) in specs.iter().enumerate() {
+        Output::separator();
+        Output::header(&format!(
+            "🏭 Multisig {} of {}{}",
+            i + 1,
+            specs.len(),
+            spec.label.as_ref().map(|label| format!(": {}", label)).unwrap_or_default()
+        ));
+        let deployments = match deploy_spec(config, spec, &fee_payers, &keys_dir, options).await {
+            Ok(deployments) => deployments,
+            Err(e) => {
+                Output::error(&format!("Multisig {} failed: {}", i + 1, format!("{:#}", e).red()));
+                failures.push(i + 1);
+                continue;
+            }
+        };
+        if deployments.len() < spec.networks.len() {
+            failures.push(i + 1);
+        }
+        for deployment in &deployments {
+            rows.push(ManifestRow {
+                number: i + 1,
+                label: spec.label.clone().unwrap_or_else(|| "-".to_string()),
+                network: NetworkCluster::from_url(&deployment.rpc_url).label(),
+                multisig: deployment.multisig_address.to_string(),
+                feature_gate_id: deployment.vault_address.to_string(),
+            });
+        }
+        all_deployments.extend(deployments);
+    }
+
+    match options.summary_format.resolved() {
+        SummaryFormat::Compact => print_compact_summary(&all_deployments),
+        SummaryFormat::Csv => print_csv_summary(&all_deployments, &[]),
+        SummaryFormat::Pretty => {
+            Output::separator();
+            Output::header("👀 Manifest Deployment Complete");
+            if !rows.is_empty() {
+                Output::table(&rows);
+                Output::field("Keys", &keys_dir.display().to_string());
+                Output::warning("The keys files hold each multisig's create and setup secret keys");
+            }
+        }
+    }
+    if !all_deployments.is_empty() && !options.no_save_config {
+        save_config(config)?;
     }
 
+    let succeeded = specs.len() - failures.len();
+    Output::field("Succeeded", &format!("{} of {}", succeeded, specs.len()));
+    if failures.is_empty() {
+        return Ok(());
+    }
+    let failed: Vec<String> = failures.iter().map(usize::to_string).collect();
+    Err(eyre::eyre!(
+        "{} of {} multisigs failed on at least one network (#{})",
+        failures.len(),
+        specs.len(),
+        failed.join(", #")
+    ))
+}
+
+/// Creates the multisig `spec` describes on each of its networks, saving its
+/// keys under `keys_dir` and recording the deployments that succeeded
+async fn deploy_spec(
+    config: &mut Config,
+    spec: &ValidatedSpec,
+    fee_payers: &[Option<Keypair>],
+    keys_dir: &Path,
+    options: &CreateOptions,
+) -> Result<Vec<DeploymentResult>> {
+    let options = CreateOptions {
+        label: spec.label.clone(),
+        create_key_seed: spec.create_key_seed.clone(),
+        ..options.clone()
+    };
+    let setup_keypair = Keypair::new();
+    let create_key = match &spec.create_key_seed {
+        Some(seed) => derive_create_key(seed, &options),
+        None => Keypair::new(),
+    };
+
+    let mut members = spec.members.clone();
+    members.insert(
+        0,
+        Member {
+            key: setup_keypair.pubkey(),
+            permissions: Permissions::from_actions(&[Permission::Initiate]),
+        },
+    );
+    if options.members_sort {
+        sort_members(&mut members);
+    }
+    let args = MultisigCreateArgsV2 {
+        config_authority: spec.config_authority,
+        threshold: spec.threshold,
+        members,
+        time_lock: spec.time_lock,
+        rent_collector: spec.rent_collector,
+        memo: None,
+    };
+
+    let program_id = options
+        .transaction
+        .program_id
+        .unwrap_or(crate::squads::SQUADS_MULTISIG_PROGRAM_ID);
+    let multisig = crate::squads::get_multisig_pda(&create_key.pubkey(), Some(&program_id)).0;
+    crate::plan::save_keys(
+        &keys_dir.join(format!("{}.keys.json", multisig)),
+        &create_key,
+        &setup_keypair,
+    )?;
+
+    check_fee_payer_balance_on_networks(
+        &paying_pubkeys(fee_payers, &setup_keypair),
+        &spec.networks,
+        0.05 + options.prefund_vault_sol.unwrap_or(0.0),
+    )
+    .await?;
+    let deployments = deploy_to_saved_networks(
+        &spec.networks,
+        &create_key,
+        &setup_keypair,
+        fee_payers,
+        &args,
+        &options,
+    )
+    .await?;
+
+    remember_deployments(config, &deployments, spec.label.as_deref());
+    record_history(&deployments, &args.members, args.threshold, spec.label.as_deref());
+    Ok(deployments)
+}
+
+/// The label a batch saves its `number`th multisig under
+fn batch_label(label: &str, number: usize) -> String {
+    format!("{}-{}", label, number)
+}
+
+/// Refuses a label that could be mistaken for an address or is already taken
+fn check_label(config: &Config, label: &str) -> Result<()> {
+    if label.trim().is_empty() || parse_pubkey(label).is_ok() {
+        return Err(eyre::eyre!("Label must be a non-empty name, not an address"));
+    }
+    if config.multisigs.iter().any(|saved| saved.label.as_deref() == Some(label)) {
+        return Err(eyre::eyre!("Label '{}' is already used by a saved multisig", label));
+    }
     Ok(())
 }
 
+/// Loads a user-supplied create key and reports the multisig it will produce
+fn load_create_key(path: &Path, options: &CreateOptions) -> Result<Keypair> {
+    let create_key = read_keypair_file(&expand_tilde_path(&path.to_string_lossy())?)
+        .map_err(|e| eyre::eyre!("Failed to load create key from {}: {}", path.display(), e))?;
+    let program_id = options
+        .transaction
+        .program_id
+        .unwrap_or(crate::squads::SQUADS_MULTISIG_PROGRAM_ID);
+    let multisig = crate::squads::get_multisig_pda(&create_key.pubkey(), Some(&program_id)).0;
+
+    Output::info(&format!(
+        "Using create key {} from {}",
+        create_key.pubkey(),
+        path.display()
+    ));
+    Output::field("Multisig", &multisig.to_string());
+    Ok(create_key)
+}
+
+/// Derives the create key from a `--create-key-seed` and reports the multisig
+/// it will produce
+fn derive_create_key(seed: &str, options: &CreateOptions) -> Keypair {
+    let create_key = derive_keypair_from_seed(CREATE_KEY_SEED_DOMAIN, seed);
+    let program_id = options
+        .transaction
+        .program_id
+        .unwrap_or(crate::squads::SQUADS_MULTISIG_PROGRAM_ID);
+    let multisig = crate::squads::get_multisig_pda(&create_key.pubkey(), Some(&program_id)).0;
+
+    Output::info(&format!("Using create key {} derived from the seed", create_key.pubkey()));
+    Output::field("Multisig", &multisig.to_string());
+    create_key
+}
+
+/// Reads the members of an existing multisig for a sibling multisig with the
+/// same council. Initiate-only members are left out: they are the temporary
+/// setup keys of earlier `create` runs, and this run adds its own. With
+/// `copy_governance` (`--template`), its time lock and config authority are
+/// returned too; otherwise the defaults. With `member_permissions`, every
+/// copied member gets that mask, e.g. when the members are the multisigs of
+/// a parent governance body.
+fn import_members_from_multisig(
+    config: &Config,
+    source: &str,
+    threshold: Option<u16>,
+    copy_governance: bool,
+    member_permissions: Option<u8>,
+) -> Result<(u16, Vec<Member>, GovernanceSettings)> {
+    let source_address = config.resolve_multisig(source)?;
+    let rpc_url = choose_network_from_config(config)?;
+    let source_multisig = fetch_multisig(&create_rpc_client(&rpc_url), &source_address)
+        .wrap_err_with(|| format!("Failed to fetch multisig {} to copy members from", source))?;
+
+    let initiate_only = Permissions::from_actions(&[Permission::Initiate]);
+    let (mut members, setup_keys): (Vec<Member>, Vec<Member>) = source_multisig
+        .members
+        .into_iter()
+        .partition(|member| member.permissions != initiate_only);
+    if members.is_empty() {
+        return Err(eyre::eyre!("Multisig {} has no members to copy", source_address));
+    }
+    if let Some(mask) = member_permissions {
+        for member in &mut members {
+            member.permissions = Permissions { mask };
+        }
+    }
+
+    Output::header(&format!("👥 Members of {}", source_address));
+    for (i, member) in members.iter().enumerate() {
+        Output::numbered_field(
+            i + 1,
+            &member.key.to_string(),
+            &decode_permissions(member.permissions.mask).join(", "),
+        );
+    }
+    for member in &setup_keys {
+        Output::info(&format!("Skipping initiate-only setup key {}", member.key));
+    }
+    Output::field("Source Threshold", &source_multisig.threshold.to_string());
+    let governance = if copy_governance {
+        // An all-zero config authority means the multisig governs itself
+        let config_authority = Some(source_multisig.config_authority)
+            .filter(|authority| *authority != Pubkey::default());
+        Output::field("Time Lock", &format!("{}s", source_multisig.time_lock));
+        Output::field(
+            "Config Authority",
+            &config_authority.map_or("None (autonomous)".to_string(), |a| a.to_string()),
+        );
+        GovernanceSettings {
+            time_lock: source_multisig.time_lock,
+            config_authority,
+            ..Default::default()
+        }
+    } else {
+        GovernanceSettings::default()
+    };
+    Output::separator();
+
+    let proceed = inquire::Confirm::new("Use these members for the new multisig?")
+        .with_default(true)
+        .prompt()?;
+    if !proceed {
+        return Err(ToolError::Cancelled("User aborted".to_string()).into());
+    }
+
+    // The new multisig also has this run's setup key
+    let max_members = members.len() + 1;
+    let final_threshold = match threshold.unwrap_or(source_multisig.threshold) {
+        t if t as usize <= max_members => t,
+        t => {
+            Output::warning(&format!(
+                "Threshold {} exceeds member count ({}), prompting for new value",
+                t, max_members
+            ));
+            prompt_for_threshold_with_max(max_members)?
+        }
+    };
+
+    Ok((final_threshold, members, governance))
+}
+
+/// The keypairs to pay fees with, in rotation order: every keypair in
+/// `--fee-payer-dir`, or just the `--keypair`/configured fee payer
+fn load_fee_payers(
+    config: &Config,
+    keypair_path: Option<String>,
+    options: &CreateOptions,
+) -> Result<Vec<Option<Keypair>>> {
+    match &options.fee_payer_dir {
+        Some(dir) => Ok(load_fee_payer_keypairs_from_dir(dir)?
+            .into_iter()
+            .map(Some)
+            .collect()),
+        None => Ok(vec![load_fee_payer_keypair(config, keypair_path)?]),
+    }
+}
+
+/// The fee payer to add as a member for `--fee-payer-is-member`. It needs a
+/// keypair of its own, and can't already be one of `members`.
+fn member_fee_payer(fee_payers: &[Option<Keypair>], members: &[Member]) -> Result<Keypair> {
+    let fee_payer = match fee_payers {
+        [Some(fee_payer)] => fee_payer,
+        _ => {
+            return Err(ToolError::Config(
+                "--fee-payer-is-member needs a fee payer; pass --keypair or configure one"
+                    .to_string(),
+            )
+            .into())
+        }
+    };
+    if members.iter().any(|member| member.key == fee_payer.pubkey()) {
+        return Err(ToolError::Config(format!(
+            "Fee payer {} is already a member; remove it from the members or drop \
+             --fee-payer-is-member",
+            fee_payer.pubkey()
+        ))
+        .into());
+    }
+    Output::info(&format!(
+        "Fee payer {} joins the multisig as a member with every permission",
+        fee_payer.pubkey()
+    ));
+    Ok(fee_payer.insecure_clone())
+}
+
+/// The key that actually pays for each rotation slot; with no fee payer the
+/// setup keypair pays
+fn paying_pubkeys(fee_payers: &[Option<Keypair>], setup_keypair: &Keypair) -> Vec<Pubkey> {
+    fee_payers
+        .iter()
+        .map(|fee_payer| SignerRoles::new(fee_payer, setup_keypair).fee_payer.pubkey())
+        .collect()
+}
+
+/// Records each deployment in the config so it shows up in `list` and can be
+/// addressed by label
+fn remember_deployments(config: &mut Config, deployments: &[DeploymentResult], label: Option<&str>) {
+    for deployment in deployments {
+        config.multisigs.push(SavedMultisig {
+            address: deployment.multisig_address.to_string(),
+            label: label.map(str::to_string),
+            network: deployment.rpc_url.clone(),
+        });
+    }
+}
+
+/// Saves the deployments already remembered in `config`, then makes
+/// `threshold` and `members` (without the setup member) the new defaults.
+/// They are replaced together, so the saved threshold always fits the saved
+/// members. A threshold that differs from the default is only saved once
+/// confirmed, so a one-off deployment doesn't quietly change it; `--yes`
+/// saves it.
+fn save_deployment_config(
+    config: &mut Config,
+    threshold: u16,
+    members: &[Member],
+    options: &CreateOptions,
+) -> Result<()> {
+    // Saved before prompting, so declining or interrupting can't lose them
+    save_config(config)?;
+
+    let replace = threshold == config.threshold
+        || options.yes
+        || inquire::Confirm::new(&format!(
+            "Save threshold {} and these members as the new defaults? (current default \
+             threshold: {})",
+            threshold, config.threshold
+        ))
+        .with_default(false)
+        .prompt()?;
+    if !replace {
+        Output::info("Kept the saved threshold and members");
+        return Ok(());
+    }
+    config.threshold = threshold;
+    let default_permissions = config.default_member_permissions;
+    config.members = members
+        .iter()
+        .map(|member| format_saved_member(member, default_permissions))
+        .collect();
+    save_config(config)
+}
+
+/// Appends the run to the deployment history. The deployments already
+/// succeeded, so failing to write it only warns.
+fn record_history(
+    deployments: &[DeploymentResult],
+    members: &[Member],
+    threshold: u16,
+    label: Option<&str>,
+) {
+    if deployments.is_empty() {
+        return;
+    }
+    let entry = HistoryEntry::new(deployments, members, threshold, label);
+    if let Err(e) = history_path().and_then(|path| append_history(&path, &entry)) {
+        Output::warning(&format!("Could not record deployment history: {}", e));
+    }
+}
+
 async fn deploy_to_single_network(
     rpc_url: &str,
     network_index: usize,
@@ -120,8 +1083,8 @@ async fn deploy_to_single_network(
     create_key: &Keypair,
     setup_keypair: &Keypair,
     fee_payer_keypair: &Option<Keypair>,
-    members: &[Member],
-    threshold: u16,
+    args: &MultisigCreateArgsV2,
+    options: &CreateOptions,
 ) -> Result<DeploymentResult> {
     // display_deployment_info(
     //     network_index,
@@ -134,43 +1097,116 @@ async fn deploy_to_single_network(
     //     members,
     // );
 
-    let signer_for_creation = fee_payer_keypair
-        .as_ref()
-        .map(|kp| kp as &dyn Signer)
-        .unwrap_or(setup_keypair as &dyn Signer);
+    options.transaction.ensure_safe_for(rpc_url)?;
+    if needs_mainnet_confirmation(rpc_url, options.yes) {
+        confirm_mainnet_deployment(rpc_url, args.threshold)?;
+    }
+
+    // Bounds the retrying across every RPC call made for this network
+    let retry_budget = RetryBudget::for_deployment();
+
+    let signer_for_creation: &dyn Signer =
+        SignerRoles::new(fee_payer_keypair, setup_keypair).fee_payer;
+    let fee_payer = signer_for_creation.pubkey();
+
+    // A reused create key maps to the same multisig address every time
+    if options.reuses_create_key() {
+        let program_id = options
+            .transaction
+            .program_id
+            .unwrap_or(crate::squads::SQUADS_MULTISIG_PROGRAM_ID);
+        let multisig = crate::squads::get_multisig_pda(&create_key.pubkey(), Some(&program_id)).0;
+        if crate::provision::create_rpc_client(rpc_url).get_account(&multisig).is_ok() {
+            Output::warning(&format!(
+                "Multisig {} already exists on {}; creating it again will fail",
+                multisig, rpc_url
+            ));
+        }
+    }
+
+    let program_id = options
+        .transaction
+        .program_id
+        .unwrap_or(crate::squads::SQUADS_MULTISIG_PROGRAM_ID);
+    verify_program(
+        rpc_url,
+        &program_id,
+        options.expected_program_hash.as_deref(),
+        options.allow_unverified_program,
+    )?;
+
+    let proposal_types = options.proposal_types();
+
+    // Check the activation could execute before sending anything
+    if proposal_types.contains(&TransactionType::Activation) {
+        let multisig = crate::squads::get_multisig_pda(&create_key.pubkey(), Some(&program_id)).0;
+        // --prefund-vault tops up vault 0, which is the activation's funder
+        let topped_up_to = options
+            .prefund_vault_sol
+            .map(|sol| (sol * LAMPORTS_PER_SOL as f64) as u64);
+        ensure_feature_funding(
+            &create_rpc_client(rpc_url),
+            &multisig,
+            &options.transaction,
+            topped_up_to,
+        )?;
+    }
 
     let (multisig_address, signature) = create_multisig(
         rpc_url.to_string(),
-        None, // Use default program ID
+        Some(program_id.to_string()),
         signer_for_creation,
         create_key,
-        members.to_vec(),
-        threshold,
-        Some(5000), // Priority fee
+        args.clone(),
+        &options.transaction,
+        &retry_budget,
+        options.yes,
     )
     .await
-    .map_err(|e| eyre::eyre!("Failed to create multisig: {}", e))?;
+    .wrap_err("Failed to create multisig")?;
 
-    let vault_address = get_vault_pda(&multisig_address, 0, None).0;
+    if options.verify_after_create {
+        let created = fetch_multisig(&create_rpc_client(rpc_url), &multisig_address)
+            .wrap_err("Failed to read back the created multisig")?;
+        verify_created_multisig(&created, args)?;
+        Output::success("Verified on-chain members and threshold match the request");
+    }
 
-    // Create both activation and revocation transactions
-    create_and_send_transaction_proposal(
-        rpc_url,
-        fee_payer_keypair,
-        setup_keypair,
-        &multisig_address,
-        "activation",
-        1, // Transaction index 1 (activation)
-    )
-    .await?;
+    let vault_address = get_vault_pda(&multisig_address, 0, Some(&program_id)).0;
+
+    if let Some(target_sol) = options.prefund_vault_sol {
+        let target_lamports = (target_sol * LAMPORTS_PER_SOL as f64) as u64;
+        prefund_vault(
+            rpc_url,
+            signer_for_creation,
+            &vault_address,
+            target_lamports,
+            &options.transaction,
+            &retry_budget,
+        )
+        .wrap_err("Failed to pre-fund vault")?;
+    }
 
-    create_and_send_transaction_proposal(
+    if proposal_types.is_empty() {
+        return Ok(DeploymentResult {
+            rpc_url: rpc_url.to_string(),
+            multisig_address,
+            vault_address,
+            transaction_signature: signature,
+            fee_payer,
+        });
+    }
+
+    // A new multisig's first transaction indices: activation, then revocation
+    create_and_send_feature_gate_proposals(
         rpc_url,
         fee_payer_keypair,
         setup_keypair,
         &multisig_address,
-        "revocation",
-        2, // Transaction index 2 (revocation)
+        1,
+        &proposal_types,
+        &options.transaction,
+        &retry_budget,
     )
     .await?;
 
@@ -179,16 +1215,37 @@ async fn deploy_to_single_network(
         multisig_address,
         vault_address,
         transaction_signature: signature,
+        fee_payer,
     })
 }
 
+/// Mainnet deployments spend real SOL, so make the user type the threshold
+/// rather than just press enter
+fn confirm_mainnet_deployment(rpc_url: &str, threshold: u16) -> Result<()> {
+    Output::separator();
+    Output::warning(&format!(
+        "You are about to deploy to MAINNET-BETA ({}) with real SOL",
+        rpc_url
+    ));
+    let answer = inquire::Text::new("Type the multisig threshold to confirm:").prompt()?;
+    if answer.trim() != threshold.to_string() {
+        return Err(ToolError::Cancelled(
+            "Mainnet deployment not confirmed: threshold didn't match".to_string(),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Deploys to each network in turn, paying with `fee_payers` round-robin so
+/// each network's fees can come from a different key
 async fn deploy_to_saved_networks(
     networks: &[String],
     create_key: &Keypair,
     setup_keypair: &Keypair,
-    fee_payer_keypair: &Option<Keypair>,
-    members: &[Member],
-    threshold: u16,
+    fee_payers: &[Option<Keypair>],
+    args: &MultisigCreateArgsV2,
+    options: &CreateOptions,
 ) -> Result<Vec<DeploymentResult>> {
     let mut deployments = Vec::new();
 
@@ -199,9 +1256,9 @@ async fn deploy_to_saved_networks(
             networks.len(),
             create_key,
             setup_keypair,
-            fee_payer_keypair,
-            members,
-            threshold,
+            &fee_payers[i % fee_payers.len()],
+            args,
+            options,
         )
         .await
         {
@@ -209,17 +1266,19 @@ async fn deploy_to_saved_networks(
                 deployments.push(deployment);
             }
             Err(e) => {
-                println!(
-                    "{} Failed to deploy on {}: {}",
-                    "❌".bright_red(),
+                Output::error(&format!(
+                    "Failed to deploy on {}: {}",
                     rpc_url.bright_white(),
-                    e.to_string().red()
-                );
+                    format!("{:#}", e).red()
+                ));
+                if let Some(logs) = crate::error::simulation_logs(&e) {
+                    Output::simulation_logs(logs);
+                }
             }
         }
 
         if i < networks.len() - 1 {
-            println!("\n{} Proceeding to next network...", "⏳".bright_yellow());
+            Output::plain(&format!("\n{} Proceeding to next network...", "⏳".bright_yellow()));
             std::thread::sleep(std::time::Duration::from_secs(1));
         }
     }
@@ -231,15 +1290,15 @@ async fn deploy_to_manual_networks(
     config: &Config,
     create_key: &Keypair,
     contributor_keypair: &Keypair,
-    fee_payer_keypair: &Option<Keypair>,
-    members: &[Member],
-    threshold: u16,
+    fee_payers: &[Option<Keypair>],
+    args: &MultisigCreateArgsV2,
+    options: &CreateOptions,
 ) -> Result<Vec<DeploymentResult>> {
-    println!("\n{} Manual network entry mode", "🔄".bright_cyan());
+    Output::plain(&format!("\n{} Manual network entry mode", "🔄".bright_cyan()));
 
     let mut deployments = Vec::new();
 
-    loop {
+    for attempt in 0.. {
         let rpc_url = prompt_for_network(config)?;
 
         match deploy_to_single_network(
@@ -248,9 +1307,9 @@ async fn deploy_to_manual_networks(
             1,
             create_key,
             contributor_keypair,
-            fee_payer_keypair,
-            members,
-            threshold,
+            &fee_payers[attempt % fee_payers.len()],
+            args,
+            options,
         )
         .await
         {
@@ -258,12 +1317,14 @@ async fn deploy_to_manual_networks(
                 deployments.push(deployment);
             }
             Err(e) => {
-                println!(
-                    "{} Failed to deploy on {}: {}",
-                    "❌".bright_red(),
+                Output::error(&format!(
+                    "Failed to deploy on {}: {}",
                     rpc_url.bright_white(),
-                    e.to_string().red()
-                );
+                    format!("{:#}", e).red()
+                ));
+                if let Some(logs) = crate::error::simulation_logs(&e) {
+                    Output::simulation_logs(logs);
+                }
             }
         }
 
@@ -276,17 +1337,87 @@ async fn deploy_to_manual_networks(
             break;
         }
 
-        println!();
+        Output::separator();
     }
 
     Ok(deployments)
 }
 
+/// One uncoloured `network\tmultisig\tvault\tsignature` line per deployment,
+/// for dashboards and log scraping
+fn print_compact_summary(deployments: &[DeploymentResult]) {
+    for deployment in deployments {
+        println!(
+            "{}\t{}\t{}\t{}",
+            deployment.rpc_url,
+            deployment.multisig_address,
+            deployment.vault_address,
+            deployment.transaction_signature
+        );
+    }
+}
+
+#[derive(Tabled)]
+struct DeploymentRow {
+    #[tabled(rename = "Network")]
+    network: String,
+    #[tabled(rename = "Multisig")]
+    multisig: String,
+    #[tabled(rename = "Vault")]
+    vault: String,
+    #[tabled(rename = "Signature")]
+    signature: String,
+}
+
+#[derive(Tabled)]
+struct MemberRow {
+    #[tabled(rename = "Index")]
+    index: usize,
+    #[tabled(rename = "Public Key")]
+    pubkey: String,
+    #[tabled(rename = "Permissions")]
+    permissions: String,
+}
+
+fn deployment_rows(deployments: &[DeploymentResult]) -> Vec<DeploymentRow> {
+    deployments
+        .iter()
+        .map(|deployment| DeploymentRow {
+            network: deployment.rpc_url.clone(),
+            multisig: deployment.multisig_address.to_string(),
+            vault: deployment.vault_address.to_string(),
+            signature: deployment.transaction_signature.clone(),
+        })
+        .collect()
+}
+
+fn member_rows(members: &[Member]) -> Vec<MemberRow> {
+    members
+        .iter()
+        .enumerate()
+        .map(|(i, member)| MemberRow {
+            index: i + 1,
+            pubkey: member.key.to_string(),
+            permissions: decode_permissions(member.permissions.mask).join(" "),
+        })
+        .collect()
+}
+
+/// Deployments then members as two CSV tables separated by a blank line,
+/// for pasting into spreadsheets
+fn print_csv_summary(deployments: &[DeploymentResult], members: &[Member]) {
+    Output::csv(&deployment_rows(deployments));
+    println!();
+    Output::csv(&member_rows(members));
+}
+
 fn print_deployment_summary(
     deployments: &[DeploymentResult],
     members: &[Member],
     threshold: u16,
-    _create_key: &solana_pubkey::Pubkey,
+    label: Option<&str>,
+    program_id: Option<&solana_pubkey::Pubkey>,
+    proposal_types: &[TransactionType],
 ) {
     if deployments.is_empty() {
         Output::error("No successful deployments to summarize.");
@@ -295,66 +1426,51 @@ fn print_deployment_summary(
 
     println!("");
     Output::header("👀 Deployment Complete");
+    Output::table(&deployment_rows(deployments));
+
+    println!("\n{}", "⚙️ Config Parameters".bright_white().bold());
+    println!();
+    Output::table(&member_rows(members));
+    Output::field("Threshold", &threshold.to_string());
 
     for deployment in deployments {
         // Feature Gate ID is the vault address (index 0)
         let feature_gate_id = deployment.vault_address;
 
-        // Calculate proposal PDAs for activation and revocation transactions
-        let activation_proposal_pda = get_proposal_pda(&deployment.multisig_address, 1, None).0;
-        let revocation_proposal_pda = get_proposal_pda(&deployment.multisig_address, 2, None).0;
-
         println!("\n{}", "⚙️ General Info".bright_white().bold());
         println!();
+        if let Some(label) = label {
+            Output::field("Label", label);
+        }
         Output::field(
             "Feature Gate Multisig",
             &deployment.multisig_address.to_string(),
         );
         Output::field("Feature Gate ID", &feature_gate_id.to_string());
-
-        println!("\n{}", "⚙️ Config Parameters".bright_white().bold());
-        println!();
-        Output::field("Members", &members.len().to_string());
-
-        // Display members with their permissions
-        for (i, member) in members.iter().enumerate() {
-            let perms = decode_permissions(member.permissions.mask);
-            let role_indicator = if member.permissions.mask == 1 {
-                " (Contributor)"
-            } else {
-                ""
-            };
-            let member_display = format!(
-                "{}{} ({})",
-                member.key.to_string(),
-                role_indicator,
-                perms.join(", ")
-            );
-            let member_label = if member.permissions.mask == 1 {
-                "Temporary Setup Keypair".to_string()
-            } else {
-                format!("Member {}", i + 1)
-            };
-            println!(
-                "  {} {}: {}",
-                "✓".bright_green(),
-                member_label,
-                member_display.bright_white()
-            );
-        }
-        println!();
-        Output::field("Threshold", &threshold.to_string());
+        Output::field("Network", NetworkCluster::from_url(&deployment.rpc_url).label());
+        Output::field("Fee Payer", &deployment.fee_payer.to_string());
+        Output::field(
+            "Creation Transaction",
+            &explorer_tx_url(&deployment.transaction_signature, &deployment.rpc_url),
+        );
 
         println!("\n{}", "⚙️ Proposals".bright_white().bold());
         println!();
-        Output::field(
-            "Feature Gate Activation Proposal",
-            &activation_proposal_pda.to_string(),
-        );
-        Output::field(
-            "Feature Gate Revocation Proposal",
-            &revocation_proposal_pda.to_string(),
-        );
+        if !proposal_types.is_empty() {
+            // Proposals take the new multisig's transaction indices from 1
+            for (transaction_type, index) in proposal_types.iter().zip(1u64..) {
+                let proposal_pda =
+                    get_proposal_pda(&deployment.multisig_address, index, program_id).0;
+                Output::field(transaction_type.proposal_label(), &proposal_pda.to_string());
+            }
+        } else {
+            Output::field("Proposals", "Not created (--no-proposals)");
+            Output::hint(&format!(
+                "Create them later with: feature-gate-multisig-tool create-proposals {} \
+                 --url {} --keypair <MEMBER_KEYPAIR>",
+                deployment.multisig_address, deployment.rpc_url
+            ));
+        }
 
         if deployments.len() > 1 {
             println!("\n{}", "─".repeat(50).bright_cyan());
@@ -0,0 +1,105 @@
+use crate::address_lookup_table::{
+    create_lookup_table, extend_lookup_table, MAX_ADDRESSES_PER_EXTEND,
+};
+use crate::error::ToolError;
+use crate::network::{explorer_tx_url, NetworkCluster};
+use crate::output::Output;
+use crate::provision::{
+    create_rpc_client, fetch_multisig, send_and_confirm_transaction, SendStrategy,
+};
+use crate::utils::*;
+use eyre::Result;
+use inquire::Confirm;
+use solana_commitment_config::CommitmentConfig;
+use solana_message::{v0::Message, VersionedMessage};
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::versioned::VersionedTransaction;
+
+/// Creates an address lookup table holding every current member of the
+/// multisig, so later transactions can reference members by table index
+pub async fn create_member_lut_command(
+    config: &Config,
+    address: String,
+    rpc_url: Option<String>,
+    keypair_path: Option<String>,
+    yes: bool,
+) -> Result<()> {
+    let multisig_address = config.resolve_multisig(&address)?;
+    let rpc_url = match rpc_url {
+        Some(url) => validate_rpc_url(&url)?,
+        None => choose_network_from_config(config)?,
+    };
+    let rpc_client = create_rpc_client(&rpc_url);
+
+    let authority = load_fee_payer_keypair(config, keypair_path)?.ok_or_else(|| {
+        eyre::eyre!("A keypair is required to create and pay for the lookup table; pass --keypair")
+    })?;
+
+    let multisig = fetch_multisig(&rpc_client, &multisig_address)?;
+    let members: Vec<Pubkey> = multisig.members.iter().map(|member| member.key).collect();
+    if members.is_empty() {
+        return Err(eyre::eyre!("Multisig {} has no members", multisig_address));
+    }
+    let chunks: Vec<&[Pubkey]> = members.chunks(MAX_ADDRESSES_PER_EXTEND).collect();
+
+    Output::header("📇 Create Member Lookup Table");
+    Output::field("Multisig", &multisig_address.to_string());
+    Output::field("Network", NetworkCluster::from_url(&rpc_url).label());
+    Output::field("Members", &members.len().to_string());
+    Output::field("Authority", &authority.pubkey().to_string());
+    Output::field("Transactions", &chunks.len().to_string());
+
+    if !yes {
+        let proceed = Confirm::new("Create the lookup table?")
+            .with_default(false)
+            .prompt()?;
+        if !proceed {
+            return Err(ToolError::Cancelled("User aborted".to_string()).into());
+        }
+    }
+
+    // The table address is derived from a slot that must still be in SlotHashes
+    let recent_slot = rpc_client
+        .get_slot_with_commitment(CommitmentConfig::finalized())
+        .map_err(|e| ToolError::Network(format!("Failed to get recent slot: {}", e)))?;
+    let (create_instruction, lookup_table) =
+        create_lookup_table(&authority.pubkey(), &authority.pubkey(), recent_slot);
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let mut instructions = Vec::new();
+        if i == 0 {
+            instructions.push(create_instruction.clone());
+        }
+        instructions.push(extend_lookup_table(
+            &lookup_table,
+            &authority.pubkey(),
+            &authority.pubkey(),
+            chunk,
+        ));
+
+        let blockhash = rpc_client
+            .get_latest_blockhash()
+            .map_err(|e| ToolError::Network(format!("Failed to get recent blockhash: {}", e)))?;
+        let message = Message::try_compile(&authority.pubkey(), &instructions, &[], blockhash)?;
+        let transaction =
+            VersionedTransaction::try_new(VersionedMessage::V0(message), &[&authority])?;
+        let signature =
+            send_and_confirm_transaction(&transaction, &rpc_client, SendStrategy::default())?;
+
+        Output::success(&format!(
+            "Added members {}-{} of {}: {}",
+            i * MAX_ADDRESSES_PER_EXTEND + 1,
+            i * MAX_ADDRESSES_PER_EXTEND + chunk.len(),
+            members.len(),
+            signature
+        ));
+        Output::field("Explorer", &explorer_tx_url(&signature, &rpc_url));
+    }
+
+    println!();
+    Output::field("Member Lookup Table", &lookup_table.to_string());
+    Output::hint("Members can now be referenced by their index in this table");
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use crate::error::ToolError;
+use crate::output::Output;
+use crate::feature_gate_program::{ensure_activatable, FeatureState};
+use crate::provision::{
+    create_rpc_client, ensure_feature_funding, fetch_epoch_of_slot, fetch_feature_state,
+    fetch_multisig, RetryBudget, TransactionOptions,
+};
+use crate::squads::get_vault_pda;
+use crate::utils::*;
+use eyre::Result;
+use inquire::Confirm;
+use solana_signer::Signer;
+
+/// Adds the feature gate activation and revocation proposals to a multisig
+/// that was created with `--no-proposals`. A feature that is already pending
+/// or active is refused unless `force`, since its proposals can't do anything.
+pub async fn create_proposals_command(
+    config: &Config,
+    address: String,
+    rpc_url: Option<String>,
+    keypair_path: Option<String>,
+    tx_options: &TransactionOptions,
+    yes: bool,
+    force: bool,
+) -> Result<()> {
+    let multisig_address = config.resolve_multisig(&address)?;
+    let rpc_url = match rpc_url {
+        Some(url) => validate_rpc_url(&url)?,
+        None => choose_network_from_config(config)?,
+    };
+    tx_options.ensure_safe_for(&rpc_url)?;
+    let rpc_client = create_rpc_client(&rpc_url);
+
+    let member = load_fee_payer_keypair(config, keypair_path)?.ok_or_else(|| {
+        eyre::eyre!("A member keypair is required to create proposals; pass --keypair")
+    })?;
+
+    let multisig = fetch_multisig(&rpc_client, &multisig_address)?;
+    multisig.ensure_can_initiate(&member.pubkey())?;
+
+    // The feature account is created by the activation proposal queued below, so
+    // an existing one makes the activation pointless, and an activated one the
+    // revocation too
+    let feature_id = get_vault_pda(&multisig_address, 0, tx_options.program_id.as_ref()).0;
+    let feature_state = fetch_feature_state(&rpc_client, &feature_id)?;
+    let activation_epoch = match feature_state {
+        FeatureState::Activated(slot) => fetch_epoch_of_slot(&rpc_client, slot),
+        _ => None,
+    };
+    if let Err(e) = ensure_activatable(&feature_id, feature_state, activation_epoch) {
+        if !force {
+            return Err(e.wrap_err("Refusing to create the proposals; pass --force to override"));
+        }
+        Output::warning(&format!("{}; creating the proposals anyway (--force)", e));
+    }
+    ensure_feature_funding(&rpc_client, &multisig_address, tx_options, None)?;
+
+    let activation_index = multisig.transaction_index + 1;
+    let revocation_index = multisig.transaction_index + 2;
+
+    Output::header("📝 Create Feature Gate Proposals");
+    Output::field("Multisig", &multisig_address.to_string());
+    Output::field("Activation Proposal Index", &activation_index.to_string());
+    Output::field("Revocation Proposal Index", &revocation_index.to_string());
+    Output::separator();
+
+    if !yes {
+        let proceed = Confirm::new("Create the activation and revocation proposals?")
+            .with_default(false)
+            .prompt()?;
+        if !proceed {
+            return Err(ToolError::Cancelled("User aborted".to_string()).into());
+        }
+    }
+
+    let retry_budget = RetryBudget::for_deployment();
+    create_and_send_feature_gate_proposals(
+        &rpc_url,
+        &None,
+        &member,
+        &multisig_address,
+        activation_index,
+        &feature_gate_proposal_types(true, true),
+        tx_options,
+        &retry_budget,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use crate::network::{explorer_tx_url, NetworkCluster};
+use crate::output::Output;
+use crate::provision::{create_rpc_client, fetch_multisig, RetryBudget, TransactionOptions};
+use crate::squads::get_vault_pda;
+use crate::utils::*;
+use colored::*;
+use eyre::Result;
+use inquire::Confirm;
+use solana_signer::Signer;
+
+/// Proposes an arbitrary compiled Solana message as the multisig's next vault
+/// transaction, for feature-gate instructions beyond the built-in activation
+/// and revocation templates
+pub async fn create_transaction_command(
+    config: &Config,
+    address: String,
+    message_base64: String,
+    rpc_url: Option<String>,
+    keypair_path: Option<String>,
+    tx_options: &TransactionOptions,
+    yes: bool,
+) -> Result<()> {
+    let multisig_address = config.resolve_multisig(&address)?;
+    let transaction_message = transaction_message_from_base64(&message_base64)?;
+
+    let rpc_url = match rpc_url {
+        Some(url) => validate_rpc_url(&url)?,
+        None => choose_network_from_config(config)?,
+    };
+    let rpc_client = create_rpc_client(&rpc_url);
+
+    let creator = load_fee_payer_keypair(config, keypair_path)?.ok_or_else(|| {
+        eyre::eyre!("A member keypair is required to create a transaction; pass --keypair")
+    })?;
+
+    let multisig = fetch_multisig(&rpc_client, &multisig_address)?;
+    multisig.ensure_can_initiate(&creator.pubkey())?;
+
+    let vault_address = get_vault_pda(&multisig_address, 0, tx_options.program_id.as_ref()).0;
+    if !transaction_message.account_keys.contains(&vault_address) {
+        Output::warning(&format!(
+            "The message doesn't reference the default vault {}; it won't sign anything",
+            vault_address
+        ));
+    }
+
+    let transaction_index = multisig.transaction_index + 1;
+
+    Output::header("📝 Create Vault Transaction");
+    Output::field("Multisig", &multisig_address.to_string());
+    Output::field("Network", NetworkCluster::from_url(&rpc_url).label());
+    Output::field("Transaction Index", &transaction_index.to_string());
+    Output::field(
+        "Instructions",
+        &transaction_message.instructions.len().to_string(),
+    );
+    Output::field(
+        "Account Keys",
+        &transaction_message.account_keys.len().to_string(),
+    );
+    Output::field(
+        "Address Table Lookups",
+        &transaction_message.address_table_lookups.len().to_string(),
+    );
+
+    if !yes {
+        let proceed = Confirm::new("Create this transaction and its proposal?")
+            .with_default(false)
+            .prompt()?;
+        if !proceed {
+            return Err(crate::error::ToolError::Cancelled("User aborted".to_string()).into());
+        }
+    }
+
+    let progress = Output::spinner("Sending transaction...");
+
+    let signature = send_vault_transaction_proposal(
+        &rpc_url,
+        &None,
+        &creator,
+        &multisig_address,
+        transaction_index,
+        transaction_message,
+        tx_options,
+        &RetryBudget::default(),
+    )?;
+
+    progress.finish_and_clear();
+    Output::result(&format!(
+        "Transaction #{} Proposal Confirmed: {}",
+        transaction_index,
+        signature.bright_green()
+    ));
+    Output::separator();
+    Output::field("Explorer", &explorer_tx_url(&signature, &rpc_url));
+
+    Ok(())
+}
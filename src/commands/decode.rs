@@ -0,0 +1,70 @@
+use crate::output::Output;
+use crate::provision::dump_message_instructions;
+use crate::utils::*;
+use colored::*;
+use eyre::Result;
+use solana_message::{v0, VersionedMessage};
+use solana_signature::Signature;
+
+/// Prints what an encoded transaction (e.g. one sent round for offline
+/// signing) would do and who has signed it so far, without sending anything
+pub fn decode_command(base64: Option<String>, base58: Option<String>) -> Result<()> {
+    let transaction = match (base64, base58) {
+        (_, Some(encoded)) => decode_versioned_transaction(&encoded, true)?,
+        (Some(encoded), None) => decode_versioned_transaction(&encoded, false)?,
+        (None, None) => return Err(eyre::eyre!("Pass the transaction with --base64 or --base58")),
+    };
+    // Account and program indices are used below without bounds checks
+    transaction
+        .sanitize()
+        .map_err(|e| eyre::eyre!("Malformed transaction: {}", e))?;
+
+    // Legacy messages display the same as a v0 message without lookups
+    let (version, message) = match &transaction.message {
+        VersionedMessage::Legacy(message) => (
+            "legacy",
+            v0::Message {
+                header: message.header,
+                account_keys: message.account_keys.clone(),
+                recent_blockhash: message.recent_blockhash,
+                instructions: message.instructions.clone(),
+                address_table_lookups: vec![],
+            },
+        ),
+        VersionedMessage::V0(message) => ("v0", message.clone()),
+    };
+
+    Output::header("🧾 Decoded Transaction");
+    Output::field("Version", version);
+    Output::field("Fee Payer", &message.account_keys[0].to_string());
+    Output::field("Recent Blockhash", &message.recent_blockhash.to_string());
+    for lookup in &message.address_table_lookups {
+        Output::field("Lookup Table", &lookup.account_key.to_string());
+    }
+
+    let num_signers = usize::from(message.header.num_required_signatures);
+    let verified = transaction.verify_with_results();
+    let mut signed = 0;
+    Output::subheader(&format!("✍️  Required Signers ({})", num_signers));
+    for (i, signer) in message.account_keys.iter().take(num_signers).enumerate() {
+        let status = match transaction.signatures.get(i) {
+            Some(signature) if *signature == Signature::default() => "unsigned".bright_yellow(),
+            Some(_) if verified.get(i) == Some(&true) => {
+                signed += 1;
+                "signed".bright_green()
+            }
+            Some(_) => "invalid signature".bright_red(),
+            None => "unsigned".bright_yellow(),
+        };
+        println!("  {} [{}]", signer, status);
+    }
+
+    dump_message_instructions(&message);
+
+    if signed == num_signers {
+        Output::success("All required signatures are present and valid");
+    } else {
+        Output::info(&format!("{} of {} required signatures are valid", signed, num_signers));
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::error::ToolError;
+use crate::history::{history_path, read_history, HistoryFilter};
+use crate::network::NetworkCluster;
+use crate::output::Output;
+use chrono::NaiveDate;
+use eyre::Result;
+use tabled::Tabled;
+
+pub fn history_command(
+    since: Option<String>,
+    until: Option<String>,
+    network: Option<String>,
+) -> Result<()> {
+    let filter = HistoryFilter {
+        since: since.as_deref().map(|date| parse_date("--since", date)).transpose()?,
+        until: until.as_deref().map(|date| parse_date("--until", date)).transpose()?,
+        network,
+    };
+
+    let path = history_path()?;
+    let (entries, unreadable) = read_history(&path)?;
+    if unreadable > 0 {
+        Output::warning(&format!(
+            "Skipped {} unreadable line(s) in {}",
+            unreadable,
+            path.display()
+        ));
+    }
+
+    let entries: Vec<_> = entries.iter().filter_map(|entry| filter.apply(entry)).collect();
+    if entries.is_empty() {
+        Output::info("No matching deployments. Every `create` run is recorded here.");
+        return Ok(());
+    }
+
+    #[derive(Tabled)]
+    struct DeploymentRow {
+        #[tabled(rename = "Network")]
+        network: String,
+        #[tabled(rename = "Multisig")]
+        multisig: String,
+        #[tabled(rename = "Feature Gate ID")]
+        feature_gate_id: String,
+        #[tabled(rename = "Signature")]
+        signature: String,
+    }
+
+    Output::header(&format!("🕘 Deployment History ({})", entries.len()));
+    for entry in &entries {
+        Output::separator();
+        let title = match &entry.label {
+            Some(label) => format!("{} ({})", entry.timestamp, label),
+            None => entry.timestamp.clone(),
+        };
+        Output::header(&title);
+        Output::field(
+            "Threshold",
+            &format!("{} of {} members", entry.threshold, entry.members.len()),
+        );
+        for member in &entry.members {
+            Output::field("Member", &format!("{} (mask {})", member.key, member.permissions));
+        }
+        let rows: Vec<DeploymentRow> = entry
+            .deployments
+            .iter()
+            .map(|deployment| DeploymentRow {
+                network: NetworkCluster::from_url(&deployment.rpc_url).label().to_string(),
+                multisig: deployment.multisig.clone(),
+                feature_gate_id: deployment.vault.clone(),
+                signature: deployment.signature.clone(),
+            })
+            .collect();
+        Output::table(&rows);
+    }
+
+    Ok(())
+}
+
+fn parse_date(flag: &str, value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        ToolError::Config(format!("{} expects a date as YYYY-MM-DD, got '{}'", flag, value)).into()
+    })
+}
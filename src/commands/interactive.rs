@@ -1,13 +1,15 @@
 use crate::commands::{
-    approve_feature_gate_activation_proposal, approve_feature_gate_activation_revocation_proposal, config_command, create_command, execute_feature_gate_activation_proposal, show_command
+    approve_feature_gate_activation_proposal, approve_feature_gate_activation_revocation_proposal, config_command, create_command, execute_feature_gate_activation_proposal, show_command,
+    review_proposals, CreateOptions,
 };
 use crate::squads::get_vault_pda;
 use crate::utils::*;
 use eyre::Result;
 use inquire::{Confirm, Select, Text};
 use solana_pubkey::Pubkey;
+use std::path::Path;
 
-pub async fn interactive_mode() -> Result<()> {
+pub async fn interactive_mode(output_dir: Option<&Path>, force: bool) -> Result<()> {
     let mut config = load_config()?;
 
     loop {
@@ -24,7 +26,14 @@ pub async fn interactive_mode() -> Result<()> {
         match choice {
             "Create new feature gate multisig" => {
                 let feepayer_path = prompt_for_fee_payer_path(&config)?;
-                create_command(&mut config, None, vec![], Some(feepayer_path)).await?;
+                create_command(
+                    &mut config,
+                    None,
+                    vec![],
+                    Some(feepayer_path),
+                    &CreateOptions::default(),
+                )
+                .await?;
             }
             "Transaction Generation" => {
                 let feature_gate_multisig_address =
@@ -33,6 +42,7 @@ pub async fn interactive_mode() -> Result<()> {
                 let fee_payer_path = prompt_for_fee_payer_path(&config)?;
 
                 let options = vec![
+                    "Review and vote on pending proposals",
                     "Approve feature gate activation proposal",
                     "Approve feature gate activation revocation proposal",
                     "Execute feature gate activation proposal",
@@ -40,6 +50,10 @@ pub async fn interactive_mode() -> Result<()> {
                 ];
                 let choice: &str = Select::new("What would you like to do?", options).prompt()?;
                 match choice {
+                    "Review and vote on pending proposals" => {
+                        review_proposals(&config, feature_gate_multisig_address, fee_payer_path)
+                            .await?;
+                    }
                     "Approve feature gate activation proposal" => {
                         Confirm::new(&format!(
                             "You're approving the activation of the following feature gate: {}?",
@@ -56,6 +70,7 @@ pub async fn interactive_mode() -> Result<()> {
                             voting_key,
                             fee_payer_path,
                             None,
+                            output_dir,
                         )
                         .await?;
                     }
@@ -75,6 +90,7 @@ pub async fn interactive_mode() -> Result<()> {
                             voting_key,
                             fee_payer_path,
                             None,
+                            output_dir,
                         )
                         .await?;
                     }
@@ -94,6 +110,8 @@ pub async fn interactive_mode() -> Result<()> {
                             voting_key,
                             fee_payer_path,
                             None,
+                            output_dir,
+                            force,
                         )
                         .await?;
                     }
@@ -103,7 +121,7 @@ pub async fn interactive_mode() -> Result<()> {
             }
             "Show feature gate multisig details" => {
                 let address = Text::new("Enter the main multisig address:").prompt()?;
-                show_command(&config, Some(address)).await?;
+                show_command(&config, Some(address), false, 0, false).await?;
             }
             "Show configuration" => {
                 config_command(&config).await?;
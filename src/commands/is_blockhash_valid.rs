@@ -0,0 +1,39 @@
+use crate::error::ToolError;
+use crate::output::Output;
+use crate::provision::create_rpc_client;
+use crate::utils::*;
+use eyre::Result;
+use solana_commitment_config::CommitmentConfig;
+use solana_hash::Hash;
+use std::str::FromStr;
+
+/// Checks a blockhash from a generated transaction is still accepted by the
+/// cluster, so signers don't sign a transaction that has already expired.
+/// Fails when the blockhash has expired so scripts can gate on the exit code.
+pub async fn is_blockhash_valid_command(
+    config: &Config,
+    blockhash: String,
+    rpc_url: Option<String>,
+) -> Result<()> {
+    let blockhash = Hash::from_str(blockhash.trim())
+        .map_err(|_| eyre::eyre!("Invalid blockhash: '{}'", blockhash.trim()))?;
+    let rpc_url = match rpc_url {
+        Some(url) => validate_rpc_url(&url)?,
+        None => choose_network_from_config(config)?,
+    };
+    let rpc_client = create_rpc_client(&rpc_url);
+
+    let is_valid = rpc_client
+        .is_blockhash_valid(&blockhash, CommitmentConfig::processed())
+        .map_err(|e| ToolError::Network(format!("Failed to check blockhash: {}", e)))?;
+
+    if !is_valid {
+        return Err(eyre::eyre!(
+            "Blockhash {} has expired; regenerate the transaction before signing",
+            blockhash
+        ));
+    }
+
+    Output::success(&format!("Blockhash {} is still valid on {}", blockhash, rpc_url));
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::feature_gate_program::FeatureState;
+use crate::network::NetworkCluster;
+use crate::output::Output;
+use crate::provision::{create_rpc_client, fetch_feature_state};
+use crate::squads::get_vault_pda;
+use crate::utils::*;
+use eyre::Result;
+use tabled::Tabled;
+
+/// Lists the saved multisigs. With `features`, each one's feature gate ID (its
+/// default vault) is looked up on its network, so the table doubles as a
+/// registry of the features being governed and how far along they are.
+pub async fn list_command(config: &Config, features: bool) -> Result<()> {
+    if config.multisigs.is_empty() {
+        Output::info("No saved multisigs yet. Multisigs created with this tool are listed here.");
+        return Ok(());
+    }
+
+    if features {
+        return list_features(config);
+    }
+
+    #[derive(Tabled)]
+    struct MultisigRow {
+        #[tabled(rename = "Label")]
+        label: String,
+        #[tabled(rename = "Address")]
+        address: String,
+        #[tabled(rename = "Network")]
+        network: String,
+    }
+
+    let rows: Vec<MultisigRow> = config
+        .multisigs
+        .iter()
+        .map(|saved| MultisigRow {
+            label: saved.label.clone().unwrap_or_else(|| "-".to_string()),
+            address: saved.address.clone(),
+            network: saved.network.clone(),
+        })
+        .collect();
+
+    Output::header(&format!("📋 Saved Multisigs ({})", rows.len()));
+    Output::table(&rows);
+
+    Ok(())
+}
+
+fn list_features(config: &Config) -> Result<()> {
+    #[derive(Tabled)]
+    struct FeatureRow {
+        #[tabled(rename = "Label")]
+        label: String,
+        #[tabled(rename = "Feature Gate ID")]
+        feature_id: String,
+        #[tabled(rename = "Network")]
+        network: &'static str,
+        #[tabled(rename = "Status")]
+        status: String,
+    }
+
+    let progress = Output::spinner("Fetching feature accounts...");
+    let rows: Vec<FeatureRow> = config
+        .multisigs
+        .iter()
+        .map(|saved| {
+            let (feature_id, status) = match parse_pubkey(&saved.address) {
+                Ok(multisig) => {
+                    let feature_id = get_vault_pda(&multisig, 0, None).0;
+                    let status = match fetch_feature_state(
+                        &create_rpc_client(&saved.network),
+                        &feature_id,
+                    ) {
+                        Ok(FeatureState::NotCreated) => "Not activated".to_string(),
+                        Ok(FeatureState::Pending) => "Pending activation".to_string(),
+                        Ok(FeatureState::Activated(slot)) => format!("Active since slot {}", slot),
+                        Err(e) => format!("Unknown ({})", e),
+                    };
+                    (feature_id.to_string(), status)
+                }
+                Err(_) => ("-".to_string(), "Invalid multisig address".to_string()),
+            };
+            FeatureRow {
+                label: saved.label.clone().unwrap_or_else(|| "-".to_string()),
+                feature_id,
+                network: NetworkCluster::from_url(&saved.network).label(),
+                status,
+            }
+        })
+        .collect();
+    progress.finish_and_clear();
+
+    Output::header(&format!("🚩 Governed Features ({})", rows.len()));
+    Output::table(&rows);
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use crate::constants::MAX_ACCOUNTS_PER_REQUEST;
+use crate::output::Output;
+use crate::provision::{
+    create_rpc_client, fetch_accounts_batched, fetch_multisig, index_gaps, IndexAccounts,
+};
+use crate::squads::{get_proposal_pda, get_transaction_pda, Proposal};
+use crate::utils::*;
+use borsh::BorshDeserialize;
+use eyre::{Result, WrapErr};
+use solana_pubkey::Pubkey;
+use tabled::Tabled;
+
+#[derive(Tabled)]
+struct IndexRow {
+    #[tabled(rename = "Index")]
+    index: u64,
+    #[tabled(rename = "Accounts")]
+    accounts: &'static str,
+    #[tabled(rename = "Status")]
+    status: String,
+}
+
+/// Lists every transaction index of a multisig with which of its transaction
+/// and proposal accounts exist, then reports the gaps left by partial
+/// creations and closed indices
+pub async fn list_proposals_command(
+    config: &Config,
+    address: String,
+    rpc_url: Option<String>,
+) -> Result<()> {
+    let multisig_address = config.resolve_multisig(&address)?;
+    let rpc_url = match rpc_url {
+        Some(url) => validate_rpc_url(&url)?,
+        None => choose_network_from_config(config)?,
+    };
+    let rpc_client = create_rpc_client(&rpc_url);
+    let multisig = fetch_multisig(&rpc_client, &multisig_address)?;
+
+    if multisig.transaction_index == 0 {
+        Output::info("No transactions yet (transaction_index = 0)");
+        return Ok(());
+    }
+
+    // Transaction and proposal of each index, fetched in as few requests as possible
+    let indices: Vec<u64> = (1..=multisig.transaction_index).collect();
+    let keys: Vec<Pubkey> = indices
+        .iter()
+        .flat_map(|index| {
+            [
+                get_transaction_pda(&multisig_address, *index, None).0,
+                get_proposal_pda(&multisig_address, *index, None).0,
+            ]
+        })
+        .collect();
+    let accounts = fetch_accounts_batched(&rpc_client, &keys, MAX_ACCOUNTS_PER_REQUEST)
+        .wrap_err("Failed to fetch transactions and proposals")?;
+
+    let mut states = Vec::with_capacity(indices.len());
+    let mut rows = Vec::with_capacity(indices.len());
+    for (index, pair) in indices.iter().zip(accounts.chunks(2)) {
+        let state = IndexAccounts::from_presence(pair[0].is_some(), pair[1].is_some());
+        let status = match &pair[1] {
+            Some(account) if account.data.len() >= 8 => {
+                match Proposal::deserialize(&mut &account.data[8..]) {
+                    Ok(proposal)
+                        if multisig.is_stale(*index) && proposal.status.is_stopped_when_stale() =>
+                    {
+                        format!("{} (stale)", proposal.status.name())
+                    }
+                    Ok(proposal) => proposal.status.name().to_string(),
+                    Err(_) => "Unreadable".to_string(),
+                }
+            }
+            Some(_) => "Unreadable".to_string(),
+            None => "-".to_string(),
+        };
+        states.push((*index, state));
+        rows.push(IndexRow {
+            index: *index,
+            accounts: state.label(),
+            status,
+        });
+    }
+
+    Output::header(&format!(
+        "📋 Transaction indices 1-{} of {}",
+        multisig.transaction_index, multisig_address
+    ));
+    Output::table(&rows);
+
+    let gaps = index_gaps(&states);
+    if gaps.is_empty() {
+        Output::success("Every index has its transaction and proposal");
+        return Ok(());
+    }
+    for (range, state) in &gaps {
+        let indices = if range.start() == range.end() {
+            format!("Index {}", range.start())
+        } else {
+            format!("Indices {}-{}", range.start(), range.end())
+        };
+        let explanation = match state {
+            IndexAccounts::TransactionOnly => "the proposal creation likely failed",
+            IndexAccounts::ProposalOnly => "the transaction account is gone",
+            _ => "both accounts were closed and their rent reclaimed",
+        };
+        Output::warning(&format!("{}: {} ({})", indices, state.label(), explanation));
+    }
+    Ok(())
+}
@@ -1,11 +1,41 @@
+pub mod activate_proposal;
+pub mod bench;
+pub mod close_proposal;
 pub mod create;
+pub mod create_member_lut;
+pub mod create_proposals;
+pub mod create_transaction;
+pub mod decode;
+pub mod history;
 pub mod show;
 pub mod config;
 pub mod interactive;
+pub mod is_blockhash_valid;
+pub mod list;
+pub mod list_proposals;
+pub mod review;
 pub mod transaction_generation;
+pub mod vaults;
+pub mod watch;
+pub mod whoami;
 
-pub use create::create_command;
+pub use activate_proposal::activate_proposal_command;
+pub use bench::bench_command;
+pub use close_proposal::close_proposal_command;
+pub use create::{batch_create_command, create_command, CreateOptions, SummaryFormat};
+pub use create_member_lut::create_member_lut_command;
+pub use create_proposals::create_proposals_command;
+pub use create_transaction::create_transaction_command;
+pub use decode::decode_command;
+pub use history::history_command;
 pub use show::show_command;
-pub use config::config_command;
+pub use config::{config_command, export_config_command, import_config_command};
 pub use interactive::interactive_mode;
-pub use transaction_generation::*;
\ No newline at end of file
+pub use is_blockhash_valid::is_blockhash_valid_command;
+pub use list::list_command;
+pub use list_proposals::list_proposals_command;
+pub use review::{approve_all_pending, review_proposals};
+pub use transaction_generation::*;
+pub use vaults::vaults_command;
+pub use watch::watch_command;
+pub use whoami::whoami_command;
\ No newline at end of file
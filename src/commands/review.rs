@@ -0,0 +1,344 @@
+use crate::constants::MAX_ACCOUNTS_PER_REQUEST;
+use crate::feature_gate_program::describe_feature_gate_intent;
+use crate::output::Output;
+use crate::provision::{
+    create_rpc_client, create_vote_transaction_message, fetch_accounts_batched, fetch_multisig,
+    send_and_confirm_transaction, SendStrategy, Vote,
+};
+use crate::squads::{
+    get_proposal_pda, get_transaction_pda, Multisig, Permission, Proposal, ProposalStatus,
+    VaultTransaction, SQUADS_MULTISIG_PROGRAM_ID,
+};
+use crate::utils::*;
+use borsh::BorshDeserialize;
+use colored::*;
+use eyre::{Result, WrapErr};
+use inquire::{Confirm, Select, Text};
+use solana_client::rpc_client::RpcClient;
+use solana_keypair::Keypair;
+use solana_message::VersionedMessage;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::versioned::VersionedTransaction;
+use tabled::Tabled;
+
+/// An active proposal together with what its transaction would do
+struct PendingProposal {
+    transaction_index: u64,
+    proposal: Proposal,
+    intent: String,
+}
+
+/// Guided governance session: lists the multisig's active proposals, lets the
+/// member vote on one, then refreshes the list until they're done
+pub async fn review_proposals(
+    config: &Config,
+    multisig_address: Pubkey,
+    fee_payer_path: String,
+) -> Result<()> {
+    let rpc_url = choose_network_from_config(config)?;
+    let rpc_client = create_rpc_client(&rpc_url);
+
+    let fee_payer = read_keypair_file(&fee_payer_path)
+        .map_err(|e| eyre::eyre!("Failed to load keypair from {}: {}", fee_payer_path, e))?;
+    let voter_path = Text::new("Enter the voting member keypair file path:")
+        .with_default(&fee_payer_path)
+        .prompt()?;
+    let voter_path = expand_tilde_path(&voter_path)?;
+    let voter = read_keypair_file(&voter_path)
+        .map_err(|e| eyre::eyre!("Failed to load keypair from {}: {}", voter_path, e))?;
+
+    loop {
+        let multisig = fetch_multisig(&rpc_client, &multisig_address)?;
+        let can_vote = multisig
+            .members
+            .iter()
+            .any(|member| member.key == voter.pubkey() && member.permissions.has(Permission::Vote));
+        if !can_vote {
+            Output::warning(&format!(
+                "{} is not a voting member of this multisig; votes will fail",
+                voter.pubkey()
+            ));
+        }
+
+        let pending = fetch_pending_proposals(&rpc_client, &multisig_address, &multisig)?;
+        if pending.is_empty() {
+            Output::info("No active proposals awaiting votes");
+            return Ok(());
+        }
+
+        print_pending_proposals(&pending, multisig.threshold, &voter.pubkey());
+
+        let mut choices: Vec<String> = pending
+            .iter()
+            .map(|p| format!("#{}: {}", p.transaction_index, p.intent))
+            .collect();
+        choices.push("Done".to_string());
+        let choice = Select::new("Select a proposal to vote on:", choices.clone()).prompt()?;
+        let Some(selected) = choices.iter().position(|c| *c == choice).and_then(|i| pending.get(i))
+        else {
+            return Ok(());
+        };
+
+        let vote = match Select::new("Your vote:", vec!["Approve", "Reject", "Back"]).prompt()? {
+            "Approve" => Vote::Approve,
+            "Reject" => Vote::Reject,
+            _ => continue,
+        };
+        let confirmed = Confirm::new(&format!(
+            "{} proposal #{} ({})?",
+            if vote == Vote::Approve { "Approve" } else { "Reject" },
+            selected.transaction_index,
+            selected.intent
+        ))
+        .with_default(false)
+        .prompt()?;
+        if !confirmed {
+            continue;
+        }
+
+        match send_vote(
+            &rpc_client,
+            &multisig_address,
+            selected.transaction_index,
+            &voter,
+            &fee_payer,
+            vote,
+        ) {
+            Ok(signature) => Output::success(&format!("Vote confirmed: {}", signature)),
+            Err(e) => Output::error(&format!("Vote failed: {}", e)),
+        }
+        println!();
+    }
+}
+
+/// Approves every active proposal the voter hasn't approved yet, after a
+/// single confirmation, and reports how each vote went
+pub async fn approve_all_pending(
+    config: &Config,
+    address: String,
+    rpc_url: Option<String>,
+    keypair_path: Option<String>,
+    yes: bool,
+) -> Result<()> {
+    let multisig_address = config.resolve_multisig(&address)?;
+    let rpc_url = match rpc_url {
+        Some(url) => validate_rpc_url(&url)?,
+        None => choose_network_from_config(config)?,
+    };
+    let rpc_client = create_rpc_client(&rpc_url);
+
+    let voter = load_fee_payer_keypair(config, keypair_path)?.ok_or_else(|| {
+        eyre::eyre!("A voting member keypair is required to approve proposals; pass --keypair")
+    })?;
+
+    let multisig = fetch_multisig(&rpc_client, &multisig_address)?;
+    let can_vote = multisig
+        .members
+        .iter()
+        .any(|member| member.key == voter.pubkey() && member.permissions.has(Permission::Vote));
+    if !can_vote {
+        return Err(eyre::eyre!(
+            "{} is not a voting member of multisig {}",
+            voter.pubkey(),
+            multisig_address
+        ));
+    }
+
+    let pending = fetch_pending_proposals(&rpc_client, &multisig_address, &multisig)?;
+    if pending.is_empty() {
+        Output::info("No active proposals awaiting votes");
+        return Ok(());
+    }
+    print_pending_proposals(&pending, multisig.threshold, &voter.pubkey());
+
+    let (already_approved, to_approve): (Vec<_>, Vec<_>) = pending
+        .iter()
+        .partition(|p| p.proposal.approved.contains(&voter.pubkey()));
+    if !already_approved.is_empty() {
+        Output::info(&format!(
+            "Skipping {} proposal(s) you have already approved",
+            already_approved.len()
+        ));
+    }
+    if to_approve.is_empty() {
+        return Ok(());
+    }
+
+    if !yes {
+        let proceed = Confirm::new(&format!("Approve {} proposal(s)?", to_approve.len()))
+            .with_default(false)
+            .prompt()?;
+        if !proceed {
+            return Err(crate::error::ToolError::Cancelled("User aborted".to_string()).into());
+        }
+    }
+
+    #[derive(Tabled)]
+    struct ApprovalRow {
+        #[tabled(rename = "Index")]
+        index: u64,
+        #[tabled(rename = "Intent")]
+        intent: String,
+        #[tabled(rename = "Result")]
+        result: String,
+    }
+
+    let mut rows = Vec::with_capacity(to_approve.len());
+    let mut failures = 0;
+    for p in to_approve {
+        let result = send_vote(
+            &rpc_client,
+            &multisig_address,
+            p.transaction_index,
+            &voter,
+            &voter,
+            Vote::Approve,
+        );
+        let result = match result {
+            Ok(signature) => format!("Approved: {}", signature),
+            Err(e) => {
+                failures += 1;
+                format!("Failed: {}", e)
+            }
+        };
+        rows.push(ApprovalRow {
+            index: p.transaction_index,
+            intent: p.intent.clone(),
+            result,
+        });
+    }
+
+    println!();
+    Output::table(&rows);
+
+    if failures > 0 {
+        return Err(eyre::eyre!("{} of {} approvals failed", failures, rows.len()));
+    }
+    Output::success(&format!("Approved {} proposal(s)", rows.len()));
+    Ok(())
+}
+
+/// Fetches every non-stale proposal and keeps the active ones, decoding the
+/// intent of their vault transactions
+fn fetch_pending_proposals(
+    rpc_client: &RpcClient,
+    multisig_address: &Pubkey,
+    multisig: &Multisig,
+) -> Result<Vec<PendingProposal>> {
+    let indices: Vec<u64> =
+        (multisig.stale_transaction_index + 1..=multisig.transaction_index).collect();
+    let keys: Vec<Pubkey> = indices
+        .iter()
+        .flat_map(|index| {
+            [
+                get_proposal_pda(multisig_address, *index, None).0,
+                get_transaction_pda(multisig_address, *index, None).0,
+            ]
+        })
+        .collect();
+    let accounts = fetch_accounts_batched(rpc_client, &keys, MAX_ACCOUNTS_PER_REQUEST)
+        .wrap_err("Failed to fetch proposals")?;
+    let mut pending = Vec::new();
+
+    for (index, pair) in indices.iter().zip(accounts.chunks(2)) {
+        let Some(proposal_account) = &pair[0] else {
+            continue;
+        };
+        if proposal_account.owner != SQUADS_MULTISIG_PROGRAM_ID
+            || proposal_account.data.len() < 8
+        {
+            continue;
+        }
+        let Ok(proposal) = Proposal::deserialize(&mut &proposal_account.data[8..]) else {
+            continue;
+        };
+        if !matches!(proposal.status, ProposalStatus::Active { .. }) {
+            continue;
+        }
+
+        let intent = pair[1]
+            .as_ref()
+            .filter(|account| account.data.len() >= 8)
+            .and_then(|account| VaultTransaction::deserialize(&mut &account.data[8..]).ok())
+            .map(|transaction| describe_feature_gate_intent(&transaction.message))
+            .unwrap_or_else(|| "Not a vault transaction".to_string());
+
+        pending.push(PendingProposal {
+            transaction_index: *index,
+            proposal,
+            intent,
+        });
+    }
+
+    Ok(pending)
+}
+
+fn print_pending_proposals(pending: &[PendingProposal], threshold: u16, voter: &Pubkey) {
+    #[derive(Tabled)]
+    struct ProposalRow {
+        #[tabled(rename = "Index")]
+        index: u64,
+        #[tabled(rename = "Intent")]
+        intent: String,
+        #[tabled(rename = "Approvals")]
+        approvals: String,
+        #[tabled(rename = "Rejections")]
+        rejections: usize,
+        #[tabled(rename = "Your Vote")]
+        your_vote: &'static str,
+    }
+
+    let rows: Vec<ProposalRow> = pending
+        .iter()
+        .map(|p| ProposalRow {
+            index: p.transaction_index,
+            intent: p.intent.clone(),
+            approvals: format!("{}/{}", p.proposal.approved.len(), threshold),
+            rejections: p.proposal.rejected.len(),
+            your_vote: if p.proposal.approved.contains(voter) {
+                "Approved"
+            } else if p.proposal.rejected.contains(voter) {
+                "Rejected"
+            } else {
+                "-"
+            },
+        })
+        .collect();
+
+    println!();
+    println!("{}", "🗳️  Active Proposals".bright_yellow().bold());
+    Output::table(&rows);
+    println!();
+}
+
+fn send_vote(
+    rpc_client: &RpcClient,
+    multisig_address: &Pubkey,
+    transaction_index: u64,
+    voter: &Keypair,
+    fee_payer: &Keypair,
+    vote: Vote,
+) -> Result<String> {
+    let blockhash = rpc_client
+        .get_latest_blockhash()
+        .map_err(|e| eyre::eyre!("Failed to get recent blockhash: {}", e))?;
+    let message = create_vote_transaction_message(
+        &SQUADS_MULTISIG_PROGRAM_ID,
+        multisig_address,
+        transaction_index,
+        &voter.pubkey(),
+        &fee_payer.pubkey(),
+        vote,
+        blockhash,
+    )?;
+
+    let signers: Vec<&dyn Signer> = if voter.pubkey() == fee_payer.pubkey() {
+        vec![fee_payer]
+    } else {
+        vec![fee_payer, voter]
+    };
+    let transaction = VersionedTransaction::try_new(VersionedMessage::V0(message), &signers)?;
+
+    send_and_confirm_transaction(&transaction, rpc_client, SendStrategy::default())
+}
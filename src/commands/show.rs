@@ -1,38 +1,87 @@
+use crate::output::Output;
 use crate::constants::*;
-use crate::squads::{get_vault_pda, get_transaction_pda, get_proposal_pda, Multisig, VaultTransaction, Proposal, ProposalStatus};
-use crate::provision::{get_account_data_with_retry, create_rpc_client};
+use crate::network::NetworkCluster;
+use crate::squads::{get_vault_pda, get_transaction_pda, get_proposal_pda, Multisig, Permission, VaultTransaction, Proposal, ProposalStatus};
+use crate::provision::{
+    create_nonblocking_rpc_client, create_rpc_client, ensure_multisig_not_truncated,
+    fetch_multisig_data_async, get_account_data_with_retry,
+};
 use crate::utils::*;
-use eyre::Result;
+use eyre::{Result, WrapErr};
 use colored::*;
 use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::TokenAccountsFilter;
 use solana_pubkey::Pubkey;
-use std::str::FromStr;
-use tabled::{settings::Style, Table, Tabled};
-
-pub async fn show_command(config: &Config, address: Option<String>) -> Result<()> {
+use tabled::Tabled;
+
+/// Searches the configured networks for a multisig and prints its details.
+/// With `identify_clusters`, also reports the cluster each network's genesis
+/// hash belongs to, so a "not found" can be told apart from a wrong network.
+/// The holdings of vault `vault_index` are listed too, with its SPL token
+/// balances when `show_tokens` is set.
+pub async fn show_command(
+    config: &Config,
+    address: Option<String>,
+    identify_clusters: bool,
+    vault_index: u8,
+    show_tokens: bool,
+) -> Result<()> {
     let address = if let Some(addr) = address {
-        // Validate provided address
-        match Pubkey::from_str(&addr) {
-            Ok(_) => addr,
-            Err(_) => {
+        // Validate provided address, or resolve a saved label to its address
+        match config.resolve_multisig(&addr) {
+            Ok(pubkey) => pubkey.to_string(),
+            Err(e) => {
                 println!(
                     "{} Invalid address format: {}",
                     "❌".bright_red(),
                     addr.bright_red()
                 );
-                return Err(eyre::eyre!("Invalid multisig address format"));
+                return Err(e);
             }
         }
     } else {
         validate_pubkey_with_retry("Enter multisig address:")?.to_string()
     };
-    show_multisig(config, &address).await
+    show_multisig(config, &address, identify_clusters, vault_index, show_tokens).await
+}
+
+/// Prints which cluster an RPC endpoint actually serves, warning when its URL
+/// suggests a different one
+fn report_cluster(rpc_url: &str, genesis_hash: Result<String, impl std::fmt::Display>) {
+    let genesis_hash = match genesis_hash {
+        Ok(hash) => hash,
+        Err(e) => {
+            println!("⚠️  Could not fetch genesis hash from {}: {}", rpc_url, e);
+            return;
+        }
+    };
+    let actual = NetworkCluster::from_genesis_hash(&genesis_hash);
+    println!(
+        "🧬 {} serves {} (genesis {})",
+        rpc_url.bright_white(),
+        actual.label().bright_cyan(),
+        genesis_hash
+    );
+    let expected = NetworkCluster::from_url(rpc_url);
+    if expected != NetworkCluster::Custom && expected != actual {
+        Output::warning(&format!(
+            "{} looks like {} but its genesis hash is {}'s",
+            rpc_url,
+            expected.label(),
+            actual.label()
+        ));
+    }
 }
 
-async fn show_multisig(config: &Config, address: &str) -> Result<()> {
+async fn show_multisig(
+    config: &Config,
+    address: &str,
+    identify_clusters: bool,
+    vault_index: u8,
+    show_tokens: bool,
+) -> Result<()> {
     // Parse the multisig address
-    let multisig_pubkey = Pubkey::from_str(address)
-        .map_err(|_| eyre::eyre!("Invalid multisig address format"))?;
+    let multisig_pubkey = parse_pubkey(address)?;
 
     println!(
         "{}",
@@ -40,7 +89,7 @@ async fn show_multisig(config: &Config, address: &str) -> Result<()> {
     );
     println!();
 
-    // Try all configured networks until we find the account
+    // Query all configured networks at once; the first to return the account wins
     let mut account_data = None;
     let mut successful_rpc_url = None;
     let mut last_error = None;
@@ -57,15 +106,46 @@ async fn show_multisig(config: &Config, address: &str) -> Result<()> {
     }
     println!();
 
+    let mut searches = tokio::task::JoinSet::new();
     for rpc_url in &networks_to_try {
         println!("🌐 Trying network: {}", rpc_url.bright_white());
 
-        let rpc_client = create_rpc_client(rpc_url);
-        match get_account_data_with_retry(&rpc_client, &multisig_pubkey) {
+        let rpc_url = rpc_url.clone();
+        searches.spawn(async move {
+            let rpc_client = create_nonblocking_rpc_client(&rpc_url);
+            let genesis_hash = async {
+                if identify_clusters {
+                    Some(rpc_client.get_genesis_hash().await)
+                } else {
+                    None
+                }
+            };
+            let (result, genesis_hash) = tokio::join!(
+                fetch_multisig_data_async(&rpc_client, &multisig_pubkey),
+                genesis_hash
+            );
+            (rpc_url, result, genesis_hash)
+        });
+    }
+
+    while let Some(joined) = searches.join_next().await {
+        let (rpc_url, result, genesis_hash) = match joined {
+            Ok(search) => search,
+            Err(e) => {
+                last_error = Some(format!("Network search task failed: {}", e));
+                continue;
+            }
+        };
+        if let Some(genesis_hash) = genesis_hash {
+            report_cluster(&rpc_url, genesis_hash.map(|hash| hash.to_string()));
+        }
+        match result {
             Ok(data) => {
                 println!("✅ Found account on: {}", rpc_url.bright_green());
                 account_data = Some(data);
-                successful_rpc_url = Some(rpc_url.clone());
+                successful_rpc_url = Some(rpc_url);
+                // No need to wait on the slower networks
+                searches.abort_all();
                 break;
             }
             Err(e) => {
@@ -86,6 +166,9 @@ async fn show_multisig(config: &Config, address: &str) -> Result<()> {
     let (rpc_url, account_data) = match (successful_rpc_url, account_data) {
         (Some(url), Some(data)) => (url, data),
         _ => {
+            if !identify_clusters {
+                Output::hint("Pass --identify-clusters to confirm which clusters were searched");
+            }
             return Err(eyre::eyre!(
                 "{}",
                 last_error.unwrap_or_else(
@@ -109,7 +192,8 @@ async fn show_multisig(config: &Config, address: &str) -> Result<()> {
         ));
     }
 
-    println!("📊 Account data length: {} bytes", account_data.len());
+    println!("📊 Account data fetched: {} bytes", account_data.len());
+    ensure_multisig_not_truncated(&multisig_pubkey, &account_data)?;
 
     // Strip the first 8 bytes (discriminator) and deserialize
     let multisig: Multisig = match borsh::BorshDeserialize::deserialize(&mut &account_data[8..]) {
@@ -128,16 +212,86 @@ async fn show_multisig(config: &Config, address: &str) -> Result<()> {
     println!("✅ Multisig deserialized successfully!");
 
     // Display the multisig details
-    display_multisig_details(&multisig, &multisig_pubkey)?;
+    display_multisig_details(&multisig, &multisig_pubkey, config.label_for(&multisig_pubkey))?;
 
-    // Fetch and display transaction and proposal details for indices 1 and 2
     let rpc_client = create_rpc_client(&rpc_url);
+    display_vault_holdings(&rpc_client, &multisig_pubkey, vault_index, show_tokens)?;
+
+    // Fetch and display transaction and proposal details for indices 1 and 2
     fetch_and_display_transactions_and_proposals(&rpc_client, &multisig_pubkey, &multisig).await?;
 
     Ok(())
 }
 
-fn display_multisig_details(multisig: &Multisig, address: &Pubkey) -> Result<()> {
+fn display_vault_holdings(
+    rpc_client: &RpcClient,
+    multisig: &Pubkey,
+    vault_index: u8,
+    show_tokens: bool,
+) -> Result<()> {
+    let (vault, _) = get_vault_pda(multisig, vault_index, None);
+
+    #[derive(Tabled)]
+    struct Holding {
+        #[tabled(rename = "Asset")]
+        asset: String,
+        #[tabled(rename = "Account")]
+        account: String,
+        #[tabled(rename = "Balance")]
+        balance: String,
+    }
+
+    let lamports = rpc_client
+        .get_balance(&vault)
+        .wrap_err_with(|| format!("Failed to fetch the balance of vault {}", vault))?;
+    let mut holdings = vec![Holding {
+        asset: "SOL".to_string(),
+        account: vault.to_string(),
+        balance: format!("{:.9}", lamports as f64 / LAMPORTS_PER_SOL as f64),
+    }];
+
+    if show_tokens {
+        for program_id in [SPL_TOKEN_PROGRAM_ID, SPL_TOKEN_2022_PROGRAM_ID] {
+            let program_id = parse_pubkey(program_id)?;
+            let accounts = rpc_client
+                .get_token_accounts_by_owner(&vault, TokenAccountsFilter::ProgramId(program_id))
+                .wrap_err_with(|| format!("Failed to fetch token accounts of vault {}", vault))?;
+            for keyed in accounts {
+                // Token accounts come back jsonParsed: data.parsed.info holds mint and amount
+                let account = serde_json::to_value(&keyed.account)?;
+                let info = &account["data"]["parsed"]["info"];
+                holdings.push(Holding {
+                    asset: info["mint"].as_str().unwrap_or("unknown mint").to_string(),
+                    account: keyed.pubkey,
+                    balance: info["tokenAmount"]["uiAmountString"]
+                        .as_str()
+                        .unwrap_or("unknown")
+                        .to_string(),
+                });
+            }
+        }
+    }
+
+    println!(
+        "{} (vault {}: {})",
+        "💰 HOLDINGS".bright_blue().bold(),
+        vault_index,
+        vault.to_string().bright_white()
+    );
+    println!();
+    Output::table(&holdings);
+    if !show_tokens {
+        Output::hint("Pass --tokens to include SPL token balances");
+    }
+    println!();
+    Ok(())
+}
+
+fn display_multisig_details(
+    multisig: &Multisig,
+    address: &Pubkey,
+    label: Option<&str>,
+) -> Result<()> {
     println!("{}", "📋 MULTISIG DETAILS".bright_green().bold());
     println!("{}", "═".repeat(80).bright_green());
     println!();
@@ -151,7 +305,7 @@ fn display_multisig_details(multisig: &Multisig, address: &Pubkey) -> Result<()>
         value: String,
     }
 
-    let info_data = vec![
+    let mut info_data = vec![
         MultisigInfo {
             property: "Multisig Address".to_string(),
             value: address.to_string(),
@@ -168,7 +322,7 @@ fn display_multisig_details(multisig: &Multisig, address: &Pubkey) -> Result<()>
             property: "Threshold".to_string(),
             value: {
                 let voting_members_count = multisig.members.iter()
-                    .filter(|member| member.permissions.mask & 2 != 0) // Check Vote permission (bit 1)
+                    .filter(|member| member.permissions.has(Permission::Vote))
                     .count();
                 format!("{} of {} voting members", multisig.threshold, voting_members_count)
             },
@@ -197,10 +351,17 @@ fn display_multisig_details(multisig: &Multisig, address: &Pubkey) -> Result<()>
             value: multisig.bump.to_string(),
         },
     ];
+    if let Some(label) = label {
+        info_data.insert(
+            0,
+            MultisigInfo {
+                property: "Label".to_string(),
+                value: label.to_string(),
+            },
+        );
+    }
 
-    let mut info_table = Table::new(info_data);
-    info_table.with(Style::rounded());
-    println!("{}", info_table);
+    Output::table(&info_data);
     println!();
 
     // Members table
@@ -242,9 +403,7 @@ fn display_multisig_details(multisig: &Multisig, address: &Pubkey) -> Result<()>
         })
         .collect();
 
-    let mut members_table = Table::new(member_data);
-    members_table.with(Style::rounded());
-    println!("{}", members_table);
+    Output::table(&member_data);
     println!();
 
     // Calculate and display vault addresses for common indices
@@ -279,9 +438,7 @@ fn display_multisig_details(multisig: &Multisig, address: &Pubkey) -> Result<()>
         },
     ];
 
-    let mut vault_table = Table::new(vault_data);
-    vault_table.with(Style::rounded());
-    println!("{}", vault_table);
+    Output::table(&vault_data);
     println!();
 
     println!(
@@ -343,7 +500,7 @@ async fn fetch_and_display_transactions_and_proposals(
         }
 
         // Fetch proposal account  
-        match fetch_and_display_proposal(rpc_client, &proposal_pda, tx_index).await {
+        match fetch_and_display_proposal(rpc_client, &proposal_pda, tx_index, multisig).await {
             Ok(_) => {},
             Err(e) => {
                 println!("❌ Failed to fetch proposal {}: {}", tx_index, e.to_string().bright_red());
@@ -429,9 +586,7 @@ async fn fetch_and_display_transaction(
         },
     ];
 
-    let mut tx_table = Table::new(tx_info);
-    tx_table.with(Style::rounded());
-    println!("{}", tx_table);
+    Output::table(&tx_info);
 
     // Display transaction message details
     println!();
@@ -472,9 +627,7 @@ async fn fetch_and_display_transaction(
         },
     ];
 
-    let mut msg_table = Table::new(msg_info);
-    msg_table.with(Style::rounded());
-    println!("{}", msg_table);
+    Output::table(&msg_info);
 
     // Display detailed instruction breakdown
     if !transaction.message.instructions.is_empty() {
@@ -548,9 +701,7 @@ async fn fetch_and_display_transaction(
             })
             .collect();
 
-        let mut instructions_table = Table::new(instruction_details);
-        instructions_table.with(Style::rounded());
-        println!("{}", instructions_table);
+        Output::table(&instruction_details);
 
         // Display account keys reference table
         if !transaction.message.account_keys.is_empty() {
@@ -590,9 +741,7 @@ async fn fetch_and_display_transaction(
                 })
                 .collect();
 
-            let mut account_keys_table = Table::new(account_key_info);
-            account_keys_table.with(Style::rounded());
-            println!("{}", account_keys_table);
+            Output::table(&account_key_info);
         }
     }
 
@@ -602,10 +751,24 @@ async fn fetch_and_display_transaction(
     Ok(())
 }
 
+/// e.g. "approved: 2 of 3 required — 1 more needed"
+fn approvals_summary(multisig: &Multisig, proposal: &Proposal) -> String {
+    let progress = format!(
+        "approved: {} of {} required",
+        proposal.approved.len(),
+        multisig.threshold
+    );
+    match multisig.approvals_needed(proposal) {
+        0 => format!("{} — threshold reached", progress),
+        needed => format!("{} — {} more needed", progress, needed),
+    }
+}
+
 async fn fetch_and_display_proposal(
     rpc_client: &RpcClient,
     proposal_pda: &Pubkey,
     tx_index: u64,
+    multisig: &Multisig,
 ) -> Result<()> {
     println!("🗳️  Proposal Account Data:");
 
@@ -645,7 +808,9 @@ async fn fetch_and_display_proposal(
     }
 
     let status_str = match &proposal.status {
-        ProposalStatus::Draft { timestamp } => format!("Draft ({})", timestamp),
+        ProposalStatus::Draft { timestamp } => {
+            format!("Draft ({}) - voting opens after activate-proposal", timestamp)
+        }
         ProposalStatus::Active { timestamp } => format!("Active ({})", timestamp),
         ProposalStatus::Rejected { timestamp } => format!("Rejected ({})", timestamp),
         ProposalStatus::Approved { timestamp } => format!("Approved ({})", timestamp),
@@ -654,6 +819,13 @@ async fn fetch_and_display_proposal(
         #[allow(deprecated)]
         ProposalStatus::Executing => "Executing (deprecated)".to_string(),
     };
+    // A config change stops every unapproved proposal at or below the stale index
+    let is_stale = multisig.is_stale(tx_index) && proposal.status.is_stopped_when_stale();
+    let status_str = if is_stale {
+        format!("{} - STALE", status_str)
+    } else {
+        status_str
+    };
 
     let proposal_info = vec![
         ProposalInfo {
@@ -673,8 +845,8 @@ async fn fetch_and_display_proposal(
             value: proposal.bump.to_string(),
         },
         ProposalInfo {
-            property: "Approved Count".to_string(),
-            value: proposal.approved.len().to_string(),
+            property: "Approvals".to_string(),
+            value: approvals_summary(multisig, &proposal),
         },
         ProposalInfo {
             property: "Rejected Count".to_string(),
@@ -686,9 +858,31 @@ async fn fetch_and_display_proposal(
         },
     ];
 
-    let mut proposal_table = Table::new(proposal_info);
-    proposal_table.with(Style::rounded());
-    println!("{}", proposal_table);
+    Output::table(&proposal_info);
+    if is_stale {
+        Output::warning(&format!(
+            "Proposal {} is stale (stale transaction index {}): it can no longer be voted on",
+            tx_index, multisig.stale_transaction_index
+        ));
+    }
+
+    let pending_approvers = multisig.pending_approvers(&proposal);
+    if !proposal.status.is_terminal() && !is_stale && !pending_approvers.is_empty() {
+        println!();
+        println!(
+            "⏳ {}",
+            approvals_summary(multisig, &proposal).bold()
+        );
+        println!("  Not yet approved by:");
+        for member in &pending_approvers {
+            let note = if proposal.rejected.contains(member) {
+                " (rejected)"
+            } else {
+                ""
+            };
+            println!("    • {}{}", member, note);
+        }
+    }
 
     // Display voting details if there are votes
     if !proposal.approved.is_empty() || !proposal.rejected.is_empty() || !proposal.cancelled.is_empty() {
@@ -726,9 +920,7 @@ async fn fetch_and_display_proposal(
             });
         }
 
-        let mut votes_table = Table::new(votes);
-        votes_table.with(Style::rounded());
-        println!("{}", votes_table);
+        Output::table(&votes);
     }
 
     println!("  ✅ Proposal {} details retrieved", tx_index);
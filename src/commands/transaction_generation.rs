@@ -1,22 +1,35 @@
+use borsh::BorshDeserialize;
 use colored::Colorize;
 use eyre::Result;
 use serde::Serialize;
 use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_hash::Hash;
+use solana_keypair::Keypair;
+use solana_message::v0::Message;
 use solana_message::VersionedMessage;
 use solana_pubkey::Pubkey;
 use solana_signature::Signature;
 use solana_signer::Signer;
 use solana_transaction::versioned::VersionedTransaction;
+use std::fs;
+use std::path::Path;
 use std::str::FromStr;
 
 use crate::{
+    feature_gate_program::{ensure_revocable, FeatureState},
     output,
     provision::{
-        create_approve_activation_revocation_transaction_message, create_approve_activation_transaction_message, create_execute_activation_transaction_message
+        create_approve_activation_revocation_transaction_message, create_approve_activation_transaction_message, create_execute_activation_transaction_message,
+        create_transaction_and_proposal_message, TransactionBuildContext,
+        ACTIVATION_TRANSACTION_INDEX,
+    },
+    squads::{
+        get_proposal_pda, get_vault_pda, Multisig, Permission, Proposal,
+        SQUADS_MULTISIG_PROGRAM_ID,
     },
     utils::{
-        choose_network_from_config, choose_transaction_encoding, load_fee_payer_keypair, Config,
-        TransactionEncoding,
+        choose_network_from_config, choose_transaction_encoding, load_fee_payer_keypair,
+        transaction_message_from_versioned, Config, TransactionEncoding,
     },
 };
 
@@ -26,14 +39,39 @@ pub async fn approve_feature_gate_activation_proposal(
     voting_key: Pubkey,
     fee_payer_path: String,
     program_id: Option<Pubkey>,
+    output_dir: Option<&Path>,
 ) -> Result<()> {
-    let program_id = program_id
-        .unwrap_or_else(|| Pubkey::from_str_const("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf"));
+    let program_id = program_id.unwrap_or(SQUADS_MULTISIG_PROGRAM_ID);
 
     let fee_payer_keypair = load_fee_payer_keypair(config, Some(fee_payer_path))?;
     let rpc_url = choose_network_from_config(config)?;
-    let rpc_client = RpcClient::new(rpc_url);
+    let rpc_client = RpcClient::new(rpc_url.clone());
+
+    if let Some(parent) = fetch_parent_multisig(&rpc_client, &voting_key, &program_id).await? {
+        let parent_vault = get_vault_pda(&voting_key, 0, Some(&program_id)).0;
+        let vote_message = create_approve_activation_transaction_message(
+            &program_id,
+            &feature_gate_multisig_address,
+            &parent_vault,
+            &parent_vault,
+            Hash::default(),
+        )?;
+        return emit_parent_multisig_vote(
+            &rpc_client,
+            &rpc_url,
+            &program_id,
+            feature_gate_multisig_address,
+            (voting_key, &parent),
+            &fee_payer_keypair.unwrap(),
+            vote_message,
+            "parent_approve_activation",
+            output_dir,
+        )
+        .await;
+    }
+
     let blockhash = rpc_client.get_latest_blockhash().await?;
+    let blockhash_fetched_at = chrono::Utc::now();
 
     let transaction_message = create_approve_activation_transaction_message(
         &program_id,
@@ -56,15 +94,19 @@ pub async fn approve_feature_gate_activation_proposal(
     transaction.signatures.push(Signature::default());
     let serialized_transaction = bincode::serialize(&transaction)?;
 
-    let transaction_encoded_bs58 = bs58::encode(&serialized_transaction).into_string();
-    let transaction_encoded_base64 = base64::encode(&serialized_transaction);
-
-    output::Output::header("Encoded Transactions:");
-    output::Output::separator();
-    output::Output::field("Base58:", &transaction_encoded_bs58);
-    output::Output::separator();
-    output::Output::field("Base64:", &transaction_encoded_base64);
-    Ok(())
+    emit_encoded_transaction(
+        &serialized_transaction,
+        &TransactionMetadata {
+            multisig: feature_gate_multisig_address,
+            action: "approve_activation",
+            voting_key,
+            program_id,
+            rpc_url,
+            blockhash: blockhash.to_string(),
+            blockhash_fetched_at,
+        },
+        output_dir,
+    )
 }
 
 pub async fn approve_feature_gate_activation_revocation_proposal(
@@ -73,14 +115,49 @@ pub async fn approve_feature_gate_activation_revocation_proposal(
     voting_key: Pubkey,
     fee_payer_path: String,
     program_id: Option<Pubkey>,
+    output_dir: Option<&Path>,
 ) -> Result<()> {
-    let program_id = program_id
-        .unwrap_or_else(|| Pubkey::from_str_const("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf"));
+    let program_id = program_id.unwrap_or(SQUADS_MULTISIG_PROGRAM_ID);
 
     let fee_payer_keypair = load_fee_payer_keypair(config, Some(fee_payer_path))?;
     let rpc_url = choose_network_from_config(config)?;
-    let rpc_client = RpcClient::new(rpc_url);
+    let rpc_client = RpcClient::new(rpc_url.clone());
+
+    let feature_id = get_vault_pda(&feature_gate_multisig_address, 0, Some(&program_id)).0;
+    let feature_account = rpc_client
+        .get_account_with_commitment(&feature_id, rpc_client.commitment())
+        .await?
+        .value;
+    let feature_state = FeatureState::from_account(
+        feature_account.as_ref().map(|a| (&a.owner, a.data.as_slice())),
+    )?;
+    ensure_revocable(&feature_id, feature_state)?;
+
+    if let Some(parent) = fetch_parent_multisig(&rpc_client, &voting_key, &program_id).await? {
+        let parent_vault = get_vault_pda(&voting_key, 0, Some(&program_id)).0;
+        let vote_message = create_approve_activation_revocation_transaction_message(
+            &program_id,
+            &feature_gate_multisig_address,
+            &parent_vault,
+            &parent_vault,
+            Hash::default(),
+        )?;
+        return emit_parent_multisig_vote(
+            &rpc_client,
+            &rpc_url,
+            &program_id,
+            feature_gate_multisig_address,
+            (voting_key, &parent),
+            &fee_payer_keypair.unwrap(),
+            vote_message,
+            "parent_approve_revocation",
+            output_dir,
+        )
+        .await;
+    }
+
     let blockhash = rpc_client.get_latest_blockhash().await?;
+    let blockhash_fetched_at = chrono::Utc::now();
 
     let transaction_message = create_approve_activation_revocation_transaction_message(
         &program_id,
@@ -103,30 +180,42 @@ pub async fn approve_feature_gate_activation_revocation_proposal(
     transaction.signatures.push(Signature::default());
     let serialized_transaction = bincode::serialize(&transaction)?;
 
-    let transaction_encoded_bs58 = bs58::encode(&serialized_transaction).into_string();
-    let transaction_encoded_base64 = base64::encode(&serialized_transaction);
-
-    output::Output::header("Encoded Transactions:");
-    output::Output::separator();
-    output::Output::field("Base58:", &transaction_encoded_bs58);
-    output::Output::separator();
-    output::Output::field("Base64:", &transaction_encoded_base64);
-    Ok(())
+    emit_encoded_transaction(
+        &serialized_transaction,
+        &TransactionMetadata {
+            multisig: feature_gate_multisig_address,
+            action: "approve_revocation",
+            voting_key,
+            program_id,
+            rpc_url,
+            blockhash: blockhash.to_string(),
+            blockhash_fetched_at,
+        },
+        output_dir,
+    )
 }
+/// Builds the execute transaction for the activation proposal. Unless `force`,
+/// refuses while the proposal isn't approved or the multisig's time lock
+/// hasn't elapsed since approval, as the execution would fail on chain.
 pub async fn execute_feature_gate_activation_proposal(
     config: &Config,
     feature_gate_multisig_address: Pubkey,
     voting_key: Pubkey,
     fee_payer_path: String,
     program_id: Option<Pubkey>,
+    output_dir: Option<&Path>,
+    force: bool,
 ) -> Result<()> {
-    let program_id = program_id
-        .unwrap_or_else(|| Pubkey::from_str_const("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf"));
+    let program_id = program_id.unwrap_or(SQUADS_MULTISIG_PROGRAM_ID);
 
     let fee_payer_keypair = load_fee_payer_keypair(config, Some(fee_payer_path))?;
     let rpc_url = choose_network_from_config(config)?;
-    let rpc_client = RpcClient::new(rpc_url);
+    let rpc_client = RpcClient::new(rpc_url.clone());
+
+    ensure_time_lock_released(&rpc_client, &feature_gate_multisig_address, &program_id, force)
+        .await?;
     let blockhash = rpc_client.get_latest_blockhash().await?;
+    let blockhash_fetched_at = chrono::Utc::now();
 
     let transaction_message = create_execute_activation_transaction_message(
         &program_id,
@@ -144,13 +233,228 @@ pub async fn execute_feature_gate_activation_proposal(
     )?;
     let serialized_transaction = bincode::serialize(&transaction)?;
 
-    output::Output::header("Encoded Transactions:");
+    emit_encoded_transaction(
+        &serialized_transaction,
+        &TransactionMetadata {
+            multisig: feature_gate_multisig_address,
+            action: "execute_activation",
+            voting_key,
+            program_id,
+            rpc_url,
+            blockhash: blockhash.to_string(),
+            blockhash_fetched_at,
+        },
+        output_dir,
+    )
+}
 
-    let transaction_encoded_bs58 = bs58::encode(&serialized_transaction).into_string();
-    let transaction_encoded_base64 = base64::encode(&serialized_transaction);
+/// Checks the proposal the execute message targets is approved and past the
+/// multisig's time lock, by the cluster's clock when it can be read. With
+/// `force`, a failed check (including one that couldn't be made) only warns.
+async fn ensure_time_lock_released(
+    rpc_client: &RpcClient,
+    multisig_address: &Pubkey,
+    program_id: &Pubkey,
+    force: bool,
+) -> Result<()> {
+    // Same proposal as create_execute_activation_transaction_message
+    let proposal_pda =
+        get_proposal_pda(multisig_address, ACTIVATION_TRANSACTION_INDEX, Some(program_id)).0;
+    let problem = match time_lock_problem(rpc_client, multisig_address, &proposal_pda).await {
+        Ok(None) => return Ok(()),
+        Ok(Some(problem)) => problem,
+        Err(e) => format!("Couldn't check proposal {}: {}", proposal_pda, e),
+    };
+    if force {
+        output::Output::warning(&format!("{}; building the transaction anyway", problem));
+        Ok(())
+    } else {
+        Err(eyre::eyre!("{} (pass --force to build it anyway)", problem))
+    }
+}
+
+/// Why `proposal_pda` can't be executed yet, or `None` when it can
+async fn time_lock_problem(
+    rpc_client: &RpcClient,
+    multisig_address: &Pubkey,
+    proposal_pda: &Pubkey,
+) -> Result<Option<String>> {
+    let multisig_data = rpc_client.get_account_data(multisig_address).await?;
+    let multisig = Multisig::deserialize(&mut &multisig_data[8..])
+        .map_err(|e| eyre::eyre!("Failed to deserialize multisig: {}", e))?;
+    let proposal_data = rpc_client.get_account_data(proposal_pda).await?;
+    let proposal = Proposal::deserialize(&mut &proposal_data[8..])
+        .map_err(|e| eyre::eyre!("Failed to deserialize proposal: {}", e))?;
+
+    let now = match rpc_client.get_slot().await {
+        Ok(slot) => rpc_client.get_block_time(slot).await.ok(),
+        Err(_) => None,
+    }
+    .unwrap_or_else(|| chrono::Utc::now().timestamp());
+
+    Ok(match proposal.status.time_lock_remaining(multisig.time_lock, now) {
+        Some(0) => None,
+        Some(remaining) => Some(format!("Time lock active: executable in {} seconds", remaining)),
+        None => Some(format!(
+            "Proposal {} is {}, not approved; it can't be executed yet",
+            proposal_pda,
+            proposal.status.name()
+        )),
+    })
+}
+
+/// The multisig at `voting_key`, when the voter is a Squads multisig rather
+/// than a wallet
+async fn fetch_parent_multisig(
+    rpc_client: &RpcClient,
+    voting_key: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Option<Multisig>> {
+    let Some(account) = rpc_client
+        .get_account_with_commitment(voting_key, rpc_client.commitment())
+        .await?
+        .value
+    else {
+        return Ok(None);
+    };
+    if !Multisig::is_multisig_account(&account.owner, &account.data, program_id) {
+        return Ok(None);
+    }
+    Multisig::deserialize(&mut &account.data[8..])
+        .map(Some)
+        .map_err(|e| eyre::eyre!("Failed to deserialize parent multisig {}: {}", voting_key, e))
+}
 
+/// Wraps `vote_message` (built with the parent's vault 0 as the voter) in a
+/// vault transaction proposal on the parent multisig, created by the fee
+/// payer. The vote lands on the feature gate multisig once the parent's
+/// members approve and execute that proposal.
+#[allow(clippy::too_many_arguments)]
+async fn emit_parent_multisig_vote(
+    rpc_client: &RpcClient,
+    rpc_url: &str,
+    program_id: &Pubkey,
+    feature_gate_multisig_address: Pubkey,
+    (parent_address, parent): (Pubkey, &Multisig),
+    fee_payer_keypair: &Keypair,
+    vote_message: Message,
+    action: &'static str,
+    output_dir: Option<&Path>,
+) -> Result<()> {
+    let parent_vault = get_vault_pda(&parent_address, 0, Some(program_id)).0;
+    let creator = fee_payer_keypair.pubkey();
+    if !parent.has_permission(&creator, Permission::Initiate) {
+        return Err(eyre::eyre!(
+            "{} is a multisig; the fee payer {} must be one of its members with Initiate \
+             permission to propose the vote",
+            parent_address,
+            creator
+        ));
+    }
+
+    let feature_gate_data = rpc_client.get_account_data(&feature_gate_multisig_address).await?;
+    let feature_gate_multisig = Multisig::deserialize(&mut &feature_gate_data[8..])
+        .map_err(|e| eyre::eyre!("Failed to deserialize multisig: {}", e))?;
+    let can_vote = feature_gate_multisig
+        .members
+        .iter()
+        .any(|m| m.key == parent_vault && m.permissions.has(Permission::Vote));
+    if !can_vote {
+        output::Output::warning(&format!(
+            "Parent vault {} is not a voting member of {}; the vote will fail when executed",
+            parent_vault, feature_gate_multisig_address
+        ));
+    }
+
+    let transaction_index = parent.transaction_index + 1;
+    let blockhash = rpc_client.get_latest_blockhash().await?;
+    let blockhash_fetched_at = chrono::Utc::now();
+
+    let context =
+        TransactionBuildContext::new(parent_address, transaction_index, creator, blockhash)
+            .with_program_id(Some(program_id));
+    let (message, _, proposal_pda) = create_transaction_and_proposal_message(
+        &context,
+        transaction_message_from_versioned(&VersionedMessage::V0(vote_message))?,
+    )?;
+    let transaction =
+        VersionedTransaction::try_new(VersionedMessage::V0(message), &[fee_payer_keypair])?;
+    let serialized_transaction = bincode::serialize(&transaction)?;
+
+    output::Output::info(&format!(
+        "{} is a multisig: the vote is proposed as its transaction {} ({}), which its members \
+         must approve and execute",
+        parent_address, transaction_index, proposal_pda
+    ));
+    emit_encoded_transaction(
+        &serialized_transaction,
+        &TransactionMetadata {
+            multisig: feature_gate_multisig_address,
+            action,
+            voting_key: parent_address,
+            program_id: *program_id,
+            rpc_url: rpc_url.to_string(),
+            blockhash: blockhash.to_string(),
+            blockhash_fetched_at,
+        },
+        output_dir,
+    )
+}
+
+/// Context recorded alongside an encoded transaction written to `--output-dir`
+struct TransactionMetadata {
+    multisig: Pubkey,
+    action: &'static str,
+    voting_key: Pubkey,
+    program_id: Pubkey,
+    rpc_url: String,
+    blockhash: String,
+    /// When `blockhash` was fetched; it expires roughly a minute later
+    blockhash_fetched_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Prints the encoded transaction and, when `output_dir` is set, also writes it
+/// with its metadata to `<multisig>_<action>.txt` for offline signing
+fn emit_encoded_transaction(
+    serialized_transaction: &[u8],
+    metadata: &TransactionMetadata,
+    output_dir: Option<&Path>,
+) -> Result<()> {
+    let transaction_encoded_bs58 = bs58::encode(serialized_transaction).into_string();
+    let transaction_encoded_base64 = base64::encode(serialized_transaction);
+
+    output::Output::header("Encoded Transactions:");
+    output::Output::separator();
     output::Output::field("Base58:", &transaction_encoded_bs58);
+    output::Output::separator();
     output::Output::field("Base64:", &transaction_encoded_base64);
+    output::Output::separator();
+    output::Output::field("Blockhash:", &metadata.blockhash);
+    output::Output::field("Fetched At:", &metadata.blockhash_fetched_at.to_rfc3339());
+    output::Output::hint(&format!(
+        "Check it is still valid before signing: feature-gate-multisig-tool is-blockhash-valid {} --url {}",
+        metadata.blockhash, metadata.rpc_url
+    ));
+
+    if let Some(output_dir) = output_dir {
+        fs::create_dir_all(output_dir)?;
+        let path = output_dir.join(format!("{}_{}.txt", metadata.multisig, metadata.action));
+        let contents = format!(
+            "Multisig: {}\nAction: {}\nVoting key: {}\nProgram ID: {}\nRPC URL: {}\nBlockhash: {}\nBlockhash fetched at: {}\n\nBase58:\n{}\n\nBase64:\n{}\n",
+            metadata.multisig,
+            metadata.action,
+            metadata.voting_key,
+            metadata.program_id,
+            metadata.rpc_url,
+            metadata.blockhash,
+            metadata.blockhash_fetched_at.to_rfc3339(),
+            transaction_encoded_bs58,
+            transaction_encoded_base64,
+        );
+        fs::write(&path, contents)
+            .map_err(|e| eyre::eyre!("Failed to write {}: {}", path.display(), e))?;
+        output::Output::success(&format!("Saved to {}", path.display()));
+    }
 
     Ok(())
 }
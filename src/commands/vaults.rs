@@ -0,0 +1,71 @@
+use crate::constants::{LAMPORTS_PER_SOL, MAX_ACCOUNTS_PER_REQUEST};
+use crate::output::Output;
+use crate::provision::{create_rpc_client, fetch_accounts_batched};
+use crate::squads::get_vault_pda;
+use crate::utils::*;
+use eyre::{Result, WrapErr};
+use solana_pubkey::Pubkey;
+use tabled::Tabled;
+
+#[derive(Tabled)]
+struct VaultRow {
+    #[tabled(rename = "Index")]
+    index: u8,
+    #[tabled(rename = "Vault")]
+    address: String,
+    #[tabled(rename = "Balance (SOL)")]
+    balance: String,
+}
+
+/// Lists the SOL balance of `count` vaults of a multisig starting at index
+/// `first`, fetched together in as few requests as possible. Only funded
+/// vaults are listed unless `all`.
+pub async fn vaults_command(
+    config: &Config,
+    address: String,
+    rpc_url: Option<String>,
+    first: u8,
+    count: u16,
+    all: bool,
+) -> Result<()> {
+    let multisig_address = config.resolve_multisig(&address)?;
+    let rpc_url = match rpc_url {
+        Some(url) => validate_rpc_url(&url)?,
+        None => choose_network_from_config(config)?,
+    };
+    let rpc_client = create_rpc_client(&rpc_url);
+
+    // Vault indices are a u8, so the range stops at 255
+    let indices: Vec<u8> = (first..=u8::MAX).take(usize::from(count)).collect();
+    let vaults: Vec<Pubkey> = indices
+        .iter()
+        .map(|index| get_vault_pda(&multisig_address, *index, None).0)
+        .collect();
+    let accounts = fetch_accounts_batched(&rpc_client, &vaults, MAX_ACCOUNTS_PER_REQUEST)
+        .wrap_err("Failed to fetch vaults")?;
+
+    let mut total = 0u64;
+    let mut rows = Vec::new();
+    for ((index, vault), account) in indices.iter().zip(&vaults).zip(&accounts) {
+        let lamports = account.as_ref().map_or(0, |account| account.lamports);
+        total += lamports;
+        if lamports > 0 || all {
+            rows.push(VaultRow {
+                index: *index,
+                address: vault.to_string(),
+                balance: format!("{:.9}", lamports as f64 / LAMPORTS_PER_SOL as f64),
+            });
+        }
+    }
+
+    let last = indices.last().copied().unwrap_or(first);
+    Output::header(&format!("🏦 Vaults {}..={} of {}", first, last, multisig_address));
+    if rows.is_empty() {
+        Output::info("No funded vaults in this range (pass --all to list empty ones)");
+        return Ok(());
+    }
+    Output::table(&rows);
+    Output::field("Total", &format!("{:.9} SOL", total as f64 / LAMPORTS_PER_SOL as f64));
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use crate::output::Output;
+use crate::provision::{create_rpc_client, fetch_multisig, get_account_data_with_retry};
+use crate::squads::{get_proposal_pda, Proposal, ProposalStatus};
+use crate::utils::*;
+use colored::*;
+use eyre::Result;
+use std::time::{Duration, Instant};
+
+pub async fn watch_command(
+    config: &Config,
+    address: String,
+    transaction_index: u64,
+    rpc_url: Option<String>,
+    timeout: Duration,
+    interval: Duration,
+) -> Result<()> {
+    let multisig = config.resolve_multisig(&address)?;
+    let rpc_url = match rpc_url {
+        Some(url) => validate_rpc_url(&url)?,
+        None => choose_network_from_config(config)?,
+    };
+    let rpc_client = create_rpc_client(&rpc_url);
+    let proposal_pda = get_proposal_pda(&multisig, transaction_index, None).0;
+
+    println!(
+        "{}",
+        format!("👀 Watching proposal {} of {}", transaction_index, multisig)
+            .bright_yellow()
+            .bold()
+    );
+    println!("🎯 Proposal PDA: {}", proposal_pda.to_string().bright_white());
+    println!("📡 Network: {}", rpc_url.bright_white());
+    println!();
+
+    let progress = Output::spinner("");
+
+    let start = Instant::now();
+    let mut last_status: Option<&'static str> = None;
+
+    loop {
+        match get_account_data_with_retry(&rpc_client, &proposal_pda) {
+            Ok(data) if data.len() >= 8 => {
+                let proposal: Proposal = borsh::BorshDeserialize::deserialize(&mut &data[8..])
+                    .map_err(|e| eyre::eyre!("Failed to deserialize proposal: {}", e))?;
+                let status = proposal.status.name();
+
+                if last_status != Some(status) {
+                    let transition = match last_status {
+                        Some(previous) => format!("{} → {}", previous, status),
+                        None => status.to_string(),
+                    };
+                    progress.println(format!(
+                        "[{}] {}",
+                        chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+                        transition.bright_white()
+                    ));
+                    last_status = Some(status);
+                }
+
+                let status_detail = match proposal.status {
+                    ProposalStatus::Draft { .. } => "Draft (awaiting activate-proposal)",
+                    _ => status,
+                };
+                progress.set_message(format!(
+                    "{} · {} approved · {} rejected · {}s elapsed",
+                    status_detail,
+                    proposal.approved.len(),
+                    proposal.rejected.len(),
+                    start.elapsed().as_secs()
+                ));
+
+                if proposal.status.is_terminal() {
+                    progress.finish_and_clear();
+                    println!("✅ Proposal reached terminal state: {}", status.bright_green());
+                    return Ok(());
+                }
+
+                // A config change since creation means an unapproved proposal
+                // will never progress
+                if let Ok(multisig_account) = fetch_multisig(&rpc_client, &multisig) {
+                    if multisig_account.is_stale(transaction_index)
+                        && proposal.status.is_stopped_when_stale()
+                    {
+                        progress.finish_and_clear();
+                        return Err(eyre::eyre!(
+                            "Proposal {} is stale (stale transaction index {}) and can no \
+                             longer be voted on",
+                            transaction_index,
+                            multisig_account.stale_transaction_index
+                        ));
+                    }
+                }
+            }
+            Ok(_) => return Err(eyre::eyre!("Proposal account data too small")),
+            Err(e)
+                if e.to_string().contains("AccountNotFound")
+                    || e.to_string().contains("could not find account") =>
+            {
+                progress.set_message(format!(
+                    "Waiting for proposal account to be created · {}s elapsed",
+                    start.elapsed().as_secs()
+                ));
+            }
+            Err(e) => {
+                // Transient RPC failures shouldn't end the watch
+                progress.set_message(format!("RPC error, retrying: {}", e));
+            }
+        }
+
+        if start.elapsed() >= timeout {
+            progress.finish_and_clear();
+            return Err(eyre::eyre!(
+                "Timed out after {}s waiting for proposal {} (last status: {})",
+                timeout.as_secs(),
+                transaction_index,
+                last_status.unwrap_or("not found")
+            ));
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
@@ -0,0 +1,101 @@
+use crate::constants::LAMPORTS_PER_SOL;
+use crate::error::ToolError;
+use crate::network::NetworkCluster;
+use crate::output::Output;
+use crate::provision::{create_rpc_client, fetch_multisig};
+use crate::utils::*;
+use eyre::Result;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use tabled::Tabled;
+
+/// Shows which keys the tool can sign with: the configured fee payer plus any
+/// `--keypair` files, their balances on each network and, with `--multisig`,
+/// the permissions each holds there
+pub async fn whoami_command(
+    config: &Config,
+    keypair_paths: Vec<String>,
+    multisig: Option<String>,
+    rpc_url: Option<String>,
+) -> Result<()> {
+    let networks = match rpc_url {
+        Some(url) => vec![validate_rpc_url(&url)?],
+        None if config.networks.is_empty() => {
+            return Err(ToolError::Config(
+                "No networks configured; pass --url <RPC_URL>".to_string(),
+            )
+            .into())
+        }
+        None => config.networks.clone(),
+    };
+    let multisig_address = multisig.map(|m| config.resolve_multisig(&m)).transpose()?;
+
+    let mut keys: Vec<(String, Pubkey)> = Vec::new();
+    if let Some(fee_payer) = load_fee_payer_keypair(config, None)? {
+        keys.push(("Fee payer (config)".to_string(), fee_payer.pubkey()));
+    }
+    for path in keypair_paths {
+        if let Some(keypair) = load_fee_payer_keypair(config, Some(path.clone()))? {
+            keys.push((path, keypair.pubkey()));
+        }
+    }
+    if keys.is_empty() {
+        return Err(ToolError::Config(
+            "No keypairs to check; set a fee payer in the config or pass --keypair".to_string(),
+        )
+        .into());
+    }
+
+    #[derive(Tabled)]
+    struct KeyRow {
+        #[tabled(rename = "Source")]
+        source: String,
+        #[tabled(rename = "Public Key")]
+        pubkey: String,
+        #[tabled(rename = "Network")]
+        network: String,
+        #[tabled(rename = "Balance (SOL)")]
+        balance: String,
+        #[tabled(rename = "Multisig Permissions")]
+        permissions: String,
+    }
+
+    let mut rows = Vec::new();
+    for network in &networks {
+        let rpc_client = create_rpc_client(network);
+        let multisig_account = multisig_address
+            .as_ref()
+            .map(|address| fetch_multisig(&rpc_client, address));
+
+        for (source, pubkey) in &keys {
+            let balance = match rpc_client.get_balance(pubkey) {
+                Ok(lamports) => format!("{:.4}", lamports as f64 / LAMPORTS_PER_SOL as f64),
+                Err(_) => "unavailable".to_string(),
+            };
+            let permissions = match &multisig_account {
+                None => "-".to_string(),
+                Some(Err(_)) => "multisig not found".to_string(),
+                Some(Ok(multisig)) => multisig
+                    .members
+                    .iter()
+                    .find(|member| member.key == *pubkey)
+                    .map(|member| decode_permissions(member.permissions.mask).join(", "))
+                    .unwrap_or_else(|| "not a member".to_string()),
+            };
+            rows.push(KeyRow {
+                source: source.clone(),
+                pubkey: pubkey.to_string(),
+                network: NetworkCluster::from_url(network).label().to_string(),
+                balance,
+                permissions,
+            });
+        }
+    }
+
+    Output::header("🔑 Signing Keys");
+    if let Some(address) = multisig_address {
+        Output::field("Multisig", &address.to_string());
+    }
+    Output::table(&rows);
+    Ok(())
+}
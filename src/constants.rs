@@ -2,24 +2,45 @@
 
 // Compute budget constants
 pub const DEFAULT_COMPUTE_UNITS: u32 = 300_000;
-pub const DEFAULT_PRIORITY_FEE: u64 = 5000;
+pub const DEFAULT_PRIORITY_FEE: u64 = 5000; // Custom / unrecognised networks
+pub const LOCALNET_PRIORITY_FEE: u64 = 0;
+pub const DEVNET_PRIORITY_FEE: u64 = 1_000; // Also used for testnet
+pub const MAINNET_PRIORITY_FEE: u64 = 100_000;
+pub const AUTO_PRIORITY_FEE_PERCENTILE: u64 = 75;
 pub const CREATE_MULTISIG_COMPUTE_UNITS: u32 = 50_000;
 
-// Program IDs
-pub const SQUADS_PROGRAM_ID_STR: &str = "SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf";
-
 // Transaction retry constants (optimized for ~10 second max retry window)
 pub const MAX_TX_RETRIES: usize = 5;
 pub const BASE_RETRY_DELAY_MS: u64 = 500;
 
+// Retry budget shared by every RPC call of one network deployment
+pub const DEPLOYMENT_MAX_RETRIES: usize = 12;
+pub const DEPLOYMENT_MAX_RETRY_SECS: u64 = 120;
+
 // Confirmation constants
 pub const CONFIRMATION_TIMEOUT_MS: u64 = 30_000; // 30 seconds
 pub const CONFIRMATION_POLL_INTERVAL_MS: u64 = 1000; // 1 second
+pub const SPAM_RESEND_INTERVAL_MS: u64 = 2000; // 2 seconds between re-sends in spam mode
+pub const MAX_BLOCKHASH_REFRESHES: usize = 3; // Rebuilds on a fresh blockhash after expiry
 
 // Account data retry constants  
 pub const MAX_ACCOUNT_RETRIES: usize = 3;
 pub const BASE_ACCOUNT_RETRY_DELAY_MS: u64 = 500;
 
+// Minimum back-off after an HTTP 429, on top of the RPC client's own
+// `Retry-After` waits
+pub const RATE_LIMIT_RETRY_DELAY_MS: u64 = 5_000;
+
+// `getMultipleAccounts` accepts at most 100 keys per request
+pub const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+// Lamports per SOL, for converting user-facing SOL amounts
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+// SPL token programs whose accounts are listed as vault holdings
+pub const SPL_TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const SPL_TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
 // Default network URLs
 pub const DEFAULT_DEVNET_URL: &str = "https://api.devnet.solana.com";
 pub const DEFAULT_MAINNET_URL: &str = "https://api.mainnet-beta.solana.com";
\ No newline at end of file
@@ -0,0 +1,124 @@
+use std::fmt;
+
+/// Generic failure exit code, used for anything not covered by a `ToolError`
+pub const EXIT_GENERIC: i32 = 1;
+pub const EXIT_CONFIG: i32 = 2;
+pub const EXIT_NETWORK: i32 = 3;
+pub const EXIT_INSUFFICIENT_FUNDS: i32 = 4;
+pub const EXIT_CANCELLED: i32 = 5;
+
+/// Failure categories that scripts can tell apart by exit code
+#[derive(Debug)]
+pub enum ToolError {
+    /// Missing, unreadable or invalid configuration; fix it before retrying
+    Config(String),
+    /// RPC or network failure; usually worth retrying later
+    Network(String),
+    /// The fee payer can't cover the operation
+    InsufficientFunds(String),
+    /// The user declined a prompt or interrupted the tool
+    Cancelled(String),
+    /// The cluster rejected a transaction in preflight simulation; `logs`
+    /// holds the program logs explaining why
+    SimulationFailed { message: String, logs: Vec<String> },
+}
+
+impl ToolError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ToolError::Config(_) => EXIT_CONFIG,
+            ToolError::Network(_) => EXIT_NETWORK,
+            ToolError::InsufficientFunds(_) => EXIT_INSUFFICIENT_FUNDS,
+            ToolError::Cancelled(_) => EXIT_CANCELLED,
+            ToolError::SimulationFailed { .. } => EXIT_GENERIC,
+        }
+    }
+}
+
+impl fmt::Display for ToolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ToolError::Config(msg)
+            | ToolError::Network(msg)
+            | ToolError::InsufficientFunds(msg)
+            | ToolError::Cancelled(msg)
+            | ToolError::SimulationFailed { message: msg, .. } => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ToolError {}
+
+/// Simulation logs carried anywhere in the error chain, for printing after
+/// the error itself
+pub fn simulation_logs(report: &eyre::Report) -> Option<&[String]> {
+    report.chain().find_map(|cause| match cause.downcast_ref::<ToolError>() {
+        Some(ToolError::SimulationFailed { logs, .. }) => Some(logs.as_slice()),
+        _ => None,
+    })
+}
+
+/// Exit code for an error returned from a command. Looks through the whole
+/// chain so context added with `wrap_err` doesn't hide the category; prompt
+/// cancellations (Esc / Ctrl-C) count as `ToolError::Cancelled`.
+pub fn exit_code(report: &eyre::Report) -> i32 {
+    for cause in report.chain() {
+        if let Some(error) = cause.downcast_ref::<ToolError>() {
+            return error.exit_code();
+        }
+        if let Some(
+            inquire::InquireError::OperationCanceled | inquire::InquireError::OperationInterrupted,
+        ) = cause.downcast_ref::<inquire::InquireError>()
+        {
+            return EXIT_CANCELLED;
+        }
+    }
+    EXIT_GENERIC
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code_for_each_category() {
+        let cases = [
+            (ToolError::Config("bad config".into()), EXIT_CONFIG),
+            (ToolError::Network("rpc down".into()), EXIT_NETWORK),
+            (ToolError::InsufficientFunds("need SOL".into()), EXIT_INSUFFICIENT_FUNDS),
+            (ToolError::Cancelled("aborted".into()), EXIT_CANCELLED),
+        ];
+        for (error, code) in cases {
+            assert_eq!(exit_code(&eyre::Report::new(error)), code);
+        }
+        assert_eq!(exit_code(&eyre::eyre!("something else")), EXIT_GENERIC);
+    }
+
+    #[test]
+    fn test_exit_code_looks_through_context() {
+        let report = eyre::Report::new(ToolError::Network("rpc down".into()))
+            .wrap_err("Failed to deploy to devnet");
+        assert_eq!(exit_code(&report), EXIT_NETWORK);
+
+        let report = eyre::Report::new(inquire::InquireError::OperationCanceled);
+        assert_eq!(exit_code(&report), EXIT_CANCELLED);
+    }
+
+    #[test]
+    fn test_simulation_logs_survive_context() {
+        let logs = vec!["Program log: insufficient funds".to_string()];
+        let report = eyre::Report::new(ToolError::SimulationFailed {
+            message: "Transaction simulation failed".into(),
+            logs: logs.clone(),
+        })
+        .wrap_err("Failed to create multisig");
+
+        assert_eq!(simulation_logs(&report), Some(logs.as_slice()));
+        assert_eq!(exit_code(&report), EXIT_GENERIC);
+        assert_eq!(
+            format!("{:#}", report),
+            "Failed to create multisig: Transaction simulation failed"
+        );
+        assert_eq!(simulation_logs(&eyre::eyre!("other")), None);
+    }
+}
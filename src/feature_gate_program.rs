@@ -1,4 +1,5 @@
 
+use crate::squads::VaultTransactionMessage;
 use solana_instruction::{AccountMeta, Instruction};
 use solana_pubkey::Pubkey;
 use solana_rent::Rent;
@@ -34,6 +35,91 @@ impl Feature {
     }
 }
 
+/// Where a feature account stands, as far as revoking it is concerned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureState {
+    /// No feature account yet; the activation hasn't been executed
+    NotCreated,
+    /// Owned by the Feature Gate program and waiting for the runtime to activate it
+    Pending,
+    /// Activated by the runtime at the given slot
+    Activated(u64),
+}
+
+impl FeatureState {
+    /// Reads the state from a feature account's owner and data, or `None` when
+    /// the account doesn't exist. A system-owned account (e.g. a funded vault
+    /// whose activation hasn't run) counts as not created.
+    pub fn from_account(account: Option<(&Pubkey, &[u8])>) -> eyre::Result<Self> {
+        let Some((owner, data)) = account else {
+            return Ok(FeatureState::NotCreated);
+        };
+        if *owner == solana_system_interface::program::ID {
+            return Ok(FeatureState::NotCreated);
+        }
+        if *owner != FEATURE_GATE_PROGRAM_ID {
+            return Err(eyre::eyre!(
+                "Feature account is owned by {}, not the Feature Gate program",
+                owner
+            ));
+        }
+        match data {
+            [0, ..] => Ok(FeatureState::Pending),
+            [1, slot @ ..] if slot.len() >= 8 => {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&slot[..8]);
+                Ok(FeatureState::Activated(u64::from_le_bytes(bytes)))
+            }
+            _ => Err(eyre::eyre!("Feature account data is malformed")),
+        }
+    }
+}
+
+/// Refuses to revoke a feature that isn't pending activation: the on-chain
+/// RevokePendingActivation instruction would fail for it
+pub fn ensure_revocable(feature_id: &Pubkey, state: FeatureState) -> eyre::Result<()> {
+    match state {
+        FeatureState::Pending => Ok(()),
+        FeatureState::NotCreated => Err(eyre::eyre!(
+            "Feature {} does not exist; its activation hasn't been executed, so there is nothing to revoke",
+            feature_id
+        )),
+        FeatureState::Activated(slot) => Err(eyre::eyre!(
+            "Feature {} was already activated at slot {}; only pending activations can be revoked",
+            feature_id,
+            slot
+        )),
+    }
+}
+
+/// Refuses to propose activating a feature that is already queued or active:
+/// the proposal could only fail on execution. `activation_epoch` is the epoch
+/// of an `Activated` slot, when known.
+pub fn ensure_activatable(
+    feature_id: &Pubkey,
+    state: FeatureState,
+    activation_epoch: Option<u64>,
+) -> eyre::Result<()> {
+    match (state, activation_epoch) {
+        (FeatureState::NotCreated, _) => Ok(()),
+        (FeatureState::Pending, _) => Err(eyre::eyre!(
+            "Feature {} is already pending activation; another activation proposal is pointless",
+            feature_id
+        )),
+        (FeatureState::Activated(slot), Some(epoch)) => Err(eyre::eyre!(
+            "Feature {} already active since epoch {} (slot {})",
+            feature_id,
+            epoch,
+            slot
+        )),
+        (FeatureState::Activated(slot), None) => Err(eyre::eyre!(
+            "Feature {} already active since slot {}",
+            feature_id,
+            slot
+        )),
+    }
+}
+
 /// Creates instructions to activate a feature gate
 ///
 /// This function creates the necessary instructions to queue a feature for activation:
@@ -116,19 +202,23 @@ pub fn activate_feature_funded(
 /// The instruction will:
 /// 1. Reallocate the feature account to zero size
 /// 2. Assign it back to the System Program
-/// 3. Transfer its lamports to the incinerator (burning them)
+/// 3. Transfer its lamports to `destination`
+///
+/// Solana's feature gate semantics expect `destination` to be the
+/// [`INCINERATOR_ID`], burning the lamports; the program may reject others.
 ///
 /// # Arguments
 /// * `feature_id` - The public key of the feature to revoke
+/// * `destination` - The account receiving the feature account's lamports
 ///
 /// # Returns
 /// An instruction to revoke the pending feature activation
-pub fn revoke_pending_activation(feature_id: &Pubkey) -> Instruction {
+pub fn revoke_pending_activation(feature_id: &Pubkey, destination: &Pubkey) -> Instruction {
     let accounts = vec![
         // Feature account (must be signer and writable)
         AccountMeta::new(*feature_id, true),
-        // Incinerator account (writable, burns the lamports)
-        AccountMeta::new(INCINERATOR_ID, false),
+        // Lamport destination, normally the incinerator (writable)
+        AccountMeta::new(*destination, false),
         // System program (for reallocating and reassigning the account)
         AccountMeta::new_readonly(solana_system_interface::program::ID, false),
     ];
@@ -160,6 +250,58 @@ pub fn create_feature_activation(
     activate_feature(feature_id, funding_address, &rent)
 }
 
+/// Summarises what a vault transaction does to a feature gate, so reviewers
+/// can tell an activation from a revocation before voting
+pub fn describe_feature_gate_intent(message: &VaultTransactionMessage) -> String {
+    let key_at = |index: u8| message.account_keys.get(index as usize);
+    let feature_account = |account_indexes: &[u8]| {
+        account_indexes
+            .first()
+            .and_then(|index| key_at(*index))
+            .map(|key| key.to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+
+    for instruction in &message.instructions {
+        let program_id = key_at(instruction.program_id_index);
+
+        // RevokePendingActivation
+        if program_id == Some(&FEATURE_GATE_PROGRAM_ID) && instruction.data == [0] {
+            return format!(
+                "Revoke pending activation of feature {}",
+                feature_account(&instruction.account_indexes)
+            );
+        }
+
+        // SystemInstruction::Assign (tag 1) handing the account to the Feature Gate program
+        if program_id == Some(&solana_system_interface::program::ID)
+            && instruction.data.len() == 36
+            && instruction.data[..4] == 1u32.to_le_bytes()
+            && instruction.data[4..] == FEATURE_GATE_PROGRAM_ID.to_bytes()
+        {
+            return format!(
+                "Activate feature {}",
+                feature_account(&instruction.account_indexes)
+            );
+        }
+    }
+
+    let programs: Vec<String> = message
+        .instructions
+        .iter()
+        .map(|instruction| {
+            key_at(instruction.program_id_index)
+                .map(|key| key.to_string())
+                .unwrap_or_else(|| "unknown".to_string())
+        })
+        .collect();
+    format!(
+        "Unrecognised: {} instruction(s) calling {}",
+        message.instructions.len(),
+        programs.join(", ")
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,6 +318,38 @@ mod tests {
         assert_eq!(feature.activated_at, None);
     }
 
+    #[test]
+    fn test_feature_state_and_revocability() {
+        let feature_id = Pubkey::new_unique();
+        let mut activated = vec![1u8];
+        activated.extend_from_slice(&42u64.to_le_bytes());
+
+        let state = |owner: &Pubkey, data: &[u8]| FeatureState::from_account(Some((owner, data)));
+        assert_eq!(FeatureState::from_account(None).unwrap(), FeatureState::NotCreated);
+        assert_eq!(state(&system_program::ID, &[]).unwrap(), FeatureState::NotCreated);
+        assert_eq!(
+            state(&FEATURE_GATE_PROGRAM_ID, &[0; 9]).unwrap(),
+            FeatureState::Pending
+        );
+        assert_eq!(
+            state(&FEATURE_GATE_PROGRAM_ID, &activated).unwrap(),
+            FeatureState::Activated(42)
+        );
+        assert!(state(&FEATURE_GATE_PROGRAM_ID, &[1, 0]).is_err());
+        assert!(state(&Pubkey::new_unique(), &[0; 9]).is_err());
+
+        assert!(ensure_revocable(&feature_id, FeatureState::Pending).is_ok());
+        assert!(ensure_revocable(&feature_id, FeatureState::NotCreated).is_err());
+        let err = ensure_revocable(&feature_id, FeatureState::Activated(42)).unwrap_err();
+        assert!(err.to_string().contains("slot 42"));
+
+        assert!(ensure_activatable(&feature_id, FeatureState::NotCreated, None).is_ok());
+        assert!(ensure_activatable(&feature_id, FeatureState::Pending, None).is_err());
+        let err =
+            ensure_activatable(&feature_id, FeatureState::Activated(42), Some(3)).unwrap_err();
+        assert!(err.to_string().contains("already active since epoch 3 (slot 42)"));
+    }
+
     #[test]
     fn test_activate_feature_instructions() {
         let feature_id = Pubkey::new_unique();
@@ -208,7 +382,7 @@ mod tests {
     fn test_revoke_pending_activation() {
         let feature_id = Pubkey::new_unique();
 
-        let instruction = revoke_pending_activation(&feature_id);
+        let instruction = revoke_pending_activation(&feature_id, &INCINERATOR_ID);
 
         assert_eq!(instruction.program_id, FEATURE_GATE_PROGRAM_ID);
         assert_eq!(instruction.accounts.len(), 3);
@@ -229,4 +403,54 @@ mod tests {
         assert!(!instruction.accounts[2].is_signer);
         assert!(!instruction.accounts[2].is_writable);
     }
+
+    fn compile_vault_message(instructions: &[Instruction]) -> VaultTransactionMessage {
+        use crate::squads::MultisigCompiledInstruction;
+
+        let mut account_keys: Vec<Pubkey> = Vec::new();
+        let mut index_of = |key: &Pubkey| {
+            let index = account_keys.iter().position(|k| k == key).unwrap_or_else(|| {
+                account_keys.push(*key);
+                account_keys.len() - 1
+            });
+            index as u8
+        };
+        let compiled = instructions
+            .iter()
+            .map(|instruction| MultisigCompiledInstruction {
+                program_id_index: index_of(&instruction.program_id),
+                account_indexes: instruction.accounts.iter().map(|m| index_of(&m.pubkey)).collect(),
+                data: instruction.data.clone(),
+            })
+            .collect();
+
+        VaultTransactionMessage {
+            num_signers: 1,
+            num_writable_signers: 1,
+            num_writable_non_signers: 0,
+            account_keys,
+            instructions: compiled,
+            address_table_lookups: vec![],
+        }
+    }
+
+    #[test]
+    fn test_describe_feature_gate_intent() {
+        let feature_id = Pubkey::new_unique();
+
+        let activation = compile_vault_message(&activate_feature_funded(&feature_id));
+        assert_eq!(
+            describe_feature_gate_intent(&activation),
+            format!("Activate feature {}", feature_id)
+        );
+
+        let revocation = compile_vault_message(&[revoke_pending_activation(&feature_id, &INCINERATOR_ID)]);
+        assert_eq!(
+            describe_feature_gate_intent(&revocation),
+            format!("Revoke pending activation of feature {}", feature_id)
+        );
+
+        let other = compile_vault_message(&[transfer(&feature_id, &Pubkey::new_unique(), 1)]);
+        assert!(describe_feature_gate_intent(&other).starts_with("Unrecognised: 1 instruction(s)"));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,225 @@
+use crate::network::NetworkCluster;
+use crate::plan::PlannedMember;
+use crate::squads::Member;
+use crate::utils::{get_config_path, DeploymentResult};
+use chrono::NaiveDate;
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// One `create` run, appended as a line of `history.jsonl` so deployments can
+/// be looked up after their summary is gone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// When the deployments finished, RFC 3339 in UTC
+    pub timestamp: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    pub threshold: u16,
+    pub members: Vec<PlannedMember>,
+    pub deployments: Vec<HistoryDeployment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryDeployment {
+    pub rpc_url: String,
+    pub multisig: String,
+    /// Default vault (index 0), i.e. the feature gate ID
+    pub vault: String,
+    pub signature: String,
+    pub fee_payer: String,
+}
+
+/// Which entries `history` prints
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    /// First day included (UTC)
+    pub since: Option<NaiveDate>,
+    /// Last day included (UTC)
+    pub until: Option<NaiveDate>,
+    /// Cluster name (e.g. `devnet`) or part of an RPC URL
+    pub network: Option<String>,
+}
+
+impl HistoryEntry {
+    pub fn new(
+        deployments: &[DeploymentResult],
+        members: &[Member],
+        threshold: u16,
+        label: Option<&str>,
+    ) -> Self {
+        Self {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            label: label.map(str::to_string),
+            threshold,
+            members: members
+                .iter()
+                .map(|member| PlannedMember {
+                    key: member.key.to_string(),
+                    permissions: member.permissions.mask,
+                })
+                .collect(),
+            deployments: deployments
+                .iter()
+                .map(|deployment| HistoryDeployment {
+                    rpc_url: deployment.rpc_url.clone(),
+                    multisig: deployment.multisig_address.to_string(),
+                    vault: deployment.vault_address.to_string(),
+                    signature: deployment.transaction_signature.clone(),
+                    fee_payer: deployment.fee_payer.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    fn date(&self) -> Option<NaiveDate> {
+        chrono::DateTime::parse_from_rfc3339(&self.timestamp)
+            .ok()
+            .map(|timestamp| timestamp.naive_utc().date())
+    }
+}
+
+impl HistoryFilter {
+    /// The entry narrowed to its deployments on the filtered network, or
+    /// `None` when it is out of the date range or has none left
+    pub fn apply(&self, entry: &HistoryEntry) -> Option<HistoryEntry> {
+        if self.since.is_some() || self.until.is_some() {
+            let date = entry.date()?;
+            if self.since.is_some_and(|since| date < since)
+                || self.until.is_some_and(|until| date > until)
+            {
+                return None;
+            }
+        }
+
+        let mut entry = entry.clone();
+        if let Some(network) = &self.network {
+            let network = network.to_lowercase();
+            entry.deployments.retain(|deployment| {
+                NetworkCluster::from_url(&deployment.rpc_url)
+                    .label()
+                    .eq_ignore_ascii_case(&network)
+                    || deployment.rpc_url.to_lowercase().contains(&network)
+            });
+        }
+        (!entry.deployments.is_empty()).then_some(entry)
+    }
+}
+
+/// `~/.feature-gate-multisig-tool/history.jsonl`, next to the config
+pub fn history_path() -> Result<PathBuf> {
+    Ok(get_config_path()?.with_file_name("history.jsonl"))
+}
+
+/// Appends `entry` as one JSON line, creating the file if needed
+pub fn append_history(path: &Path, entry: &HistoryEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let line = serde_json::to_string(entry)?;
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)?;
+    Ok(())
+}
+
+/// Every entry in the history file, oldest first, and how many lines
+/// couldn't be read. A missing file is an empty history.
+pub fn read_history(path: &Path) -> Result<(Vec<HistoryEntry>, usize)> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+        Err(e) => return Err(eyre::eyre!("Failed to read {}: {}", path.display(), e)),
+    };
+
+    let mut entries = Vec::new();
+    let mut unreadable = 0;
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str(line) {
+            Ok(entry) => entries.push(entry),
+            Err(_) => unreadable += 1,
+        }
+    }
+    Ok((entries, unreadable))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_pubkey::Pubkey;
+
+    fn entry(timestamp: &str, rpc_urls: &[&str]) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: timestamp.to_string(),
+            label: None,
+            threshold: 1,
+            members: vec![],
+            deployments: rpc_urls
+                .iter()
+                .map(|rpc_url| HistoryDeployment {
+                    rpc_url: rpc_url.to_string(),
+                    multisig: Pubkey::new_unique().to_string(),
+                    vault: Pubkey::new_unique().to_string(),
+                    signature: "sig".to_string(),
+                    fee_payer: Pubkey::new_unique().to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_history_round_trip_skips_unreadable_lines() {
+        let dir = std::env::temp_dir().join(format!("history-test-{}", Pubkey::new_unique()));
+        let path = dir.join("history.jsonl");
+        assert!(read_history(&path).unwrap().0.is_empty());
+
+        let first = entry("2026-01-02T03:04:05+00:00", &["https://api.devnet.solana.com"]);
+        append_history(&path, &first).unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{not json\n")
+            .unwrap();
+        append_history(&path, &entry("2026-02-01T00:00:00+00:00", &[])).unwrap();
+
+        let (entries, unreadable) = read_history(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(unreadable, 1);
+        assert_eq!(entries[0].deployments[0].multisig, first.deployments[0].multisig);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_history_filter() {
+        let devnet = "https://api.devnet.solana.com";
+        let custom = "https://my-rpc.example.com";
+        let entry = entry("2026-03-10T23:59:00+00:00", &[devnet, custom]);
+        let date = |day: &str| Some(NaiveDate::parse_from_str(day, "%Y-%m-%d").unwrap());
+
+        assert!(HistoryFilter::default().apply(&entry).is_some());
+        let in_range = HistoryFilter {
+            since: date("2026-03-10"),
+            until: date("2026-03-10"),
+            network: None,
+        };
+        assert!(in_range.apply(&entry).is_some());
+        let too_late = HistoryFilter {
+            since: date("2026-03-11"),
+            ..Default::default()
+        };
+        assert!(too_late.apply(&entry).is_none());
+
+        let on_network = |network: &str| HistoryFilter {
+            network: Some(network.to_string()),
+            ..Default::default()
+        };
+        let devnet_only = on_network("Devnet").apply(&entry).unwrap();
+        assert_eq!(devnet_only.deployments.len(), 1);
+        assert_eq!(devnet_only.deployments[0].rpc_url, devnet);
+        assert_eq!(on_network("my-rpc").apply(&entry).unwrap().deployments[0].rpc_url, custom);
+        assert!(on_network("mainnet").apply(&entry).is_none());
+    }
+}
@@ -0,0 +1,243 @@
+//! End-to-end tests against a local validator, run with
+//! `cargo test --features integration-tests`.
+//!
+//! The validator must have the Squads program and its program config account
+//! (and the treasury that config names) loaded, e.g. cloned from mainnet:
+//!
+//! ```text
+//! solana-test-validator --reset --url mainnet-beta \
+//!     --clone-upgradeable-program SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf \
+//!     --clone <PROGRAM_CONFIG> --clone <TREASURY>
+//! ```
+//!
+//! `FEATURE_GATE_TEST_RPC_URL` points the tests at a validator other than
+//! `http://127.0.0.1:8899`.
+
+use crate::feature_gate_program::FeatureState;
+use crate::provision::{
+    create_multisig, create_rpc_client, create_vote_transaction_message, fetch_feature_state,
+    fetch_multisig, prefund_vault, send_and_confirm_transaction, RetryBudget, SendStrategy,
+    TransactionOptions, Vote,
+};
+use crate::squads::{
+    get_multisig_pda, get_proposal_pda, get_transaction_pda, get_vault_pda, Member,
+    MultisigCreateArgsV2, MultisigExecuteTransactionAccounts, Permission, Permissions, Proposal,
+    ProposalStatus, VaultTransaction, EXECUTE_TRANSACTION_DISCRIMINATOR,
+    SQUADS_MULTISIG_PROGRAM_ID,
+};
+use crate::utils::{create_and_send_feature_gate_proposals, feature_gate_proposal_types};
+use borsh::BorshDeserialize;
+use solana_client::rpc_client::RpcClient;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_keypair::Keypair;
+use solana_message::v0::Message;
+use solana_message::VersionedMessage;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::versioned::VersionedTransaction;
+use std::time::{Duration, Instant};
+
+const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
+
+fn rpc_url() -> String {
+    std::env::var("FEATURE_GATE_TEST_RPC_URL").unwrap_or_else(|_| DEFAULT_RPC_URL.to_string())
+}
+
+/// A fresh keypair holding `sol` SOL from the validator's faucet
+fn funded_keypair(rpc_client: &RpcClient, sol: u64) -> Keypair {
+    let keypair = Keypair::new();
+    rpc_client
+        .request_airdrop(&keypair.pubkey(), sol * crate::constants::LAMPORTS_PER_SOL)
+        .expect("airdrop request failed; is a local validator running?");
+
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while rpc_client.get_balance(&keypair.pubkey()).unwrap_or(0) == 0 {
+        assert!(Instant::now() < deadline, "airdrop did not land");
+        std::thread::sleep(Duration::from_millis(500));
+    }
+    keypair
+}
+
+fn fetch_proposal(rpc_client: &RpcClient, multisig: &Pubkey, transaction_index: u64) -> Proposal {
+    let proposal_address = get_proposal_pda(multisig, transaction_index, None).0;
+    let data = rpc_client.get_account_data(&proposal_address).unwrap();
+    Proposal::deserialize(&mut &data[8..]).unwrap()
+}
+
+fn send(rpc_client: &RpcClient, message: Message, signers: &[&dyn Signer]) -> String {
+    let transaction =
+        VersionedTransaction::try_new(VersionedMessage::V0(message), signers).unwrap();
+    send_and_confirm_transaction(&transaction, rpc_client, SendStrategy::default()).unwrap()
+}
+
+fn approve(
+    rpc_client: &RpcClient,
+    multisig: &Pubkey,
+    transaction_index: u64,
+    member: &Keypair,
+    fee_payer: &Keypair,
+) {
+    let message = create_vote_transaction_message(
+        &SQUADS_MULTISIG_PROGRAM_ID,
+        multisig,
+        transaction_index,
+        &member.pubkey(),
+        &fee_payer.pubkey(),
+        Vote::Approve,
+        rpc_client.get_latest_blockhash().unwrap(),
+    )
+    .unwrap();
+    send(rpc_client, message, &[fee_payer, member]);
+}
+
+/// Executes the approved vault transaction at `transaction_index`, passing
+/// its message accounts through with the vault signed for by the program
+fn execute(
+    rpc_client: &RpcClient,
+    multisig: &Pubkey,
+    transaction_index: u64,
+    member: &Keypair,
+    fee_payer: &Keypair,
+) {
+    let transaction_address = get_transaction_pda(multisig, transaction_index, None).0;
+    let data = rpc_client.get_account_data(&transaction_address).unwrap();
+    let vault_transaction = VaultTransaction::deserialize(&mut &data[8..]).unwrap();
+    let vault = get_vault_pda(multisig, vault_transaction.vault_index, None).0;
+
+    let message = &vault_transaction.message;
+    let execution_accounts = message
+        .account_keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| AccountMeta {
+            pubkey: *key,
+            is_signer: message.is_signer_index(i) && *key != vault,
+            is_writable: message.is_static_writable_index(i),
+        })
+        .collect();
+
+    let accounts = MultisigExecuteTransactionAccounts {
+        multisig: *multisig,
+        proposal: get_proposal_pda(multisig, transaction_index, None).0,
+        transaction: transaction_address,
+        member: member.pubkey(),
+    };
+    let instruction = Instruction::new_with_bytes(
+        SQUADS_MULTISIG_PROGRAM_ID,
+        EXECUTE_TRANSACTION_DISCRIMINATOR,
+        accounts.to_account_metas(execution_accounts),
+    );
+    let message = Message::try_compile(
+        &fee_payer.pubkey(),
+        &[instruction],
+        &[],
+        rpc_client.get_latest_blockhash().unwrap(),
+    )
+    .unwrap();
+    send(rpc_client, message, &[fee_payer, member]);
+}
+
+#[tokio::test]
+async fn test_feature_gate_lifecycle() {
+    let rpc_url = rpc_url();
+    let rpc_client = create_rpc_client(&rpc_url);
+    let tx_options = TransactionOptions::default();
+    let retry_budget = RetryBudget::for_deployment();
+
+    let fee_payer = funded_keypair(&rpc_client, 10);
+    let members = [Keypair::new(), Keypair::new()];
+    let permissions =
+        Permissions::from_actions(&[Permission::Initiate, Permission::Vote, Permission::Execute]);
+    let args = MultisigCreateArgsV2 {
+        config_authority: None,
+        threshold: 2,
+        members: members
+            .iter()
+            .map(|member| Member {
+                key: member.pubkey(),
+                permissions,
+            })
+            .collect(),
+        time_lock: 0,
+        rent_collector: None,
+        memo: None,
+    };
+
+    // Multisig
+    let create_key = Keypair::new();
+    let (multisig_address, _) = create_multisig(
+        rpc_url.clone(),
+        None,
+        &fee_payer,
+        &create_key,
+        args,
+        &tx_options,
+        &retry_budget,
+        true,
+    )
+    .await
+    .unwrap();
+    assert_eq!(multisig_address, get_multisig_pda(&create_key.pubkey(), None).0);
+
+    let multisig = fetch_multisig(&rpc_client, &multisig_address).unwrap();
+    assert_eq!(multisig.threshold, 2);
+    assert_eq!(multisig.transaction_index, 0);
+    assert_eq!(multisig.members.len(), 2);
+    for member in &members {
+        assert!(multisig
+            .members
+            .iter()
+            .any(|m| m.key == member.pubkey() && m.permissions == permissions));
+    }
+
+    // The vault is the feature account, so it funds its own rent
+    let feature_id = get_vault_pda(&multisig_address, 0, None).0;
+    let rent = rpc_client
+        .get_minimum_balance_for_rent_exemption(crate::feature_gate_program::FEATURE_ACCOUNT_SIZE)
+        .unwrap();
+    prefund_vault(&rpc_url, &fee_payer, &feature_id, rent, &tx_options, &retry_budget).unwrap();
+
+    // Activation and revocation proposals
+    create_and_send_feature_gate_proposals(
+        &rpc_url,
+        &Some(fee_payer.insecure_clone()),
+        &members[0],
+        &multisig_address,
+        1,
+        &feature_gate_proposal_types(true, true),
+        &tx_options,
+        &retry_budget,
+    )
+    .await
+    .unwrap();
+    assert_eq!(fetch_multisig(&rpc_client, &multisig_address).unwrap().transaction_index, 2);
+    for index in [1, 2] {
+        let proposal = fetch_proposal(&rpc_client, &multisig_address, index);
+        assert_eq!(proposal.multisig, multisig_address);
+        assert!(matches!(proposal.status, ProposalStatus::Active { .. }));
+    }
+
+    // Approve the activation
+    for member in &members {
+        approve(&rpc_client, &multisig_address, 1, member, &fee_payer);
+    }
+    let proposal = fetch_proposal(&rpc_client, &multisig_address, 1);
+    assert!(matches!(proposal.status, ProposalStatus::Approved { .. }));
+    assert_eq!(proposal.approved.len(), 2);
+    for member in &members {
+        assert!(proposal.approved.contains(&member.pubkey()));
+    }
+
+    // Execute it, queueing the feature
+    assert!(matches!(
+        fetch_feature_state(&rpc_client, &feature_id).unwrap(),
+        FeatureState::NotCreated
+    ));
+    execute(&rpc_client, &multisig_address, 1, &members[0], &fee_payer);
+    let proposal = fetch_proposal(&rpc_client, &multisig_address, 1);
+    assert!(matches!(proposal.status, ProposalStatus::Executed { .. }));
+    assert!(matches!(
+        fetch_feature_state(&rpc_client, &feature_id).unwrap(),
+        FeatureState::Pending
+    ));
+}
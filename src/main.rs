@@ -1,17 +1,38 @@
+mod address_lookup_table;
+mod audit;
 mod commands;
 mod constants;
+mod error;
 mod feature_gate_program;
+mod history;
+#[cfg(all(test, feature = "integration-tests"))]
+mod integration_tests;
+mod network;
 mod output;
+mod plan;
 mod provision;
+mod rate_limit;
+mod spec;
 mod squads;
 mod utils;
 
-use crate::commands::{config_command, create_command, interactive_mode, show_command};
+use crate::commands::{
+    activate_proposal_command, approve_all_pending, batch_create_command, bench_command,
+    close_proposal_command, config_command, create_command, create_member_lut_command,
+    create_proposals_command, create_transaction_command, decode_command, export_config_command,
+    history_command, import_config_command, interactive_mode, is_blockhash_valid_command,
+    list_command, list_proposals_command, show_command, vaults_command, watch_command,
+    whoami_command, CreateOptions, SummaryFormat,
+};
 use crate::output::Output;
-use crate::utils::{load_config, prompt_for_threshold};
+use crate::provision::{SendStrategy, TransactionOptions};
+use crate::utils::{ensure_interactive, load_config, parse_pubkey, prompt_for_threshold};
 use eyre::Result;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 use colored::*;
+use solana_pubkey::Pubkey;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "feature-gate-multisig-tool")]
@@ -32,11 +53,33 @@ For more information, run: feature-gate-multisig-tool help <COMMAND>"
 )]
 #[command(version = "0.1.0")]
 struct Cli {
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "Print only final results (addresses, signatures); warnings and errors go to stderr"
+    )]
+    quiet: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Disable colored output (also off when NO_COLOR is set or stdout isn't a terminal)"
+    )]
+    no_color: bool,
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Send at most N RPC requests per second when sending transactions and fetching accounts (overrides `rps` in the config; unlimited by default)"
+    )]
+    rps: Option<u32>,
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)] // parsed once at startup; boxing buys nothing
 enum Commands {
     #[command(about = "Create a new multisig wallet with interactive setup")]
     #[command(
@@ -68,43 +111,640 @@ The contributor key receives Initiate-only permissions, while additional members
             help = "Keypair file path for paying transaction fees (e.g., ~/.config/solana/id.json)"
         )]
         keypair: Option<String>,
+        #[arg(
+            long,
+            value_name = "SOL",
+            help = "Top up the default vault (index 0) to this SOL balance after creating the multisig"
+        )]
+        prefund_vault: Option<f64>,
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = SendStrategy::Once,
+            help = "How transactions are submitted: send once per attempt, or re-send every ~2s until confirmed"
+        )]
+        send_strategy: SendStrategy,
+        #[arg(
+            long,
+            value_name = "PUBKEY",
+            help = "Squads program id to deploy against (defaults to the canonical Squads v4 program)"
+        )]
+        program_id: Option<String>,
+        #[arg(
+            long,
+            value_name = "PUBKEY",
+            conflicts_with_all = ["plan_out", "plan_in"],
+            help = "Pay the creation fee to this treasury without reading the program config (for mock or forked programs on a local validator)"
+        )]
+        treasury: Option<String>,
+        #[arg(
+            long,
+            value_name = "SHA256",
+            help = "Abort unless the target program's executable hash matches (as reported by solana-verify)"
+        )]
+        expected_program_hash: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "expected_program_hash",
+            help = "Allow a non-canonical --program-id without --expected-program-hash off localnet, leaving its code unverified"
+        )]
+        allow_unverified_program: bool,
+        #[arg(
+            long,
+            value_name = "MICROLAMPORTS",
+            help = "Compute unit price for all transactions (defaults per network: localnet 0, devnet/testnet low, mainnet high)"
+        )]
+        priority_fee: Option<u64>,
+        #[arg(
+            long,
+            help = "Estimate the compute unit price from each RPC's recent prioritization fees (75th percentile), falling back to --priority-fee or the network default"
+        )]
+        auto_priority_fee: bool,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Write the full creation plan (members, threshold, program, networks, create key) to a JSON file for review"
+        )]
+        plan_out: Option<PathBuf>,
+        #[arg(
+            long,
+            requires = "plan_out",
+            help = "Stop after writing --plan-out without sending any transactions"
+        )]
+        plan_only: bool,
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with_all = ["plan_out", "threshold"],
+            help = "Execute a plan written by --plan-out, checking the on-chain program and treasury still match"
+        )]
+        plan_in: Option<PathBuf>,
+        #[arg(short = 'y', long, help = "Skip confirmation prompts")]
+        yes: bool,
+        #[arg(
+            long,
+            value_name = "NAME",
+            help = "Human-readable name saved with the multisig, usable in place of its address"
+        )]
+        label: Option<String>,
+        #[arg(
+            long,
+            help = "Only create the multisig; add the activation and revocation proposals later with create-proposals"
+        )]
+        no_proposals: bool,
+        #[arg(
+            long,
+            conflicts_with_all = ["no_proposals", "no_revocation"],
+            help = "Skip the activation proposal; the revocation proposal takes index 1"
+        )]
+        no_activation: bool,
+        #[arg(
+            long,
+            conflicts_with_all = ["no_proposals", "no_activation"],
+            help = "Skip the revocation proposal; only the activation proposal is created"
+        )]
+        no_revocation: bool,
+        #[arg(long, help = "Create proposals as drafts; voting opens after activate-proposal")]
+        draft: bool,
+        #[arg(
+            long,
+            help = "Print each transaction's instructions (program, accounts with signer/writable flags, data hex) before signing"
+        )]
+        dump_instructions: bool,
+        #[arg(
+            long,
+            help = "Before signing, check each writable non-signer account is new, a system account or owned by the Squads program, warning about any other"
+        )]
+        validate_accounts: bool,
+        #[arg(
+            long,
+            value_name = "DIR",
+            conflicts_with = "keypair",
+            help = "Directory of fee payer keypairs (*.json), used round-robin so each network is paid by a different key"
+        )]
+        fee_payer_dir: Option<PathBuf>,
+        #[arg(
+            long,
+            help = "Refuse to create the activation proposal while its funder can't cover the feature account's rent, instead of warning"
+        )]
+        require_funded_feature: bool,
+        #[arg(
+            long,
+            value_name = "PUBKEY",
+            help = "Send a revoked feature account's lamports here instead of the incinerator"
+        )]
+        revocation_destination: Option<String>,
+        #[arg(
+            long,
+            help = "Allow --program-id, --treasury and --revocation-destination on mainnet, where they are otherwise refused"
+        )]
+        i_understand_mainnet_risk: bool,
+        #[arg(
+            long,
+            help = "Summarize each deployment as one tab-separated line: network, multisig, vault, signature"
+        )]
+        compact: bool,
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = SummaryFormat::Pretty,
+            conflicts_with = "compact",
+            help = "Summary output: pretty, compact (as --compact), or csv with deployment and member rows"
+        )]
+        format: SummaryFormat,
+        #[arg(
+            long,
+            value_name = "N",
+            conflicts_with_all = ["plan_out", "plan_in"],
+            help = "Create N independent multisigs with the same members, threshold and networks; their create and setup keys are saved under ~/.feature-gate-multisig-tool/keys"
+        )]
+        count: Option<usize>,
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with_all = ["plan_in", "count"],
+            help = "Keypair file to use as the create key (e.g. a vanity keypair) so the multisig address is known in advance"
+        )]
+        create_key_path: Option<PathBuf>,
+        #[arg(
+            long,
+            conflicts_with_all = ["plan_in", "count", "create_key_path"],
+            help = "Derive the create key from a secret seed (PBKDF2 with a domain separator), so the same seed always gives the same multisig address; the seed is read from FEATURE_GATE_CREATE_KEY_SEED or a hidden prompt, never the command line"
+        )]
+        create_key_seed: bool,
+        #[arg(
+            long,
+            requires = "create_key_seed",
+            help = "Also derive the contributor (setup member) from --create-key-seed under its own domain separator, so it can be rebuilt to initiate later proposals"
+        )]
+        deterministic_contributor: bool,
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "fee_payer_dir",
+                "deterministic_contributor",
+                "plan_out",
+                "plan_in",
+                "count",
+            ],
+            help = "Add the fee payer as a member with every permission in place of the throwaway Initiate-only setup key, so one key both pays and votes"
+        )]
+        fee_payer_is_member: bool,
+        #[arg(
+            long,
+            value_name = "ADDRESS",
+            conflicts_with = "plan_in",
+            help = "Copy the members (and threshold, unless --threshold is given) of this existing multisig or saved label; initiate-only setup keys are skipped"
+        )]
+        members_from_multisig: Option<String>,
+        #[arg(
+            long,
+            value_name = "MASK",
+            requires = "members_from_multisig",
+            value_parser = parse_member_permissions,
+            help = "Give every copied member this permission mask (Initiate 1 + Vote 2 + Execute 4; must include Vote) instead of its mask in the source multisig"
+        )]
+        member_permissions: Option<u8>,
+        #[arg(
+            long,
+            value_name = "ADDRESS",
+            conflicts_with_all = ["plan_in", "members_from_multisig"],
+            help = "Copy the members, threshold (unless --threshold is given), time lock and config authority of this existing multisig or saved label, with a fresh create key"
+        )]
+        template: Option<String>,
+        #[arg(
+            long,
+            help = "Show members sorted by pubkey instead of in entry order in the review and summary; Squads stores them sorted by pubkey either way (the setup key is identified by its Initiate-only permission)"
+        )]
+        members_sort: bool,
+        #[arg(
+            long,
+            help = "After each multisig is confirmed, read it back and fail unless its members and threshold exactly match the request"
+        )]
+        verify_after_create: bool,
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with_all = ["plan_in", "template", "members_from_multisig", "threshold"],
+            help = "JSON (or .toml) spec with threshold, members, networks, time_lock, config_authority and rent_collector, used instead of the interactive prompts"
+        )]
+        spec: Option<PathBuf>,
+        #[arg(
+            long,
+            conflicts_with = "label",
+            help = "Don't save the threshold, members or created multisigs to the config (the deployment history is still written)"
+        )]
+        no_save_config: bool,
+    },
+    #[command(about = "Create every multisig described in a manifest of specs")]
+    #[command(
+        long_about = "Reads a JSON array of multisig specs (the --spec format, plus optional label and create_key_seed per entry), validates all of them, then creates each on its own networks with its activation and revocation proposals. A failed multisig doesn't stop the rest; the command ends with a success/failure tally and exits non-zero if any failed. Every multisig is saved to the config and the deployment history, and its create and setup keys to ~/.feature-gate-multisig-tool/keys."
+    )]
+    BatchCreate {
+        #[arg(long, value_name = "PATH", help = "JSON array of multisig specs")]
+        manifest: PathBuf,
+        #[arg(
+            short = 'k',
+            long,
+            help = "Keypair file path for paying transaction fees (e.g., ~/.config/solana/id.json)"
+        )]
+        keypair: Option<String>,
+        #[arg(
+            long,
+            value_name = "DIR",
+            conflicts_with = "keypair",
+            help = "Directory of fee payer keypairs (*.json), used round-robin so each network is paid by a different key"
+        )]
+        fee_payer_dir: Option<PathBuf>,
+        #[arg(
+            long,
+            value_name = "MICROLAMPORTS",
+            help = "Compute unit price for all transactions (defaults per network: localnet 0, devnet/testnet low, mainnet high)"
+        )]
+        priority_fee: Option<u64>,
+        #[arg(long, help = "Only create the multisigs; add proposals later with create-proposals")]
+        no_proposals: bool,
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = SummaryFormat::Pretty,
+            help = "Summary output: pretty, compact or csv"
+        )]
+        format: SummaryFormat,
+        #[arg(
+            long,
+            help = "Don't save the created multisigs to the config (the deployment history is still written)"
+        )]
+        no_save_config: bool,
+        #[arg(long, help = "Skip the mainnet confirmation prompts")]
+        yes: bool,
+    },
+    #[command(about = "Create the feature gate activation and revocation proposals on an existing multisig")]
+    #[command(
+        long_about = "For multisigs created with --no-proposals: creates the activation and revocation proposals as the next two transaction indices, initiated by the given member keypair (which also pays fees)."
+    )]
+    CreateProposals {
+        #[arg(help = "The multisig address (or saved label)")]
+        address: String,
+        #[arg(long, help = "RPC URL to send to (prompts from saved networks if not provided)")]
+        url: Option<String>,
+        #[arg(
+            short = 'k',
+            long,
+            help = "Keypair of a member with Initiate permission; also pays fees (defaults to the configured fee payer)"
+        )]
+        keypair: Option<String>,
+        #[arg(long, value_name = "MICROLAMPORTS", help = "Compute unit price (defaults per network)")]
+        priority_fee: Option<u64>,
+        #[arg(long, help = "Create proposals as drafts; voting opens after activate-proposal")]
+        draft: bool,
+        #[arg(
+            long,
+            help = "Print each transaction's instructions (program, accounts with signer/writable flags, data hex) before signing"
+        )]
+        dump_instructions: bool,
+        #[arg(
+            long,
+            help = "Before signing, check each writable non-signer account is new, a system account or owned by the Squads program, warning about any other"
+        )]
+        validate_accounts: bool,
+        #[arg(
+            long,
+            help = "Refuse to create the activation proposal while its funder can't cover the feature account's rent, instead of warning"
+        )]
+        require_funded_feature: bool,
+        #[arg(
+            long,
+            value_name = "PUBKEY",
+            help = "Send a revoked feature account's lamports here instead of the incinerator"
+        )]
+        revocation_destination: Option<String>,
+        #[arg(
+            long,
+            help = "Allow --revocation-destination on mainnet, where it is otherwise refused"
+        )]
+        i_understand_mainnet_risk: bool,
+        #[arg(
+            long,
+            help = "Create the proposals even if the feature is already pending or active"
+        )]
+        force: bool,
+        #[arg(short = 'y', long, help = "Skip the confirmation prompt")]
+        yes: bool,
+    },
+    #[command(about = "Propose an arbitrary compiled Solana message as a vault transaction")]
+    #[command(
+        long_about = "Converts a base64-encoded Solana message (legacy or v0, e.g. from web3.js `message.serialize()`) into the Squads transaction message format and creates it, with a proposal, as the multisig's next vault transaction. Use this for feature-gate instructions beyond the built-in activation and revocation proposals."
+    )]
+    CreateTransaction {
+        #[arg(help = "The multisig address (or saved label)")]
+        address: String,
+        #[arg(long, value_name = "BASE64", help = "Serialized Solana message to propose")]
+        message_base64: String,
+        #[arg(long, help = "RPC URL to send to (prompts from saved networks if not provided)")]
+        url: Option<String>,
+        #[arg(
+            short = 'k',
+            long,
+            help = "Keypair of a member with Initiate permission; also pays fees (defaults to the configured fee payer)"
+        )]
+        keypair: Option<String>,
+        #[arg(long, value_name = "MICROLAMPORTS", help = "Compute unit price (defaults per network)")]
+        priority_fee: Option<u64>,
+        #[arg(long, help = "Create the proposal as a draft; voting opens after activate-proposal")]
+        draft: bool,
+        #[arg(
+            long,
+            help = "Print each transaction's instructions (program, accounts with signer/writable flags, data hex) before signing"
+        )]
+        dump_instructions: bool,
+        #[arg(
+            long,
+            help = "Before signing, check each writable non-signer account is new, a system account or owned by the Squads program, warning about any other"
+        )]
+        validate_accounts: bool,
+        #[arg(short = 'y', long, help = "Skip the confirmation prompt")]
+        yes: bool,
+    },
+    #[command(about = "Approve every active proposal on a multisig in one go")]
+    #[command(
+        long_about = "Lists the multisig's active proposals with their decoded intents, asks for a single confirmation, then approves each one the voter hasn't already approved and prints a per-proposal result."
+    )]
+    Approve {
+        #[arg(help = "The multisig address (or saved label)")]
+        address: String,
+        #[arg(long, required = true, help = "Approve all active proposals")]
+        all_pending: bool,
+        #[arg(long, help = "RPC URL to send to (prompts from saved networks if not provided)")]
+        url: Option<String>,
+        #[arg(
+            short = 'k',
+            long,
+            help = "Keypair of a member with Vote permission; also pays fees (defaults to the configured fee payer)"
+        )]
+        keypair: Option<String>,
+        #[arg(short = 'y', long, help = "Skip the confirmation prompt")]
+        yes: bool,
+    },
+    #[command(about = "Open voting on a draft proposal")]
+    ActivateProposal {
+        #[arg(help = "The multisig address (or saved label)")]
+        address: String,
+        #[arg(help = "Transaction index of the draft proposal")]
+        index: u64,
+        #[arg(long, help = "RPC URL to send to (prompts from saved networks if not provided)")]
+        url: Option<String>,
+        #[arg(
+            short = 'k',
+            long,
+            help = "Keypair of a member with Initiate permission; also pays fees (defaults to the configured fee payer)"
+        )]
+        keypair: Option<String>,
     },
     #[command(about = "Show feature multisig details for a given address")]
     #[command(
         long_about = "Display detailed information about an existing multisig wallet including member permissions, threshold settings, and network deployment status."
     )]
     Show {
-        #[arg(help = "The multisig address to inspect")]
+        #[arg(help = "The multisig address (or saved label) to inspect")]
         address: Option<String>,
+        #[arg(
+            long,
+            help = "Report the cluster (from its genesis hash) behind each network searched, to rule out querying the wrong network"
+        )]
+        identify_clusters: bool,
+        #[arg(long, default_value_t = 0, help = "Vault index whose holdings are shown")]
+        vault_index: u8,
+        #[arg(long, help = "Also list the vault's SPL token account balances")]
+        tokens: bool,
+    },
+    #[command(about = "List multisigs created with this tool and their labels")]
+    List {
+        #[arg(
+            long,
+            help = "List each multisig's feature gate ID and whether it is pending or active on its network instead"
+        )]
+        features: bool,
+    },
+    #[command(about = "Show past deployments recorded by create")]
+    #[command(
+        long_about = "Prints every create run recorded in ~/.feature-gate-multisig-tool/history.jsonl: when it ran, its threshold and members, and the multisig, feature gate ID and signature of each deployment. The file is append-only, so deployments stay listed even after they are removed from the config."
+    )]
+    History {
+        #[arg(long, help = "Only deployments on or after this date (YYYY-MM-DD, UTC)")]
+        since: Option<String>,
+        #[arg(long, help = "Only deployments on or before this date (YYYY-MM-DD, UTC)")]
+        until: Option<String>,
+        #[arg(long, help = "Only deployments on this network (devnet, mainnet, ...) or RPC URL")]
+        network: Option<String>,
+    },
+    #[command(about = "List every transaction index and report missing transactions or proposals")]
+    #[command(
+        long_about = "Fetches the transaction and proposal account of every index from 1 to the multisig's transaction index, classifying each as transaction + proposal, transaction only (the proposal creation failed) or missing, and reports the gaps. Read-only."
+    )]
+    ListProposals {
+        #[arg(help = "The multisig address (or saved label)")]
+        address: String,
+        #[arg(long, help = "RPC URL to query (prompts from saved networks if not provided)")]
+        url: Option<String>,
+    },
+    #[command(about = "List the SOL balances of a range of a multisig's vaults")]
+    #[command(
+        long_about = "Derives the vault addresses for a range of vault indices (0 to 19 by default) and fetches them together with getMultipleAccounts, listing the funded ones and their total. Read-only."
+    )]
+    Vaults {
+        #[arg(help = "The multisig address (or saved label)")]
+        address: String,
+        #[arg(long, help = "RPC URL to query (prompts from saved networks if not provided)")]
+        url: Option<String>,
+        #[arg(long, default_value_t = 0, help = "First vault index to check")]
+        first: u8,
+        #[arg(
+            long,
+            default_value_t = 20,
+            value_parser = clap::value_parser!(u16).range(1..=256),
+            help = "How many consecutive vault indices to check"
+        )]
+        count: u16,
+        #[arg(long, help = "Also list vaults with no balance")]
+        all: bool,
+    },
+    #[command(about = "Follow a proposal until it is executed, rejected or cancelled")]
+    #[command(
+        long_about = "Polls the proposal account for the given multisig and transaction index, printing each status transition (Active → Approved → Executed, or Rejected/Cancelled) with a timestamp. Exits once a terminal state is reached or the timeout elapses."
+    )]
+    Watch {
+        #[arg(help = "The multisig address (or saved label)")]
+        address: String,
+        #[arg(help = "Transaction index of the proposal to watch")]
+        index: u64,
+        #[arg(long, help = "RPC URL to poll (prompts from saved networks if not provided)")]
+        url: Option<String>,
+        #[arg(long, default_value_t = 3600, help = "Give up after this many seconds")]
+        timeout: u64,
+        #[arg(long, default_value_t = 5, help = "Seconds between polls")]
+        interval: u64,
+    },
+    #[command(about = "Close a finished proposal's transaction and proposal accounts to reclaim rent")]
+    #[command(
+        long_about = "Closes the vault transaction and proposal accounts at the given index once the proposal is executed, rejected or cancelled, or has gone stale (index at or below the multisig's stale transaction index) without being approved; an approved proposal can still be executed and can't be closed. Rent is returned to the multisig's rent collector; multisigs without one can't close accounts."
+    )]
+    CloseProposal {
+        #[arg(help = "The multisig address (or saved label)")]
+        address: String,
+        #[arg(help = "Transaction index of the proposal to close")]
+        index: u64,
+        #[arg(long, help = "RPC URL to send to (prompts from saved networks if not provided)")]
+        url: Option<String>,
+        #[arg(
+            short = 'k',
+            long,
+            help = "Keypair paying the transaction fee (defaults to the configured fee payer)"
+        )]
+        keypair: Option<String>,
+        #[arg(
+            long,
+            value_name = "PUBKEY",
+            help = "Expected rent recipient; must match the multisig's rent collector"
+        )]
+        rent_collector: Option<String>,
+        #[arg(short = 'y', long, help = "Skip the confirmation prompt")]
+        yes: bool,
+    },
+    #[command(about = "Create an address lookup table holding all current multisig members")]
+    #[command(
+        long_about = "Fetches the multisig's members and writes them to a new address lookup table owned by the keypair, so transactions that reference many members can use table indices instead of full pubkeys. Large member sets are added over several transactions."
+    )]
+    CreateMemberLut {
+        #[arg(help = "The multisig address (or saved label)")]
+        address: String,
+        #[arg(long, help = "RPC URL to send to (prompts from saved networks if not provided)")]
+        url: Option<String>,
+        #[arg(
+            short = 'k',
+            long,
+            help = "Keypair that owns the table and pays for it (defaults to the configured fee payer)"
+        )]
+        keypair: Option<String>,
+        #[arg(short = 'y', long, help = "Skip the confirmation prompt")]
+        yes: bool,
+    },
+    #[command(about = "Check a generated transaction's blockhash hasn't expired before signing it")]
+    IsBlockhashValid {
+        #[arg(help = "The blockhash recorded with the generated transaction")]
+        blockhash: String,
+        #[arg(long, help = "RPC URL to check against (prompts from saved networks if not provided)")]
+        url: Option<String>,
+    },
+    #[command(about = "Decode an encoded transaction to review it before signing")]
+    #[command(
+        long_about = "Deserializes a base64 or base58 VersionedTransaction (e.g. one generated by this tool and passed round for offline signing) and prints its fee payer, recent blockhash, required signers with whether each has signed, and every instruction's program, accounts and data. Nothing is sent."
+    )]
+    Decode {
+        #[arg(
+            long,
+            value_name = "BLOB",
+            required_unless_present = "base58",
+            conflicts_with = "base58",
+            help = "The transaction, base64-encoded"
+        )]
+        base64: Option<String>,
+        #[arg(long, value_name = "BLOB", help = "The transaction, base58-encoded")]
+        base58: Option<String>,
+    },
+    #[command(about = "Show which keys the tool can sign with, their balances and multisig membership")]
+    Whoami {
+        #[arg(
+            short,
+            long = "keypair",
+            help = "Additional keypair to check (repeatable); the configured fee payer is always checked"
+        )]
+        keypairs: Vec<String>,
+        #[arg(long, help = "Multisig address or saved label to check membership of")]
+        multisig: Option<String>,
+        #[arg(long, help = "RPC URL to check (defaults to every saved network)")]
+        url: Option<String>,
+    },
+    #[command(about = "Measure RPC latency and health across the saved networks")]
+    #[command(
+        long_about = "Times get_latest_blockhash round trips and checks get_health for every saved network (or --url), reporting min/avg/max latency fastest first. Read-only."
+    )]
+    Bench {
+        #[arg(long, default_value_t = 5, help = "Round trips to time per network")]
+        samples: usize,
+        #[arg(long, help = "RPC URL to benchmark (defaults to every saved network)")]
+        url: Option<String>,
     },
     #[command(about = "Start interactive mode (default when no command is specified)")]
     #[command(
         long_about = "Launches the interactive mode which provides a guided experience for creating multisig wallets. This is the default mode when no command is specified."
     )]
-    Interactive,
+    Interactive {
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Also save each generated transaction to <PATH>/<multisig>_<action>.txt"
+        )]
+        output_dir: Option<PathBuf>,
+        #[arg(
+            long,
+            help = "Build execute transactions even if the proposal isn't approved or its time lock hasn't elapsed"
+        )]
+        force: bool,
+    },
     #[command(about = "Show current configuration including networks and saved members")]
     #[command(
         long_about = "Displays the current configuration stored in ~/.feature-gate-multisig-tool/config.json including:
 • Saved networks array for automatic deployment
 • Saved member public keys
 • Default threshold setting
-• Configuration file location"
+• Configuration file location
+
+Use `config export` / `config import` to share members and networks with a team."
     )]
-    Config,
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    #[command(about = "Write the config, minus the fee payer path, to share with a team")]
+    Export {
+        #[arg(help = "File to write")]
+        path: PathBuf,
+    },
+    #[command(about = "Merge a shared config into the local one, prompting on conflicts")]
+    #[command(
+        long_about = "Adds the imported networks, members and saved multisigs to the local config and takes the imported threshold. Where a member's permissions or a label differ, asks which to keep. The local fee payer is left unchanged."
+    )]
+    Import {
+        #[arg(help = "Config file written by `config export`")]
+        path: PathBuf,
+    },
 }
 
 
 #[tokio::main]
 async fn main() {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    audit::set_command(matches.subcommand_name().unwrap_or("interactive"));
+    Output::set_quiet(cli.quiet);
+    Output::configure_color(cli.no_color);
 
-    let result = match cli.command {
-        Some(command) => handle_command(command).await,
-        None => interactive_mode().await,
-    };
+    let command = cli.command.unwrap_or(Commands::Interactive {
+        output_dir: None,
+        force: false,
+    });
+    let result = handle_command(command, cli.rps).await;
 
     if let Err(e) = result {
-        Output::error(&format!("Error: {}", e));
+        Output::error(&format!("Error: {:#}", e));
+        if let Some(logs) = error::simulation_logs(&e) {
+            Output::simulation_logs(logs);
+        }
 
         // Provide helpful error messages for common issues
         let error_msg = e.to_string();
@@ -120,18 +760,56 @@ async fn main() {
 
         Output::separator();
         Output::hint("Run --help for usage information");
-        std::process::exit(1);
+        std::process::exit(error::exit_code(&e));
     }
 }
 
-async fn handle_command(command: Commands) -> Result<()> {
+async fn handle_command(command: Commands, rps: Option<u32>) -> Result<()> {
     let mut config = load_config()?;
+    rate_limit::configure(rps.or(config.rps));
 
     match command {
         Commands::Create {
             threshold,
             signers: _,
             keypair,
+            prefund_vault,
+            send_strategy,
+            program_id,
+            treasury,
+            expected_program_hash,
+            allow_unverified_program,
+            priority_fee,
+            auto_priority_fee,
+            plan_out,
+            plan_only,
+            plan_in,
+            yes,
+            label,
+            no_proposals,
+            no_activation,
+            no_revocation,
+            draft,
+            dump_instructions,
+            validate_accounts,
+            fee_payer_dir,
+            require_funded_feature,
+            revocation_destination,
+            i_understand_mainnet_risk,
+            compact,
+            format,
+            count,
+            create_key_path,
+            create_key_seed,
+            deterministic_contributor,
+            fee_payer_is_member,
+            members_from_multisig,
+            member_permissions,
+            template,
+            members_sort,
+            verify_after_create,
+            spec,
+            no_save_config,
         } => {
             let threshold_option = threshold.map(|t| {
                 if t == 0 {
@@ -145,13 +823,338 @@ async fn handle_command(command: Commands) -> Result<()> {
                 }
             }).flatten();
 
-            create_command(&mut config, threshold_option, vec![], keypair).await
+            if let Some(sol) = prefund_vault {
+                if !sol.is_finite() || sol <= 0.0 {
+                    return Err(eyre::eyre!("--prefund-vault must be a positive SOL amount"));
+                }
+            }
+
+            let program_id = program_id
+                .map(|id| {
+                    parse_pubkey(&id)
+                        .map_err(|_| eyre::eyre!("Invalid --program-id pubkey: {}", id))
+                })
+                .transpose()?;
+            let treasury = treasury
+                .map(|key| {
+                    parse_pubkey(&key)
+                        .map_err(|_| eyre::eyre!("Invalid --treasury pubkey: {}", key))
+                })
+                .transpose()?;
+
+            let options = CreateOptions {
+                prefund_vault_sol: prefund_vault,
+                transaction: TransactionOptions {
+                    program_id,
+                    priority_fee,
+                    auto_priority_fee,
+                    send_strategy,
+                    draft,
+                    require_funded_feature,
+                    revocation_destination: parse_revocation_destination(revocation_destination)?,
+                    mainnet_risk_acknowledged: i_understand_mainnet_risk,
+                    dump_instructions,
+                    validate_accounts,
+                    treasury,
+                },
+                expected_program_hash,
+                allow_unverified_program,
+                plan_out,
+                plan_only,
+                plan_in,
+                yes,
+                label: label.map(|label| label.trim().to_string()),
+                no_proposals,
+                no_activation,
+                no_revocation,
+                fee_payer_dir,
+                summary_format: if compact { SummaryFormat::Compact } else { format },
+                count,
+                create_key_path,
+                create_key_seed: create_key_seed.then(read_create_key_seed).transpose()?,
+                deterministic_contributor,
+                fee_payer_is_member,
+                members_from_multisig,
+                member_permissions,
+                template,
+                members_sort,
+                verify_after_create,
+                spec,
+                no_save_config,
+            };
+
+            create_command(&mut config, threshold_option, vec![], keypair, &options).await
         }
-        Commands::Show { address } => {
-            show_command(&config, address).await
+        Commands::BatchCreate {
+            manifest,
+            keypair,
+            fee_payer_dir,
+            priority_fee,
+            no_proposals,
+            format,
+            no_save_config,
+            yes,
+        } => {
+            if !yes {
+                ensure_interactive("pass --yes to skip confirmation prompts")?;
+            }
+            let options = CreateOptions {
+                transaction: TransactionOptions {
+                    priority_fee,
+                    ..Default::default()
+                },
+                yes,
+                no_proposals,
+                fee_payer_dir,
+                summary_format: format,
+                no_save_config,
+                ..Default::default()
+            };
+            batch_create_command(&mut config, &manifest, keypair, &options).await
+        }
+        Commands::CreateProposals {
+            address,
+            url,
+            keypair,
+            priority_fee,
+            draft,
+            dump_instructions,
+            validate_accounts,
+            require_funded_feature,
+            revocation_destination,
+            i_understand_mainnet_risk,
+            force,
+            yes,
+        } => {
+            if url.is_none() || !yes {
+                ensure_interactive("pass --url <RPC_URL> and --yes")?;
+            }
+            let tx_options = TransactionOptions {
+                priority_fee,
+                draft,
+                require_funded_feature,
+                revocation_destination: parse_revocation_destination(revocation_destination)?,
+                mainnet_risk_acknowledged: i_understand_mainnet_risk,
+                dump_instructions,
+                validate_accounts,
+                ..Default::default()
+            };
+            create_proposals_command(&config, address, url, keypair, &tx_options, yes, force).await
         }
-        Commands::Interactive => interactive_mode().await,
-        Commands::Config => config_command(&config).await,
+        Commands::CreateTransaction {
+            address,
+            message_base64,
+            url,
+            keypair,
+            priority_fee,
+            draft,
+            dump_instructions,
+            validate_accounts,
+            yes,
+        } => {
+            if url.is_none() || !yes {
+                ensure_interactive("pass --url <RPC_URL> and --yes")?;
+            }
+            let tx_options = TransactionOptions {
+                priority_fee,
+                draft,
+                dump_instructions,
+                validate_accounts,
+                ..Default::default()
+            };
+            create_transaction_command(
+                &config,
+                address,
+                message_base64,
+                url,
+                keypair,
+                &tx_options,
+                yes,
+            )
+            .await
+        }
+        Commands::Show {
+            address,
+            identify_clusters,
+            vault_index,
+            tokens,
+        } => {
+            if address.is_none() {
+                ensure_interactive("pass the multisig address as an argument")?;
+            }
+            show_command(&config, address, identify_clusters, vault_index, tokens).await
+        }
+        Commands::ListProposals { address, url } => {
+            if url.is_none() {
+                ensure_interactive("pass --url <RPC_URL>")?;
+            }
+            list_proposals_command(&config, address, url).await
+        }
+        Commands::Vaults {
+            address,
+            url,
+            first,
+            count,
+            all,
+        } => {
+            if url.is_none() {
+                ensure_interactive("pass --url <RPC_URL>")?;
+            }
+            vaults_command(&config, address, url, first, count, all).await
+        }
+        Commands::Watch {
+            address,
+            index,
+            url,
+            timeout,
+            interval,
+        } => {
+            if url.is_none() {
+                ensure_interactive("pass --url <RPC_URL>")?;
+            }
+            watch_command(
+                &config,
+                address,
+                index,
+                url,
+                Duration::from_secs(timeout),
+                Duration::from_secs(interval.max(1)),
+            )
+            .await
+        }
+        Commands::Approve {
+            address,
+            all_pending: _,
+            url,
+            keypair,
+            yes,
+        } => {
+            if url.is_none() || !yes {
+                ensure_interactive("pass --url <RPC_URL> and --yes")?;
+            }
+            approve_all_pending(&config, address, url, keypair, yes).await
+        }
+        Commands::ActivateProposal {
+            address,
+            index,
+            url,
+            keypair,
+        } => {
+            if url.is_none() {
+                ensure_interactive("pass --url <RPC_URL>")?;
+            }
+            activate_proposal_command(&config, address, index, url, keypair).await
+        }
+        Commands::CloseProposal {
+            address,
+            index,
+            url,
+            keypair,
+            rent_collector,
+            yes,
+        } => {
+            if url.is_none() || !yes {
+                ensure_interactive("pass --url <RPC_URL> and --yes")?;
+            }
+            let rent_collector = rent_collector
+                .map(|key| {
+                    parse_pubkey(&key)
+                        .map_err(|_| eyre::eyre!("Invalid --rent-collector pubkey: {}", key))
+                })
+                .transpose()?;
+            close_proposal_command(&config, address, index, url, keypair, rent_collector, yes)
+                .await
+        }
+        Commands::CreateMemberLut {
+            address,
+            url,
+            keypair,
+            yes,
+        } => {
+            if url.is_none() || !yes {
+                ensure_interactive("pass --url <RPC_URL> and --yes")?;
+            }
+            create_member_lut_command(&config, address, url, keypair, yes).await
+        }
+        Commands::IsBlockhashValid { blockhash, url } => {
+            if url.is_none() {
+                ensure_interactive("pass --url <RPC_URL>")?;
+            }
+            is_blockhash_valid_command(&config, blockhash, url).await
+        }
+        Commands::Decode { base64, base58 } => decode_command(base64, base58),
+        Commands::Whoami { keypairs, multisig, url } => {
+            whoami_command(&config, keypairs, multisig, url).await
+        }
+        Commands::Bench { samples, url } => bench_command(&config, samples, url).await,
+        Commands::Interactive { output_dir, force } => {
+            start_interactive_mode(output_dir.as_deref(), force).await
+        }
+        Commands::List { features } => list_command(&config, features).await,
+        Commands::History {
+            since,
+            until,
+            network,
+        } => history_command(since, until, network),
+        Commands::Config { action: None } => config_command(&config).await,
+        Commands::Config {
+            action: Some(ConfigAction::Export { path }),
+        } => export_config_command(&config, &path).await,
+        Commands::Config {
+            action: Some(ConfigAction::Import { path }),
+        } => import_config_command(&mut config, &path).await,
+    }
+}
+
+async fn start_interactive_mode(output_dir: Option<&Path>, force: bool) -> Result<()> {
+    ensure_interactive(
+        "run a subcommand such as `show <ADDRESS>` or `create --plan-in <PATH> --yes`",
+    )?;
+    interactive_mode(output_dir, force).await
+}
+
+/// Parses `--member-permissions`: a mask from 1 to 7 that lets members vote,
+/// since the copied members are the ones the threshold counts
+fn parse_member_permissions(mask: &str) -> Result<u8, String> {
+    let mask: u8 = mask.parse().map_err(|_| format!("'{}' is not a number", mask))?;
+    let permissions = crate::squads::Permissions { mask };
+    if mask > 7 || !permissions.has(crate::squads::Permission::Vote) {
+        return Err(format!(
+            "{} must be a permission mask from 1 to 7 that includes Vote (2)",
+            mask
+        ));
     }
+    Ok(mask)
 }
 
+/// Variable `--create-key-seed` reads the seed from, for scripted runs
+const CREATE_KEY_SEED_ENV: &str = "FEATURE_GATE_CREATE_KEY_SEED";
+
+/// The secret seed for `--create-key-seed`, from `CREATE_KEY_SEED_ENV` or a
+/// hidden prompt, so it never shows up in `ps` or shell history
+fn read_create_key_seed() -> Result<String> {
+    let seed = match std::env::var(CREATE_KEY_SEED_ENV) {
+        Ok(seed) => seed,
+        Err(_) => {
+            ensure_interactive(&format!("set {} to the seed", CREATE_KEY_SEED_ENV))?;
+            inquire::Password::new("Create key seed:")
+                .with_display_mode(inquire::PasswordDisplayMode::Masked)
+                .with_custom_confirmation_message("Seed (again):")
+                .prompt()?
+        }
+    };
+    if seed.is_empty() {
+        let message = "the create key seed must not be empty".to_string();
+        return Err(error::ToolError::Config(message).into());
+    }
+    Ok(seed)
+}
+
+fn parse_revocation_destination(destination: Option<String>) -> Result<Option<Pubkey>> {
+    destination
+        .map(|address| {
+            parse_pubkey(&address)
+                .map_err(|_| eyre::eyre!("Invalid --revocation-destination pubkey: {}", address))
+        })
+        .transpose()
+}
@@ -1,46 +1,136 @@
 use colored::*;
+use indicatif::ProgressBar;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tabled::{settings::Style, Table, Tabled};
+
+/// Set by `--quiet`: only results (`result`, `table`, `csv`) are printed,
+/// with warnings and errors on stderr
+static QUIET: AtomicBool = AtomicBool::new(false);
 
 /// Centralized output formatting for consistent UI throughout the application
 pub struct Output;
 
 impl Output {
+    pub fn set_quiet(quiet: bool) {
+        QUIET.store(quiet, Ordering::Relaxed);
+    }
+
+    pub fn is_quiet() -> bool {
+        QUIET.load(Ordering::Relaxed)
+    }
+
+    /// Turns ANSI colors off for `--no-color`, a non-empty `NO_COLOR`
+    /// (https://no-color.org) or stdout that isn't a terminal, so captured
+    /// logs stay readable. The override is global to `colored`, so it covers
+    /// these helpers and every command's own colored strings.
+    pub fn configure_color(no_color: bool) {
+        let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        if !use_color(no_color, no_color_env, std::io::stdout().is_terminal()) {
+            colored::control::set_override(false);
+        }
+    }
+
+    /// Final result of a command, such as a created address or signature;
+    /// printed even with `--quiet`
+    pub fn result(msg: &str) {
+        println!("{}", msg);
+    }
+
+    /// Undecorated status line, suppressed by `--quiet`
+    pub fn plain(msg: &str) {
+        if !Self::is_quiet() {
+            println!("{}", msg);
+        }
+    }
+
+    /// Steady-ticking spinner, hidden with `--quiet`
+    pub fn spinner(msg: impl Into<std::borrow::Cow<'static, str>>) -> ProgressBar {
+        if Self::is_quiet() {
+            return ProgressBar::hidden();
+        }
+        let progress = ProgressBar::new_spinner().with_message(msg);
+        progress.enable_steady_tick(std::time::Duration::from_millis(100));
+        progress
+    }
+
     /// Success message with green checkmark
     pub fn success(msg: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("{} {}", "✅".bright_green(), msg);
     }
 
     /// Information message with blue info icon
     pub fn info(msg: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("{} {}", "ℹ️".bright_blue(), msg);
     }
 
-    /// Warning message with yellow warning icon
+    /// Warning message with yellow warning icon; sent to stderr with
+    /// `--quiet` rather than dropped, as warnings can be safety-relevant
     pub fn warning(msg: &str) {
+        if Self::is_quiet() {
+            eprintln!("Warning: {}", msg);
+            return;
+        }
         println!("{} {}", "⚠️".bright_yellow(), msg);
     }
 
     /// Error message with red X icon
     pub fn error(msg: &str) {
+        if Self::is_quiet() {
+            eprintln!("{}", msg);
+            return;
+        }
         println!("{} {}", "❌".bright_red(), msg);
     }
 
     /// Header with yellow bold text
     pub fn header(msg: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("{}", msg.bright_yellow().bold());
     }
 
+    /// Program logs from a failed preflight simulation
+    pub fn simulation_logs(logs: &[String]) {
+        if Self::is_quiet() {
+            eprintln!("Simulation logs:");
+            logs.iter().for_each(|line| eprintln!("  {}", line));
+            return;
+        }
+        println!("{}", "Simulation logs:".bright_cyan().bold());
+        for line in logs {
+            println!("  {}", line.bright_yellow());
+        }
+    }
+
     /// Subheader with cyan bold text
     pub fn subheader(msg: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("{}", msg.bright_cyan().bold());
     }
 
     /// Field display with cyan key and white value
     pub fn field(key: &str, value: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("  {}: {}", key.cyan(), value.bright_white());
     }
 
     /// Numbered field display (for lists)
     pub fn numbered_field(index: usize, key: &str, value: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!(
             "    {}: {}",
             format!("{} {}", index, key).cyan(),
@@ -50,41 +140,65 @@ impl Output {
 
     /// Process step with blue arrow
     pub fn step(msg: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("{} {}", "📤".bright_blue(), msg);
     }
 
     /// Network status with globe icon
     pub fn network(msg: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("🌐 {}", msg.bright_white());
     }
 
     /// Search/fetch with magnifying glass
     pub fn search(msg: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("🔍 {}", msg.bright_white());
     }
 
     /// Target/address display with target icon
     pub fn target(msg: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("🎯 {}", msg.bright_white());
     }
 
     /// Progress with rocket icon
     pub fn progress(msg: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("🚀 {}", msg);
     }
 
     /// Hint message with blue lightbulb
     pub fn hint(msg: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("{} {}", "💡 Hint:".bright_blue(), msg);
     }
 
     /// Separator line for sections
     pub fn separator() {
+        if Self::is_quiet() {
+            return;
+        }
         println!();
     }
 
     /// Double separator with equals signs
     pub fn section_break(title: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("{}", "═".repeat(80).bright_green());
         println!("{}", title.bright_green().bold());
         println!("{}", "═".repeat(80).bright_green());
@@ -93,6 +207,9 @@ impl Output {
 
     /// Single line separator with dashes
     pub fn subsection_break(title: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("{}", "─".repeat(50).bright_cyan());
         println!("{}", title.bright_cyan().bold());
         println!("{}", "─".repeat(50).bright_cyan());
@@ -100,6 +217,9 @@ impl Output {
 
     /// Configuration display with special formatting
     pub fn config_item(key: &str, value: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!(
             "  {}: {}",
             key.cyan(),
@@ -113,24 +233,149 @@ impl Output {
 
     /// Transaction signature display
     pub fn signature(label: &str, sig: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("  {}: {}", label.cyan(), sig.bright_cyan());
     }
 
     /// Address display with consistent formatting
     pub fn address(label: &str, addr: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("  {}: {}", label.cyan(), addr.bright_white());
     }
 
     /// Status indicators for various states
     pub fn status_found(msg: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("✅ {}", msg.bright_green());
     }
 
     pub fn status_not_found(msg: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("❌ {}", msg.bright_red());
     }
 
     pub fn status_processing(msg: &str) {
+        if Self::is_quiet() {
+            return;
+        }
         println!("⚙️ {}", msg.bright_white());
     }
+
+    /// Rounded table of `rows`, falling back to one key-value block per row
+    /// when the table is wider than the terminal. Piped output always gets
+    /// the full table.
+    pub fn table<T: Tabled>(rows: &[T]) {
+        let mut table = Table::new(rows);
+        table.with(Style::rounded());
+
+        match terminal_width() {
+            Some(width) if table.total_width() > width => print!("{}", render_vertical(rows)),
+            _ => println!("{}", table),
+        }
+    }
+
+    /// `rows` as CSV under a header line, for spreadsheets and reporting
+    /// pipelines
+    pub fn csv<T: Tabled>(rows: &[T]) {
+        print!("{}", render_csv(rows));
+    }
+}
+
+fn use_color(no_color_flag: bool, no_color_env: bool, is_terminal: bool) -> bool {
+    !no_color_flag && !no_color_env && is_terminal
+}
+
+fn terminal_width() -> Option<usize> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    crossterm::terminal::size().ok().map(|(columns, _)| usize::from(columns))
+}
+
+/// Each row as `header: value` lines, rows separated by a rule
+fn render_vertical<T: Tabled>(rows: &[T]) -> String {
+    let headers = T::headers();
+    let mut output = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        if i > 0 {
+            output.push_str(&format!("  {}\n", "─".repeat(20).dimmed()));
+        }
+        for (header, value) in headers.iter().zip(row.fields()) {
+            output.push_str(&format!("  {}: {}\n", header.cyan(), value.bright_white()));
+        }
+    }
+    output
+}
+
+/// Header line then one line per row, quoting fields that hold commas,
+/// quotes or line breaks
+fn render_csv<T: Tabled>(rows: &[T]) -> String {
+    let record = |fields: Vec<std::borrow::Cow<'_, str>>| -> String {
+        let fields: Vec<String> = fields
+            .iter()
+            .map(|field| {
+                if field.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.to_string()
+                }
+            })
+            .collect();
+        format!("{}\n", fields.join(","))
+    };
+    let mut output = record(T::headers());
+    for row in rows {
+        output.push_str(&record(row.fields()));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Tabled)]
+    struct Row {
+        #[tabled(rename = "Public Key")]
+        key: &'static str,
+        #[tabled(rename = "Bitmask")]
+        mask: u8,
+    }
+
+    #[test]
+    fn test_render_vertical_lists_each_field() {
+        colored::control::set_override(false);
+        let rows = [Row { key: "A", mask: 7 }, Row { key: "B", mask: 1 }];
+        let rendered = render_vertical(&rows);
+        let lines: Vec<&str> = rendered.lines().map(str::trim).collect();
+        assert_eq!(lines[0], "Public Key: A");
+        assert_eq!(lines[1], "Bitmask: 7");
+        assert_eq!(lines[3], "Public Key: B");
+        assert_eq!(lines[4], "Bitmask: 1");
+    }
+
+    #[test]
+    fn test_use_color_only_on_terminals_without_opt_out() {
+        assert!(use_color(false, false, true));
+        assert!(!use_color(true, false, true));
+        assert!(!use_color(false, true, true));
+        assert!(!use_color(false, false, false));
+    }
+
+    #[test]
+    fn test_render_csv_quotes_special_fields() {
+        let rows = [Row { key: "A", mask: 7 }, Row { key: "B, \"C\"", mask: 1 }];
+        assert_eq!(
+            render_csv(&rows),
+            "Public Key,Bitmask\nA,7\n\"B, \"\"C\"\"\",1\n"
+        );
+    }
 }
\ No newline at end of file
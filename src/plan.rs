@@ -0,0 +1,296 @@
+use crate::provision::{create_rpc_client, fetch_program_treasury, RetryBudget};
+use crate::squads::{get_multisig_pda, get_vault_pda, Member, MultisigCreateArgsV2, Permissions};
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use std::fs;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+/// Every parameter of a multisig creation, written for review with
+/// `--plan-out` and replayed exactly with `--plan-in`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreationPlan {
+    pub program_id: String,
+    pub create_key: String,
+    /// Derived from `create_key`; informational for reviewers
+    pub multisig: String,
+    /// Default vault (index 0), i.e. the feature gate ID
+    pub vault: String,
+    pub threshold: u16,
+    pub time_lock: u32,
+    pub config_authority: Option<String>,
+    pub rent_collector: Option<String>,
+    pub members: Vec<PlannedMember>,
+    pub networks: Vec<PlannedNetwork>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedMember {
+    pub key: String,
+    pub permissions: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedNetwork {
+    pub rpc_url: String,
+    /// Treasury configured in the Squads program config when the plan was made
+    pub treasury: String,
+}
+
+/// Secret keys the plan depends on, stored next to the plan so the exact same
+/// multisig and setup member can be reproduced
+#[derive(Serialize, Deserialize)]
+struct PlanKeys {
+    create_key: Vec<u8>,
+    setup_key: Vec<u8>,
+}
+
+impl CreationPlan {
+    /// Builds a plan, fetching the treasury each network currently expects
+    pub fn new(
+        program_id: &Pubkey,
+        create_key: &Pubkey,
+        args: &MultisigCreateArgsV2,
+        networks: &[String],
+    ) -> Result<Self> {
+        let multisig = get_multisig_pda(create_key, Some(program_id)).0;
+        let vault = get_vault_pda(&multisig, 0, Some(program_id)).0;
+
+        let networks = networks
+            .iter()
+            .map(|rpc_url| {
+                let rpc_client = create_rpc_client(rpc_url);
+                let treasury = fetch_program_treasury(&rpc_client, program_id, &RetryBudget::default())
+                    .map_err(|e| eyre::eyre!("{}: {}", rpc_url, e))?;
+                Ok(PlannedNetwork {
+                    rpc_url: rpc_url.clone(),
+                    treasury: treasury.to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            program_id: program_id.to_string(),
+            create_key: create_key.to_string(),
+            multisig: multisig.to_string(),
+            vault: vault.to_string(),
+            threshold: args.threshold,
+            time_lock: args.time_lock,
+            config_authority: args.config_authority.map(|key| key.to_string()),
+            rent_collector: args.rent_collector.map(|key| key.to_string()),
+            members: args
+                .members
+                .iter()
+                .map(|member| PlannedMember {
+                    key: member.key.to_string(),
+                    permissions: member.permissions.mask,
+                })
+                .collect(),
+            networks,
+        })
+    }
+
+    pub fn program_id(&self) -> Result<Pubkey> {
+        parse_pubkey("program_id", &self.program_id)
+    }
+
+    pub fn network_urls(&self) -> Vec<String> {
+        self.networks.iter().map(|n| n.rpc_url.clone()).collect()
+    }
+
+    /// The `multisig_create_v2` arguments described by this plan
+    pub fn create_args(&self) -> Result<MultisigCreateArgsV2> {
+        let members = self
+            .members
+            .iter()
+            .map(|member| {
+                Ok(Member {
+                    key: parse_pubkey("members.key", &member.key)?,
+                    permissions: Permissions {
+                        mask: member.permissions,
+                    },
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(MultisigCreateArgsV2 {
+            config_authority: self
+                .config_authority
+                .as_deref()
+                .map(|key| parse_pubkey("config_authority", key))
+                .transpose()?,
+            threshold: self.threshold,
+            members,
+            time_lock: self.time_lock,
+            rent_collector: self
+                .rent_collector
+                .as_deref()
+                .map(|key| parse_pubkey("rent_collector", key))
+                .transpose()?,
+            memo: None,
+        })
+    }
+
+    /// Checks every network still has the program and the treasury the plan
+    /// was reviewed against
+    pub fn verify_on_chain(&self) -> Result<()> {
+        let program_id = self.program_id()?;
+        for network in &self.networks {
+            let expected = parse_pubkey("networks.treasury", &network.treasury)?;
+            let rpc_client = create_rpc_client(&network.rpc_url);
+            let actual = fetch_program_treasury(&rpc_client, &program_id, &RetryBudget::default())
+                .map_err(|e| eyre::eyre!("{}: {}", network.rpc_url, e))?;
+            if actual != expected {
+                return Err(eyre::eyre!(
+                    "Treasury mismatch on {}: plan expects {}, program config has {}",
+                    network.rpc_url,
+                    expected,
+                    actual
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the plan as JSON and the keys it depends on to `<path>.keys.json`
+    pub fn save(&self, path: &Path, create_key: &Keypair, setup_key: &Keypair) -> Result<PathBuf> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .map_err(|e| eyre::eyre!("Failed to write plan {}: {}", path.display(), e))?;
+
+        let keys_path = keys_path(path);
+        save_keys(&keys_path, create_key, setup_key)?;
+        Ok(keys_path)
+    }
+
+    /// Reads a plan and its keys, checking the keys are the ones it names
+    pub fn load(path: &Path) -> Result<(Self, Keypair, Keypair)> {
+        let plan_json = fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("Failed to read plan {}: {}", path.display(), e))?;
+        let plan: CreationPlan = serde_json::from_str(&plan_json)
+            .map_err(|e| eyre::eyre!("Failed to parse plan {}: {}", path.display(), e))?;
+
+        let keys_path = keys_path(path);
+        let keys_json = Zeroizing::new(fs::read_to_string(&keys_path).map_err(|e| {
+            eyre::eyre!("Failed to read plan keys {}: {}", keys_path.display(), e)
+        })?);
+        let keys: PlanKeys = serde_json::from_str(&keys_json)?;
+        let create_key = Keypair::try_from(Zeroizing::new(keys.create_key).as_slice())
+            .map_err(|e| eyre::eyre!("Invalid create key in {}: {}", keys_path.display(), e))?;
+        let setup_key = Keypair::try_from(Zeroizing::new(keys.setup_key).as_slice())
+            .map_err(|e| eyre::eyre!("Invalid setup key in {}: {}", keys_path.display(), e))?;
+
+        if create_key.pubkey() != parse_pubkey("create_key", &plan.create_key)? {
+            return Err(eyre::eyre!("Create key in {} doesn't match the plan", keys_path.display()));
+        }
+        let setup_pubkey = setup_key.pubkey().to_string();
+        if !plan.members.iter().any(|member| member.key == setup_pubkey) {
+            return Err(eyre::eyre!(
+                "Setup key in {} is not a member of the plan",
+                keys_path.display()
+            ));
+        }
+
+        let program_id = plan.program_id()?;
+        let multisig = get_multisig_pda(&create_key.pubkey(), Some(&program_id)).0;
+        if multisig.to_string() != plan.multisig {
+            return Err(eyre::eyre!(
+                "Plan multisig {} doesn't match the address derived from its create key ({})",
+                plan.multisig,
+                multisig
+            ));
+        }
+
+        Ok((plan, create_key, setup_key))
+    }
+}
+
+/// Writes a multisig's create and setup secret keys, readable only by the
+/// owner, in the format `CreationPlan::load` expects
+pub fn save_keys(path: &Path, create_key: &Keypair, setup_key: &Keypair) -> Result<()> {
+    let keys = PlanKeys {
+        create_key: create_key.to_bytes().to_vec(),
+        setup_key: setup_key.to_bytes().to_vec(),
+    };
+    let keys_json = Zeroizing::new(serde_json::to_string(&keys)?);
+    write_secret_file(path, keys_json.as_bytes())
+        .map_err(|e| eyre::eyre!("Failed to write keys {}: {}", path.display(), e))
+}
+
+fn keys_path(plan_path: &Path) -> PathBuf {
+    plan_path.with_extension("keys.json")
+}
+
+fn parse_pubkey(field: &str, value: &str) -> Result<Pubkey> {
+    crate::utils::parse_pubkey(value)
+        .map_err(|_| eyre::eyre!("Invalid pubkey for `{}`: {}", field, value))
+}
+
+#[cfg(unix)]
+fn write_secret_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?
+        .write_all(contents)
+}
+
+#[cfg(not(unix))]
+fn write_secret_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::squads::Permission;
+
+    #[test]
+    fn test_plan_round_trip() {
+        let dir = std::env::temp_dir().join(format!("plan-test-{}", Pubkey::new_unique()));
+        fs::create_dir_all(&dir).unwrap();
+        let plan_path = dir.join("plan.json");
+
+        let program_id = crate::squads::SQUADS_MULTISIG_PROGRAM_ID;
+        let create_key = Keypair::new();
+        let setup_key = Keypair::new();
+        let args = MultisigCreateArgsV2 {
+            config_authority: None,
+            threshold: 2,
+            members: vec![
+                Member {
+                    key: setup_key.pubkey(),
+                    permissions: Permissions::from_actions(&[Permission::Initiate]),
+                },
+                Member {
+                    key: Pubkey::new_unique(),
+                    permissions: Permissions::from_actions(&Permission::ALL),
+                },
+            ],
+            time_lock: 0,
+            rent_collector: Some(Pubkey::new_unique()),
+            memo: None,
+        };
+        // No networks, so no RPC access is needed
+        let plan = CreationPlan::new(&program_id, &create_key.pubkey(), &args, &[]).unwrap();
+        plan.save(&plan_path, &create_key, &setup_key).unwrap();
+
+        let (loaded, loaded_create_key, loaded_setup_key) = CreationPlan::load(&plan_path).unwrap();
+        assert_eq!(loaded_create_key.pubkey(), create_key.pubkey());
+        assert_eq!(loaded_setup_key.pubkey(), setup_key.pubkey());
+
+        let loaded_args = loaded.create_args().unwrap();
+        assert_eq!(loaded_args.threshold, 2);
+        assert_eq!(loaded_args.rent_collector, args.rent_collector);
+        assert!(loaded_args.members == args.members);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,23 +1,31 @@
 use crate::constants::*;
+use crate::error::ToolError;
+use crate::network::NetworkCluster;
+use crate::output::Output;
+use crate::feature_gate_program::{FeatureState, FEATURE_ACCOUNT_SIZE};
 use crate::squads::{
     get_multisig_pda, get_program_config_pda, get_proposal_pda, get_transaction_pda, get_vault_pda,
-    Member, MultisigApproveProposalData, MultisigCreateArgsV2, MultisigCreateProposalAccounts,
+    Member, Multisig, MultisigActivateProposalAccounts, MultisigActivateProposalData,
+    MultisigApproveProposalData, MultisigCloseTransactionAccounts,
+    MultisigCloseTransactionData, MultisigCreateArgsV2, MultisigCreateProposalAccounts,
     MultisigCreateProposalArgs, MultisigCreateProposalData, MultisigCreateTransaction,
     MultisigCreateV2Accounts, MultisigCreateV2Data, MultisigExecuteTransactionAccounts,
-    MultisigVoteOnProposalAccounts, MultisigVoteOnProposalArgs, Permissions, ProgramConfig,
+    MultisigRejectProposalData, MultisigVoteOnProposalAccounts, MultisigVoteOnProposalArgs,
+    Permissions, ProgramConfig, PROGRAM_CONFIG_DISCRIMINATOR, SQUADS_MULTISIG_PROGRAM_ID,
     TransactionMessage, VaultTransaction, VaultTransactionCreateArgs,
     VaultTransactionCreateArgsData, EXECUTE_TRANSACTION_DISCRIMINATOR,
 };
-use crate::utils::decode_permissions;
+use crate::utils::{decode_permissions, fee_payer_role_note, parse_pubkey, unique_signers};
 use borsh::BorshDeserialize;
 use colored::Colorize;
 use dialoguer::Confirm;
-use eyre::eyre;
-use indicatif::ProgressBar;
-use solana_client::client_error::ClientErrorKind;
+use eyre::{eyre, WrapErr};
+use solana_account::Account;
+use solana_account_decoder_client_types::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::client_error::{ClientError, ClientErrorKind};
 use solana_client::nonblocking;
 use solana_client::rpc_client::RpcClient;
-use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcSendTransactionConfig};
 use solana_client::rpc_request::{RpcError, RpcResponseErrorData};
 use solana_client::rpc_response::RpcSimulateTransactionResult;
 use solana_commitment_config::CommitmentConfig;
@@ -28,63 +36,441 @@ use solana_keypair::Keypair;
 use solana_message::v0::Message;
 use solana_message::VersionedMessage;
 use solana_pubkey::Pubkey;
+use solana_signature::Signature;
 use solana_signer::Signer;
 use solana_transaction::versioned::VersionedTransaction;
-use std::str::FromStr;
-use std::time::Duration;
+use solana_transaction_error::TransactionError;
+use sha2::{Digest, Sha256};
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+const BPF_LOADER_UPGRADEABLE_ID: Pubkey =
+    Pubkey::from_str_const("BPFLoaderUpgradeab1e11111111111111111111111");
+
+/// Size of the `UpgradeableLoaderState::ProgramData` header preceding the ELF
+const PROGRAM_DATA_METADATA_SIZE: usize = 45;
+
+/// Transaction indices of the activation and revocation proposals `create`
+/// adds to a new multisig. Squads indices start at 1.
+pub const ACTIVATION_TRANSACTION_INDEX: u64 = 1;
+pub const REVOCATION_TRANSACTION_INDEX: u64 = 2;
 
 /// Creates an RPC client with consistent commitment configuration
 pub fn create_rpc_client(url: &str) -> RpcClient {
     RpcClient::new_with_commitment(url, CommitmentConfig::confirmed())
 }
 
+/// Whether `rpc_url` reaches mainnet-beta, judged by its host or, for a
+/// custom host, by the cluster's genesis hash. A custom RPC that can't be
+/// asked counts as mainnet, so a guard on this fails safe.
+pub fn is_mainnet(rpc_url: &str) -> bool {
+    match NetworkCluster::from_url(rpc_url) {
+        NetworkCluster::MainnetBeta => true,
+        NetworkCluster::Custom => {
+            let genesis_hash = create_rpc_client(rpc_url).get_genesis_hash();
+            genesis_hash.map_or(true, |hash| {
+                NetworkCluster::from_genesis_hash(&hash.to_string()) == NetworkCluster::MainnetBeta
+            })
+        }
+        _ => false,
+    }
+}
+
+/// Whether a deployment to `rpc_url` must be confirmed by typing its
+/// threshold: on mainnet as `is_mainnet` judges it, unless `yes`
+pub fn needs_mainnet_confirmation(rpc_url: &str, yes: bool) -> bool {
+    !yes && is_mainnet(rpc_url)
+}
+
+/// Async counterpart of `create_rpc_client`, for querying several networks at once
+pub fn create_nonblocking_rpc_client(url: &str) -> nonblocking::rpc_client::RpcClient {
+    nonblocking::rpc_client::RpcClient::new_with_commitment(
+        url.to_string(),
+        CommitmentConfig::confirmed(),
+    )
+}
+
+/// How a signed transaction is submitted while waiting for confirmation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum SendStrategy {
+    /// Send once, then poll until confirmed or the blockhash expires
+    #[default]
+    Once,
+    /// Re-send the same signed transaction every couple of seconds while
+    /// polling, until it confirms or its blockhash expires
+    Spam,
+}
+
+/// Caps the retrying done across all the RPC calls of one operation, such as
+/// deploying to a single network, on top of each call's own retry limit. Without
+/// it a slow network multiplies the per-call retries of every blockhash, fetch,
+/// send and confirmation into minutes of waiting.
+#[derive(Debug)]
+pub struct RetryBudget {
+    max_retries: usize,
+    max_duration: Duration,
+    started: Instant,
+    retries_used: Cell<usize>,
+}
+
+impl RetryBudget {
+    pub fn new(max_retries: usize, max_duration: Duration) -> Self {
+        Self {
+            max_retries,
+            max_duration,
+            started: Instant::now(),
+            retries_used: Cell::new(0),
+        }
+    }
+
+    /// The budget for deploying a multisig and its proposals to one network
+    pub fn for_deployment() -> Self {
+        Self::new(
+            DEPLOYMENT_MAX_RETRIES,
+            Duration::from_secs(DEPLOYMENT_MAX_RETRY_SECS),
+        )
+    }
+
+    /// Takes one retry from the budget, or returns false once the retries or
+    /// the time have run out
+    pub fn try_spend(&self) -> bool {
+        if self.is_exhausted() || self.retries_used.get() >= self.max_retries {
+            return false;
+        }
+        self.retries_used.set(self.retries_used.get() + 1);
+        true
+    }
+
+    pub fn remaining_time(&self) -> Duration {
+        self.max_duration.saturating_sub(self.started.elapsed())
+    }
+
+    /// Whether the time allowance has run out
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining_time().is_zero()
+    }
+}
+
+/// No shared cap: only each call's own retry limit applies
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self::new(usize::MAX, Duration::MAX)
+    }
+}
+
+impl std::fmt::Display for RetryBudget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.max_retries == usize::MAX {
+            return write!(f, "unlimited");
+        }
+        write!(
+            f,
+            "{} retries, {}s left",
+            self.max_retries - self.retries_used.get(),
+            self.remaining_time().as_secs()
+        )
+    }
+}
+
+/// Settings shared by every transaction the tool builds and sends
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TransactionOptions {
+    /// Squads program to target (the canonical program when unset)
+    pub program_id: Option<Pubkey>,
+    /// Compute unit price in micro-lamports (the network default when unset)
+    pub priority_fee: Option<u64>,
+    /// Estimate the compute unit price from recent prioritization fees,
+    /// falling back to `priority_fee` or the network default on failure
+    pub auto_priority_fee: bool,
+    pub send_strategy: SendStrategy,
+    /// Create proposals as drafts, which need `activate-proposal` before
+    /// members can vote
+    pub draft: bool,
+    /// Print each instruction's program, accounts and data before signing
+    pub dump_instructions: bool,
+    /// Check the owners of each message's writable accounts before signing,
+    /// warning about any the message couldn't plausibly write
+    pub validate_accounts: bool,
+    /// Refuse activation proposals while the funding account can't cover the
+    /// feature account's rent, instead of warning
+    pub require_funded_feature: bool,
+    /// Send a revoked feature account's lamports here instead of burning
+    /// them in the incinerator
+    pub revocation_destination: Option<Pubkey>,
+    /// Allow `unsafe_overrides` on mainnet, where they are otherwise refused
+    pub mainnet_risk_acknowledged: bool,
+    /// Pay the multisig creation fee to this treasury instead of the one in
+    /// the program config, which isn't read at all (for mock programs)
+    pub treasury: Option<Pubkey>,
+}
+
+impl TransactionOptions {
+    /// The account a revocation sends the feature account's lamports to
+    pub fn revocation_destination(&self) -> Pubkey {
+        self.revocation_destination
+            .unwrap_or(crate::feature_gate_program::INCINERATOR_ID)
+    }
+
+    /// Flags set that bypass a safety check or the canonical feature gate
+    /// flow; fine on a local validator, dangerous with real funds
+    pub fn unsafe_overrides(&self) -> Vec<&'static str> {
+        let mut overrides = Vec::new();
+        if self.program_id.is_some_and(|id| id != SQUADS_MULTISIG_PROGRAM_ID) {
+            overrides.push("--program-id");
+        }
+        if self.treasury.is_some() {
+            overrides.push("--treasury");
+        }
+        if self.revocation_destination() != crate::feature_gate_program::INCINERATOR_ID {
+            overrides.push("--revocation-destination");
+        }
+        overrides
+    }
+
+    /// Refuses `unsafe_overrides` when `rpc_url` reaches mainnet, unless
+    /// `mainnet_risk_acknowledged`
+    pub fn ensure_safe_for(&self, rpc_url: &str) -> eyre::Result<()> {
+        let overrides = self.unsafe_overrides();
+        if overrides.is_empty() || !is_mainnet(rpc_url) {
+            return Ok(());
+        }
+        if !self.mainnet_risk_acknowledged {
+            return Err(ToolError::Config(format!(
+                "{} refused on mainnet ({}); pass --i-understand-mainnet-risk to use {} anyway",
+                overrides.join(", "),
+                rpc_url,
+                if overrides.len() == 1 { "it" } else { "them" }
+            ))
+            .into());
+        }
+        Output::warning(&format!(
+            "Using {} on mainnet ({}) as acknowledged",
+            overrides.join(", "),
+            rpc_url
+        ));
+        Ok(())
+    }
+
+    /// The explicit priority fee if one was given, otherwise the network default
+    pub fn priority_fee_for(&self, rpc_url: &str) -> u64 {
+        self.priority_fee.unwrap_or_else(|| default_priority_fee(rpc_url))
+    }
+
+    /// Compute unit price for a transaction that writes `writable_accounts`
+    pub fn resolve_priority_fee(
+        &self,
+        rpc_client: &RpcClient,
+        rpc_url: &str,
+        writable_accounts: &[Pubkey],
+    ) -> u64 {
+        if self.auto_priority_fee {
+            if let Some(fee) = estimate_priority_fee(rpc_client, writable_accounts) {
+                return fee;
+            }
+        }
+        self.priority_fee_for(rpc_url)
+    }
+}
+
+/// Default compute unit price for a network: free on localnet, cheap on
+/// devnet/testnet, and high enough to land during mainnet congestion
+pub fn default_priority_fee(rpc_url: &str) -> u64 {
+    match NetworkCluster::from_url(rpc_url) {
+        NetworkCluster::Localnet => LOCALNET_PRIORITY_FEE,
+        NetworkCluster::Devnet | NetworkCluster::Testnet => DEVNET_PRIORITY_FEE,
+        NetworkCluster::MainnetBeta => MAINNET_PRIORITY_FEE,
+        NetworkCluster::Custom => DEFAULT_PRIORITY_FEE,
+    }
+}
+
+/// Queries recent prioritization fees for `writable_accounts` and returns the
+/// `AUTO_PRIORITY_FEE_PERCENTILE`th percentile, or `None` if the RPC doesn't
+/// support the method or has no samples
+pub fn estimate_priority_fee(rpc_client: &RpcClient, writable_accounts: &[Pubkey]) -> Option<u64> {
+    let fees = rpc_client
+        .get_recent_prioritization_fees(writable_accounts)
+        .ok()?
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect();
+    fee_percentile(fees, AUTO_PRIORITY_FEE_PERCENTILE)
+}
+
+/// Nearest-rank percentile of `fees`
+fn fee_percentile(mut fees: Vec<u64>, percentile: u64) -> Option<u64> {
+    if fees.is_empty() {
+        return None;
+    }
+    fees.sort_unstable();
+    let rank = (percentile.min(100) as usize * fees.len()).div_ceil(100);
+    Some(fees[rank.saturating_sub(1)])
+}
+
 pub fn send_and_confirm_transaction(
     transaction: &VersionedTransaction,
     rpc_client: &RpcClient,
+    strategy: SendStrategy,
 ) -> eyre::Result<String> {
+    send_and_confirm_transaction_with_budget(
+        transaction,
+        rpc_client,
+        strategy,
+        &RetryBudget::default(),
+    )
+}
+
+/// `send_and_confirm_transaction`, also drawing its retries and confirmation
+/// time from `retry_budget`
+pub fn send_and_confirm_transaction_with_budget(
+    transaction: &VersionedTransaction,
+    rpc_client: &RpcClient,
+    strategy: SendStrategy,
+    retry_budget: &RetryBudget,
+) -> eyre::Result<String> {
+    send_and_confirm_signed(transaction, rpc_client, strategy, retry_budget)
+        .map_err(|failure| failure.report)
+}
+
+/// Builds a transaction on the latest blockhash with `build` and sends it like
+/// `send_and_confirm_transaction_with_budget`. If the blockhash expires before
+/// the transaction lands, it is rebuilt on a fresh one and sent again, up to
+/// `MAX_BLOCKHASH_REFRESHES` times.
+pub fn send_and_confirm_rebuildable_transaction(
+    rpc_client: &RpcClient,
+    strategy: SendStrategy,
+    retry_budget: &RetryBudget,
+    build: impl FnMut(Hash) -> eyre::Result<VersionedTransaction>,
+) -> eyre::Result<String> {
+    send_with_fresh_blockhashes(
+        || {
+            rpc_client.get_latest_blockhash().map_err(|e| {
+                ToolError::Network(format!("Failed to get recent blockhash: {}", e)).into()
+            })
+        },
+        build,
+        |transaction| send_and_confirm_signed(transaction, rpc_client, strategy, retry_budget),
+    )
+}
+
+/// Why a signed transaction didn't land
+struct SendFailure {
+    report: eyre::Report,
+    /// Its blockhash expired first, so only a copy signed over a fresh
+    /// blockhash can still land
+    blockhash_expired: bool,
+}
+
+fn send_with_fresh_blockhashes(
+    mut latest_blockhash: impl FnMut() -> eyre::Result<Hash>,
+    mut build: impl FnMut(Hash) -> eyre::Result<VersionedTransaction>,
+    mut send: impl FnMut(&VersionedTransaction) -> Result<String, SendFailure>,
+) -> eyre::Result<String> {
+    let mut refreshes = 0;
+    loop {
+        let transaction = build(latest_blockhash()?)?;
+        match send(&transaction) {
+            Ok(signature) => return Ok(signature),
+            Err(failure) if failure.blockhash_expired && refreshes < MAX_BLOCKHASH_REFRESHES => {
+                refreshes += 1;
+                Output::plain(
+                    &format!(
+                        "Blockhash expired before the transaction landed, rebuilding it on a \
+                         fresh one ({}/{})",
+                        refreshes, MAX_BLOCKHASH_REFRESHES
+                    )
+                    .bright_yellow()
+                    .to_string(),
+                );
+            }
+            Err(failure) => return Err(failure.report),
+        }
+    }
+}
+
+/// Sends a signed transaction and waits for it to confirm, recording the
+/// outcome in the audit log
+fn send_and_confirm_signed(
+    transaction: &VersionedTransaction,
+    rpc_client: &RpcClient,
+    strategy: SendStrategy,
+    retry_budget: &RetryBudget,
+) -> Result<String, SendFailure> {
+    let result = try_send_and_confirm_signed(transaction, rpc_client, strategy, retry_budget);
+    let outcome = match &result {
+        Ok(signature) => Ok(signature.as_str()),
+        Err(failure) => Err(failure.report.to_string()),
+    };
+    crate::audit::record(transaction, &rpc_client.url(), outcome);
+    result
+}
+
+fn try_send_and_confirm_signed(
+    transaction: &VersionedTransaction,
+    rpc_client: &RpcClient,
+    strategy: SendStrategy,
+    retry_budget: &RetryBudget,
+) -> Result<String, SendFailure> {
     const MAX_RETRIES: usize = MAX_TX_RETRIES;
     const BASE_DELAY_MS: u64 = BASE_RETRY_DELAY_MS;
     const MAX_TOTAL_RETRY_TIME_MS: u64 = 10_000; // 10 seconds total
 
     let mut last_error: Option<eyre::Report> = None;
+    // Whether the last failure looked transient (RPC/network) rather than a rejected transaction
+    let mut last_error_retryable = false;
+    // Program logs from the last failed preflight simulation, if that's how it failed
+    let mut last_simulation_logs: Option<Vec<String>> = None;
+    let mut blockhash_expired = false;
+    let mut rate_limited = false;
     let retry_start = std::time::Instant::now();
+    let mut attempts = 0;
 
     for attempt in 0..MAX_RETRIES {
         // Check if we've exceeded our total retry time budget
         if retry_start.elapsed().as_millis() as u64 >= MAX_TOTAL_RETRY_TIME_MS {
-            println!(
+            Output::plain(&format!(
                 "Exceeded maximum retry time of {}ms",
                 MAX_TOTAL_RETRY_TIME_MS
-            );
+            ));
             break;
         }
 
         if attempt > 0 {
-            let delay = BASE_DELAY_MS * (2_u64.pow(attempt as u32 - 1));
+            if !retry_budget.try_spend() {
+                Output::plain("Retry budget exhausted, giving up on this transaction");
+                break;
+            }
+
+            let delay = retry_delay_ms(attempt, BASE_DELAY_MS, rate_limited);
             // Ensure we don't exceed our total time budget with this delay
             let remaining_time =
                 MAX_TOTAL_RETRY_TIME_MS.saturating_sub(retry_start.elapsed().as_millis() as u64);
-            let actual_delay = std::cmp::min(delay, remaining_time);
+            let actual_delay = delay
+                .min(remaining_time)
+                .min(retry_budget.remaining_time().as_millis() as u64);
 
             if actual_delay > 0 {
-                println!(
-                    "Retrying transaction in {}ms... (attempt {}/{}, {}ms elapsed)",
+                Output::plain(&format!(
+                    "Retrying transaction in {}ms... (attempt {}/{}, {}ms elapsed, budget: {})",
                     actual_delay,
                     attempt + 1,
                     MAX_RETRIES,
-                    retry_start.elapsed().as_millis()
-                );
+                    retry_start.elapsed().as_millis(),
+                    retry_budget
+                ));
                 std::thread::sleep(Duration::from_millis(actual_delay));
             } else {
-                println!(
+                Output::plain(&format!(
                     "No time remaining for delay, proceeding with retry attempt {}/{}",
                     attempt + 1,
                     MAX_RETRIES
-                );
+                ));
             }
         }
 
+        attempts = attempt + 1;
+
         // First try to send the transaction
+        crate::rate_limit::acquire();
         let signature = match rpc_client.send_transaction_with_config(
             transaction,
             RpcSendTransactionConfig {
@@ -111,146 +497,444 @@ pub fn send_and_confirm_transaction(
                     ClientErrorKind::Reqwest(_) => true, // HTTP client issues
                     _ => false,
                 };
-
-                if let ClientErrorKind::RpcError(RpcError::RpcResponseError {
-                    data:
-                        RpcResponseErrorData::SendTransactionPreflightFailure(
-                            RpcSimulateTransactionResult {
-                                logs: Some(logs), ..
-                            },
-                        ),
-                    ..
-                }) = &err.kind
-                {
-                    println!("Simulation logs:\n\n{}\n", logs.join("\n").bright_yellow());
-                }
+                rate_limited = is_rate_limited(&err);
+
+                last_simulation_logs = match &err.kind {
+                    ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                        data:
+                            RpcResponseErrorData::SendTransactionPreflightFailure(
+                                RpcSimulateTransactionResult {
+                                    logs: Some(logs), ..
+                                },
+                            ),
+                        ..
+                    }) => Some(logs.clone()),
+                    _ => None,
+                };
 
                 last_error = Some(eyre::eyre!("{}", err));
+                last_error_retryable = is_retryable;
 
                 // Don't retry on the last attempt or if error is not retryable
                 if attempt == MAX_RETRIES - 1 || !is_retryable {
                     break;
                 }
 
-                println!(
-                    "Retryable error occurred: {}",
-                    last_error.as_ref().unwrap().to_string().bright_yellow()
-                );
+                if rate_limited {
+                    Output::plain(&format!(
+                        "Rate limited by the RPC, backing off for at least {}ms",
+                        RATE_LIMIT_RETRY_DELAY_MS
+                    ));
+                } else {
+                    Output::plain(&format!(
+                        "Retryable error occurred: {}",
+                        last_error.as_ref().unwrap().to_string().bright_yellow()
+                    ));
+                }
                 continue;
             }
         };
 
-        // Now wait for confirmation with exponential backoff polling
-        let confirmation_start = std::time::Instant::now();
-        let mut confirmation_poll_delay = CONFIRMATION_POLL_INTERVAL_MS;
+        // A sent transaction is only ever waited on from here: re-sending it from
+        // scratch can't help, since the same signed bytes share its blockhash
+        last_simulation_logs = None;
+        match wait_for_confirmation(transaction, &signature, rpc_client, strategy) {
+            Confirmation::Confirmed => return Ok(signature.to_string()),
+            // It executed, so sending it again would fail the same way or, worse,
+            // apply twice; report the on-chain error instead of retrying
+            Confirmation::FailedOnChain(error) => {
+                return Err(SendFailure {
+                    report: failed_on_chain(&signature, &error),
+                    blockhash_expired: false,
+                });
+            }
+            Confirmation::Expired => {
+                last_error = Some(eyre!(
+                    "Transaction {} was dropped: its blockhash expired before it landed",
+                    signature
+                ));
+                last_error_retryable = true;
+                blockhash_expired = true;
+            }
+            Confirmation::Abandoned(reason) => {
+                last_error = Some(eyre!("Transaction {} unconfirmed: {}", signature, reason));
+                last_error_retryable = true;
+            }
+        }
+        break;
+    }
 
-        loop {
-            if confirmation_start.elapsed().as_millis() as u64 > CONFIRMATION_TIMEOUT_MS {
-                println!(
-                    "Transaction confirmation timeout after {}ms",
-                    CONFIRMATION_TIMEOUT_MS
-                );
-                break; // Will retry sending
+    let message = format!(
+        "Transaction failed after {} attempts: {}",
+        attempts,
+        last_error
+            .map(|e| e.to_string())
+            .unwrap_or_else(|| "Unknown error".to_string())
+    );
+    let report = if let Some(logs) = last_simulation_logs {
+        ToolError::SimulationFailed { message, logs }.into()
+    } else if last_error_retryable {
+        ToolError::Network(message).into()
+    } else {
+        eyre!(message)
+    };
+    Err(SendFailure {
+        report,
+        blockhash_expired,
+    })
+}
+
+/// Sends transactions that must land in order, such as consecutive Squads
+/// transaction indices, without waiting out each one's confirmation: each is
+/// sent once the one before it is processed, then all are confirmed. Returns
+/// the signatures in order.
+///
+/// Each sent transaction is audited once, with its outcome at `confirmed`
+/// commitment; if one fails to send, the ones before it are still confirmed
+/// and audited before the first error is returned.
+pub fn send_and_confirm_transactions_in_order(
+    transactions: &[VersionedTransaction],
+    rpc_url: &str,
+    strategy: SendStrategy,
+    retry_budget: &RetryBudget,
+) -> eyre::Result<Vec<String>> {
+    // Preflight and status checks at `processed` see the previous transaction's effects
+    let processed_client =
+        RpcClient::new_with_commitment(rpc_url.to_string(), CommitmentConfig::processed());
+    let mut sent = 0;
+    let mut send_error = None;
+    for transaction in transactions {
+        if let Err(failure) =
+            try_send_and_confirm_signed(transaction, &processed_client, strategy, retry_budget)
+        {
+            crate::audit::record(transaction, rpc_url, Err(failure.report.to_string()));
+            send_error = Some(failure.report);
+            break;
+        }
+        sent += 1;
+    }
+
+    let rpc_client = create_rpc_client(rpc_url);
+    let mut signatures = Vec::with_capacity(sent);
+    let mut confirm_error = None;
+    for transaction in &transactions[..sent] {
+        let signature = &transaction.signatures[0];
+        let result = match wait_for_confirmation(transaction, signature, &rpc_client, strategy) {
+            Confirmation::Confirmed => Ok(signature.to_string()),
+            Confirmation::FailedOnChain(error) => Err(failed_on_chain(signature, &error)),
+            Confirmation::Expired => Err(ToolError::Network(format!(
+                "Transaction {} was processed but dropped before it was confirmed",
+                signature
+            ))
+            .into()),
+            Confirmation::Abandoned(reason) => Err(ToolError::Network(format!(
+                "Transaction {} unconfirmed: {}",
+                signature, reason
+            ))
+            .into()),
+        };
+        let outcome = match &result {
+            Ok(signature) => Ok(signature.as_str()),
+            Err(e) => Err(e.to_string()),
+        };
+        crate::audit::record(transaction, rpc_url, outcome);
+        match result {
+            Ok(signature) => signatures.push(signature),
+            Err(e) => {
+                confirm_error.get_or_insert(e);
             }
+        }
+    }
 
-            match rpc_client.get_signature_status(&signature) {
-                Ok(Some(Ok(()))) => {
-                    return Ok(signature.to_string());
-                }
-                Ok(Some(Err(_))) => {
-                    // Transaction failed
-                    println!("Transaction failed confirmation");
-                    break;
-                }
-                Ok(None) => {
-                    // Transaction not yet confirmed, continue polling
-                }
-                Err(confirmation_err) => {
-                    // Check if confirmation error is retryable
-                    match &confirmation_err.kind {
-                        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => {
-                            if *code == -32004 || *code == -32005 || *code == -32603 {
-                                // Temporary RPC issue, continue polling
-                                println!(
-                                    "Temporary confirmation error: {}",
-                                    confirmation_err.to_string().bright_yellow()
-                                );
-                            } else {
-                                // Non-retryable confirmation error, break and retry transaction
-                                println!(
-                                    "Non-retryable confirmation error: {}",
-                                    confirmation_err.to_string().bright_red()
-                                );
-                                break;
-                            }
-                        }
-                        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) => {
-                            // Network issues, continue polling
-                            println!(
-                                "Network error during confirmation: {}",
-                                confirmation_err.to_string().bright_yellow()
-                            );
-                        }
-                        _ => {
-                            // Unknown error, break and retry transaction
-                            println!(
-                                "Unknown confirmation error: {}",
-                                confirmation_err.to_string().bright_red()
-                            );
-                            break;
-                        }
-                    }
+    match confirm_error.or(send_error) {
+        Some(e) => Err(e),
+        None => Ok(signatures),
+    }
+}
+
+/// The error for a transaction that landed but failed executing. Never
+/// retryable: the same transaction fails deterministically.
+fn failed_on_chain(signature: &Signature, error: &TransactionError) -> eyre::Report {
+    eyre!("Transaction {} executed and failed on chain: {}", signature, error)
+}
+
+/// How waiting on a sent transaction's signature ended
+enum Confirmation {
+    Confirmed,
+    /// It landed, and its execution failed with this error
+    FailedOnChain(TransactionError),
+    /// The blockhash expired without the transaction landing, so it never will
+    Expired,
+    /// Stopped polling before the outcome was known
+    Abandoned(String),
+}
+
+/// Polls a sent transaction's signature until it confirms, fails on chain, or
+/// its blockhash expires. With `SendStrategy::Spam` the same signed bytes are
+/// re-broadcast while waiting, which can't cause a duplicate. The retry
+/// budget doesn't cut this short: the transaction is already sent, and its
+/// blockhash expiring bounds the wait anyway.
+fn wait_for_confirmation(
+    transaction: &VersionedTransaction,
+    signature: &solana_signature::Signature,
+    rpc_client: &RpcClient,
+    strategy: SendStrategy,
+) -> Confirmation {
+    let confirmation_start = std::time::Instant::now();
+    let mut confirmation_poll_delay = CONFIRMATION_POLL_INTERVAL_MS;
+    let mut last_send = std::time::Instant::now();
+    let mut consecutive_errors = 0;
+
+    loop {
+        crate::rate_limit::acquire();
+        match rpc_client.get_signature_status(signature) {
+            Ok(Some(Ok(()))) => return Confirmation::Confirmed,
+            Ok(Some(Err(error))) => return Confirmation::FailedOnChain(error),
+            Ok(None) => consecutive_errors = 0,
+            Err(err) => {
+                consecutive_errors += 1;
+                if consecutive_errors >= MAX_TX_RETRIES {
+                    return Confirmation::Abandoned(format!(
+                        "could not check its status: {}",
+                        err
+                    ));
                 }
+                Output::plain(&format!(
+                    "Error checking confirmation: {}",
+                    err.to_string().bright_yellow()
+                ));
+            }
+        }
+
+        // A blockhash lives for about a minute, so only start checking it after
+        // the confirmation timeout, or at each re-send when spamming
+        let resend_due = strategy == SendStrategy::Spam
+            && last_send.elapsed().as_millis() as u64 >= SPAM_RESEND_INTERVAL_MS;
+        if resend_due
+            || confirmation_start.elapsed().as_millis() as u64 >= CONFIRMATION_TIMEOUT_MS
+        {
+            crate::rate_limit::acquire();
+            let blockhash_valid = rpc_client
+                .is_blockhash_valid(transaction.message.recent_blockhash(), rpc_client.commitment())
+                .unwrap_or(true);
+            if !blockhash_valid {
+                // It may have landed between the last poll and the expiry check
+                crate::rate_limit::acquire();
+                return match rpc_client.get_signature_status(signature) {
+                    Ok(Some(Ok(()))) => Confirmation::Confirmed,
+                    Ok(Some(Err(error))) => Confirmation::FailedOnChain(error),
+                    _ => Confirmation::Expired,
+                };
             }
+        }
+
+        if resend_due {
+            crate::rate_limit::acquire();
+            let _ = rpc_client.send_transaction_with_config(
+                transaction,
+                RpcSendTransactionConfig {
+                    skip_preflight: true,
+                    max_retries: Some(0),
+                    ..RpcSendTransactionConfig::default()
+                },
+            );
+            last_send = std::time::Instant::now();
+        }
+
+        // Back off between checks (capped at 5 seconds, or at the re-send interval when spamming)
+        let max_poll_delay = match strategy {
+            SendStrategy::Once => 5000,
+            SendStrategy::Spam => SPAM_RESEND_INTERVAL_MS,
+        };
+        std::thread::sleep(Duration::from_millis(confirmation_poll_delay));
+        confirmation_poll_delay = std::cmp::min(confirmation_poll_delay * 2, max_poll_delay);
+    }
+}
+
+/// Compute budget instructions in the order every transaction here uses:
+/// the unit limit, then the unit price
+fn compute_budget_instructions(
+    compute_unit_limit: Option<u32>,
+    microlamports_per_unit: Option<u64>,
+) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    if let Some(units) = compute_unit_limit {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+    }
+    if let Some(microlamports) = microlamports_per_unit {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(microlamports));
+    }
+    instructions
+}
+
+/// Computes the SHA-256 of a program's executable bytes, matching the hash
+/// reported by `solana-verify get-program-hash`.
+///
+/// For upgradeable programs the bytes live in the program data account after
+/// its metadata header; trailing zero padding is excluded from the hash.
+pub fn get_program_hash(rpc_client: &RpcClient, program_id: &Pubkey) -> eyre::Result<String> {
+    let program_account = rpc_client
+        .get_account(program_id)
+        .map_err(|e| eyre!("Failed to fetch program account {}: {}", program_id, e))?;
+
+    if !program_account.executable {
+        return Err(eyre!("Account {} is not an executable program", program_id));
+    }
+
+    let program_bytes = if program_account.owner == BPF_LOADER_UPGRADEABLE_ID {
+        // UpgradeableLoaderState::Program { programdata_address }: u32 tag + pubkey
+        let programdata_address = program_account
+            .data
+            .get(4..36)
+            .and_then(|bytes| Pubkey::try_from(bytes).ok())
+            .ok_or_else(|| eyre!("Malformed upgradeable program account {}", program_id))?;
+        let programdata = get_account_data_with_retry(rpc_client, &programdata_address)?;
+        programdata
+            .get(PROGRAM_DATA_METADATA_SIZE..)
+            .unwrap_or_default()
+            .to_vec()
+    } else {
+        program_account.data
+    };
+
+    let end = program_bytes
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |i| i + 1);
+    let digest = Sha256::digest(&program_bytes[..end]);
+
+    Ok(digest.iter().map(|b| format!("{:02x}", b)).collect())
+}
 
-            // Wait before next confirmation check with exponential backoff (capped at 5 seconds)
-            std::thread::sleep(Duration::from_millis(confirmation_poll_delay));
-            confirmation_poll_delay = std::cmp::min(confirmation_poll_delay * 2, 5000);
+/// Aborts unless the deployed program matches `expected_hash`. Without an
+/// expected hash, a non-canonical program id can't be verified: it is refused
+/// unless `rpc_url` is a local validator or `allow_unverified`, and then only
+/// warned about.
+pub fn verify_program(
+    rpc_url: &str,
+    program_id: &Pubkey,
+    expected_hash: Option<&str>,
+    allow_unverified: bool,
+) -> eyre::Result<()> {
+    let Some(expected_hash) = expected_hash else {
+        if *program_id == SQUADS_MULTISIG_PROGRAM_ID {
+            return Ok(());
         }
+        if NetworkCluster::from_url(rpc_url) != NetworkCluster::Localnet && !allow_unverified {
+            return Err(ToolError::Config(format!(
+                "Non-canonical Squads program {} on {} needs --expected-program-hash, \
+                 or --allow-unverified-program to use it unverified",
+                program_id, rpc_url
+            ))
+            .into());
+        }
+        Output::warning(&format!(
+            "Using non-canonical Squads program {} without --expected-program-hash; \
+             its code is not verified",
+            program_id
+        ));
+        return Ok(());
+    };
 
-        // If we reach here, confirmation failed or timed out
-        last_error = Some(eyre!(
-            "Transaction sent but confirmation failed or timed out"
+    let rpc_client = create_rpc_client(rpc_url);
+    let actual_hash = get_program_hash(&rpc_client, program_id)?;
+
+    if !actual_hash.eq_ignore_ascii_case(expected_hash.trim()) {
+        Output::plain(&format!(
+            "{} Program hash mismatch for {}\n  expected: {}\n  actual:   {}",
+            "❌".bright_red(),
+            program_id,
+            expected_hash.trim().bright_yellow(),
+            actual_hash.bright_red()
+        ));
+        return Err(eyre!(
+            "Program {} does not match the expected hash on {}",
+            program_id,
+            rpc_url
         ));
     }
 
-    Err(eyre!(
-        "Transaction failed after {} attempts: {}",
-        MAX_RETRIES,
-        last_error
-            .map(|e| e.to_string())
-            .unwrap_or_else(|| "Unknown error".to_string())
-    ))
+    Output::plain(&format!(
+        "{} Program hash verified: {}",
+        "✓".bright_green(),
+        actual_hash.bright_white()
+    ));
+    Ok(())
+}
+
+/// Reads the Squads program config and returns the treasury that receives the
+/// multisig creation fee
+pub fn fetch_program_treasury(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    retry_budget: &RetryBudget,
+) -> eyre::Result<Pubkey> {
+    let program_config_pda = get_program_config_pda(Some(program_id)).0;
+    let account =
+        retry_account_fetch(rpc_client, &program_config_pda, RpcClient::get_account, retry_budget)
+            .map_err(|e| eyre!("Failed to fetch program config account: {}", e))?;
+
+    let program_config = parse_program_config(&account.owner, &account.data, program_id)?;
+    Ok(program_config.treasury)
+}
+
+/// Checks the account at the program config PDA really is `program_id`'s
+/// `ProgramConfig` before trusting its treasury
+fn parse_program_config(
+    owner: &Pubkey,
+    data: &[u8],
+    program_id: &Pubkey,
+) -> eyre::Result<ProgramConfig> {
+    if owner != program_id || !data.starts_with(PROGRAM_CONFIG_DISCRIMINATOR) {
+        return Err(eyre!(
+            "Program config not initialized for program {} on this network",
+            program_id
+        ));
+    }
+
+    ProgramConfig::deserialize(&mut &data[8..])
+        .map_err(|e| eyre!("Failed to deserialize program config: {}", e))
 }
 
 pub fn get_account_data_with_retry(
     rpc_client: &RpcClient,
     pubkey: &Pubkey,
 ) -> eyre::Result<Vec<u8>> {
+    retry_account_fetch(rpc_client, pubkey, RpcClient::get_account_data, &RetryBudget::default())
+}
+
+/// Runs an account fetch with exponential backoff, retrying only errors
+/// that may clear up (see `is_retryable_account_error`)
+fn retry_account_fetch<T>(
+    rpc_client: &RpcClient,
+    pubkey: &Pubkey,
+    fetch: fn(&RpcClient, &Pubkey) -> Result<T, ClientError>,
+    retry_budget: &RetryBudget,
+) -> eyre::Result<T> {
     const MAX_RETRIES: usize = MAX_ACCOUNT_RETRIES;
     const BASE_DELAY_MS: u64 = BASE_ACCOUNT_RETRY_DELAY_MS;
 
-    let mut last_error = None;
+    let mut last_error: Option<ClientError> = None;
+    let mut last_error_retryable = false;
 
     for attempt in 0..MAX_RETRIES {
         if attempt > 0 {
-            let delay = BASE_DELAY_MS * (2_u64.pow(attempt as u32 - 1));
-            std::thread::sleep(Duration::from_millis(delay));
+            if !retry_budget.try_spend() {
+                break;
+            }
+            let rate_limited = last_error.as_ref().is_some_and(is_rate_limited);
+            let delay = retry_delay_ms(attempt, BASE_DELAY_MS, rate_limited);
+            std::thread::sleep(Duration::from_millis(delay).min(retry_budget.remaining_time()));
         }
 
-        match rpc_client.get_account_data(pubkey) {
-            Ok(data) => return Ok(data),
+        crate::rate_limit::acquire();
+        match fetch(rpc_client, pubkey) {
+            Ok(value) => return Ok(value),
             Err(err) => {
-                let is_retryable = match &err.kind {
-                    ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => {
-                        *code == -32005 || *code == -32004 || *code == -32603
-                    }
-                    ClientErrorKind::Io(_) => true,
-                    ClientErrorKind::Reqwest(_) => true,
-                    _ => false,
-                };
+                let is_retryable = is_retryable_account_error(&err);
 
                 last_error = Some(err);
+                last_error_retryable = is_retryable;
 
                 if attempt == MAX_RETRIES - 1 || !is_retryable {
                     break;
@@ -259,192 +943,618 @@ pub fn get_account_data_with_retry(
         }
     }
 
-    Err(eyre!(
+    Err(account_data_error(last_error.unwrap(), last_error_retryable))
+}
+
+/// Bytes of a multisig account fetched first: everything up to and including
+/// the members of a multisig with up to 16 of them. The account itself is
+/// usually far bigger, padded with pre-allocated member slots.
+const MULTISIG_SLICE_LEN: usize = 8 + 32 + 32 + 2 + 4 + 8 + 8 + 33 + 1 + 4 + 16 * (32 + 1);
+
+/// Fetches a multisig account without its trailing member slot padding, for
+/// commands that read it on many networks at once. A first fixed slice holds
+/// most multisigs; a bigger one is read again at exactly the length its
+/// member count calls for.
+pub async fn fetch_multisig_data_async(
+    rpc_client: &nonblocking::rpc_client::RpcClient,
+    multisig_address: &Pubkey,
+) -> eyre::Result<Vec<u8>> {
+    let data =
+        get_account_data_slice_with_retry_async(rpc_client, multisig_address, MULTISIG_SLICE_LEN)
+            .await?;
+    match Multisig::truncated_len(&data) {
+        // A shorter slice than asked for is the whole account, cut short by
+        // the RPC; fetching it again at a length it already didn't serve
+        // won't help, so it is left to `ensure_multisig_not_truncated`
+        Some(required) if data.len() == MULTISIG_SLICE_LEN => {
+            get_account_data_slice_with_retry_async(rpc_client, multisig_address, required).await
+        }
+        _ => Ok(data),
+    }
+}
+
+/// The first `length` bytes of `pubkey`'s data (fewer if the account is
+/// smaller), retried like `get_account_data_with_retry` without blocking the
+/// runtime
+async fn get_account_data_slice_with_retry_async(
+    rpc_client: &nonblocking::rpc_client::RpcClient,
+    pubkey: &Pubkey,
+    length: usize,
+) -> eyre::Result<Vec<u8>> {
+    let config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        data_slice: Some(UiDataSliceConfig { offset: 0, length }),
+        commitment: Some(rpc_client.commitment()),
+        min_context_slot: None,
+    };
+    let mut last_error = None;
+    let mut last_error_retryable = false;
+
+    for attempt in 0..MAX_ACCOUNT_RETRIES {
+        if attempt > 0 {
+            let rate_limited = last_error.as_ref().is_some_and(is_rate_limited);
+            let delay = retry_delay_ms(attempt, BASE_ACCOUNT_RETRY_DELAY_MS, rate_limited);
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+        }
+
+        crate::rate_limit::acquire_async().await;
+        match rpc_client.get_account_with_config(pubkey, config.clone()).await {
+            Ok(response) => {
+                return response
+                    .value
+                    .map(|account| account.data)
+                    .ok_or_else(|| eyre!("AccountNotFound: pubkey={}", pubkey));
+            }
+            Err(err) => {
+                last_error_retryable = is_retryable_account_error(&err);
+                last_error = Some(err);
+                if !last_error_retryable {
+                    break;
+                }
+            }
+        }
+    }
+
+    Err(account_data_error(last_error.unwrap(), last_error_retryable))
+}
+
+/// Rate limits, node-behind and transport errors are worth retrying;
+/// anything else (notably AccountNotFound) won't change on retry
+fn is_retryable_account_error(err: &ClientError) -> bool {
+    match &err.kind {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => {
+            *code == -32005 || *code == -32004 || *code == -32603
+        }
+        ClientErrorKind::Io(_) => true,
+        ClientErrorKind::Reqwest(_) => true,
+        _ => false,
+    }
+}
+
+/// Whether `err` is an HTTP 429 from a rate-limiting endpoint. The RPC
+/// client has already slept out the response's `Retry-After` a few times
+/// before surfacing one, and doesn't pass the header on, so all that is left
+/// to honor here is the fact the endpoint wants us to slow down.
+fn is_rate_limited(err: &ClientError) -> bool {
+    match &err.kind {
+        ClientErrorKind::Reqwest(e) => e.status().is_some_and(|status| status.as_u16() == 429),
+        _ => false,
+    }
+}
+
+/// Delay before retry `attempt` (1-based): exponential from `base_delay_ms`,
+/// but never shorter than `RATE_LIMIT_RETRY_DELAY_MS` after a rate limit
+fn retry_delay_ms(attempt: usize, base_delay_ms: u64, rate_limited: bool) -> u64 {
+    let delay = base_delay_ms * 2_u64.pow(attempt as u32 - 1);
+    if rate_limited {
+        delay.max(RATE_LIMIT_RETRY_DELAY_MS)
+    } else {
+        delay
+    }
+}
+
+fn account_data_error(err: ClientError, retryable: bool) -> eyre::Report {
+    let message = format!(
         "Failed to get account data after {} attempts: {}",
-        MAX_RETRIES,
-        last_error.unwrap().to_string()
-    ))
+        MAX_ACCOUNT_RETRIES, err
+    );
+    if retryable {
+        ToolError::Network(message).into()
+    } else {
+        eyre!(message)
+    }
+}
+
+/// Fetches `pubkeys` with `getMultipleAccounts`, `chunk_size` keys per
+/// request (capped at the RPC limit of 100). Results line up with `pubkeys`,
+/// with `None` for accounts that don't exist.
+pub fn fetch_accounts_batched(
+    rpc_client: &RpcClient,
+    pubkeys: &[Pubkey],
+    chunk_size: usize,
+) -> eyre::Result<Vec<Option<Account>>> {
+    fetch_in_chunks(pubkeys, chunk_size, |chunk| {
+        crate::rate_limit::acquire();
+        rpc_client
+            .get_multiple_accounts(chunk)
+            .map_err(|e| ToolError::Network(format!("Failed to fetch accounts: {}", e)).into())
+    })
+}
+
+/// Which of its two accounts exist at one transaction index of a multisig
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexAccounts {
+    /// Transaction and proposal
+    Complete,
+    /// The proposal was never created, e.g. its creation failed after the
+    /// transaction's
+    TransactionOnly,
+    ProposalOnly,
+    /// Neither; both accounts were closed and their rent reclaimed
+    Missing,
+}
+
+impl IndexAccounts {
+    pub fn from_presence(has_transaction: bool, has_proposal: bool) -> Self {
+        match (has_transaction, has_proposal) {
+            (true, true) => IndexAccounts::Complete,
+            (true, false) => IndexAccounts::TransactionOnly,
+            (false, true) => IndexAccounts::ProposalOnly,
+            (false, false) => IndexAccounts::Missing,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            IndexAccounts::Complete => "transaction + proposal",
+            IndexAccounts::TransactionOnly => "transaction only",
+            IndexAccounts::ProposalOnly => "proposal only",
+            IndexAccounts::Missing => "missing",
+        }
+    }
+}
+
+/// Runs of consecutive indices that aren't `Complete`, each with the state
+/// all its indices share, in index order
+pub fn index_gaps(
+    states: &[(u64, IndexAccounts)],
+) -> Vec<(std::ops::RangeInclusive<u64>, IndexAccounts)> {
+    let mut gaps: Vec<(std::ops::RangeInclusive<u64>, IndexAccounts)> = Vec::new();
+    for &(index, state) in states {
+        if state == IndexAccounts::Complete {
+            continue;
+        }
+        match gaps.last_mut() {
+            Some((range, last_state)) if *last_state == state && *range.end() + 1 == index => {
+                *range = *range.start()..=index;
+            }
+            _ => gaps.push((index..=index, state)),
+        }
+    }
+    gaps
+}
+
+/// `fetch_accounts_batched` over any `fetch` of one chunk of keys
+fn fetch_in_chunks<T>(
+    pubkeys: &[Pubkey],
+    chunk_size: usize,
+    mut fetch: impl FnMut(&[Pubkey]) -> eyre::Result<Vec<Option<T>>>,
+) -> eyre::Result<Vec<Option<T>>> {
+    let chunk_size = chunk_size.clamp(1, MAX_ACCOUNTS_PER_REQUEST);
+    let mut accounts = Vec::with_capacity(pubkeys.len());
+    for chunk in pubkeys.chunks(chunk_size) {
+        let fetched = fetch(chunk)?;
+        if fetched.len() != chunk.len() {
+            return Err(eyre!(
+                "RPC returned {} accounts for {} requested",
+                fetched.len(),
+                chunk.len()
+            ));
+        }
+        accounts.extend(fetched);
+    }
+    Ok(accounts)
+}
+
+pub fn fetch_multisig(rpc_client: &RpcClient, multisig_address: &Pubkey) -> eyre::Result<Multisig> {
+    let mut data = get_account_data_with_retry(rpc_client, multisig_address)?;
+    // RPCs with response size limits can cut off multisigs with many members;
+    // a refetch may be served by a node that doesn't
+    for attempt in 1..MAX_ACCOUNT_RETRIES {
+        if Multisig::truncated_len(&data).is_none() {
+            break;
+        }
+        let delay = BASE_ACCOUNT_RETRY_DELAY_MS * (2_u64.pow(attempt as u32 - 1));
+        std::thread::sleep(Duration::from_millis(delay));
+        data = get_account_data_with_retry(rpc_client, multisig_address)?;
+    }
+    ensure_multisig_not_truncated(multisig_address, &data)?;
+    if data.len() < 8 {
+        return Err(eyre!("Account data too small to be a valid multisig"));
+    }
+    // Pre-allocated member slots leave trailing padding, so don't require all bytes be read
+    Multisig::deserialize(&mut &data[8..])
+        .map_err(|e| eyre!("Failed to deserialize multisig: {}", e))
+}
+
+/// Checks a freshly created multisig holds exactly the members and threshold
+/// it was created with, listing every difference. Member order is ignored.
+pub fn verify_created_multisig(
+    multisig: &Multisig,
+    requested: &MultisigCreateArgsV2,
+) -> eyre::Result<()> {
+    let mut mismatches = Vec::new();
+    if multisig.threshold != requested.threshold {
+        mismatches.push(format!(
+            "threshold is {}, requested {}",
+            multisig.threshold, requested.threshold
+        ));
+    }
+    for member in &requested.members {
+        match multisig.members.iter().find(|m| m.key == member.key) {
+            None => mismatches.push(format!("member {} is missing", member.key)),
+            Some(found) if found.permissions != member.permissions => mismatches.push(format!(
+                "member {} has permission mask {}, requested {}",
+                member.key, found.permissions.mask, member.permissions.mask
+            )),
+            Some(_) => {}
+        }
+    }
+    for member in &multisig.members {
+        if !requested.members.iter().any(|m| m.key == member.key) {
+            mismatches.push(format!("member {} was not requested", member.key));
+        }
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(eyre!(
+            "Created multisig doesn't match the request: {}",
+            mismatches.join("; ")
+        ))
+    }
 }
+
+/// Reports a multisig account cut short by the RPC as such, rather than as
+/// the deserialization error it would otherwise cause
+pub fn ensure_multisig_not_truncated(
+    multisig_address: &Pubkey,
+    account_data: &[u8],
+) -> eyre::Result<()> {
+    match Multisig::truncated_len(account_data) {
+        None => Ok(()),
+        Some(required) => Err(ToolError::Network(format!(
+            "Multisig account {} is truncated: the RPC returned {} of at least {} bytes. It \
+             may limit response sizes; try another RPC URL",
+            multisig_address,
+            account_data.len(),
+            required
+        ))
+        .into()),
+    }
+}
+
+/// The epoch `slot` falls in, per the cluster's epoch schedule; `None` when
+/// the schedule can't be fetched
+pub fn fetch_epoch_of_slot(rpc_client: &RpcClient, slot: u64) -> Option<u64> {
+    rpc_client
+        .get_epoch_schedule()
+        .ok()
+        .map(|schedule| schedule.get_epoch(slot))
+}
+
+/// Fetches a feature account and reads where it stands for revocation
+pub fn fetch_feature_state(
+    rpc_client: &RpcClient,
+    feature_id: &Pubkey,
+) -> eyre::Result<FeatureState> {
+    crate::rate_limit::acquire();
+    let account = rpc_client
+        .get_account_with_commitment(feature_id, rpc_client.commitment())
+        .map_err(|e| eyre!("Failed to fetch feature account {}: {}", feature_id, e))?
+        .value;
+    FeatureState::from_account(account.as_ref().map(|a| (&a.owner, a.data.as_slice())))
+        .wrap_err_with(|| format!("Failed to read feature account {}", feature_id))
+}
+
+/// Checks the account funding `multisig`'s feature activation can cover the
+/// feature account's rent-exempt minimum on this cluster; otherwise the
+/// activation fails when executed. `topped_up_to` is a balance the funder is
+/// about to be topped up to (`--prefund-vault`). A shortfall is a warning,
+/// as the funder can still be topped up before the vote, or an error with
+/// `tx_options.require_funded_feature`.
+pub fn ensure_feature_funding(
+    rpc_client: &RpcClient,
+    multisig: &Pubkey,
+    tx_options: &TransactionOptions,
+    topped_up_to: Option<u64>,
+) -> eyre::Result<()> {
+    // The feature account is vault 0, which funds its own activation
+    let funding_address = get_vault_pda(multisig, 0, tx_options.program_id.as_ref()).0;
+    let required = rpc_client
+        .get_minimum_balance_for_rent_exemption(FEATURE_ACCOUNT_SIZE)
+        .map_err(|e| ToolError::Network(format!("Failed to fetch feature account rent: {}", e)))?;
+    let balance = rpc_client
+        .get_balance(&funding_address)
+        .map_err(|e| ToolError::Network(format!("Failed to fetch funding balance: {}", e)))?;
+
+    let Some(shortfall) = funding_shortfall(balance, topped_up_to, required) else {
+        return Ok(());
+    };
+    let message = format!(
+        "Feature activation funder {} is {} lamports short of the {} the feature account \
+         needs to be rent-exempt; the activation will fail until it is funded",
+        funding_address, shortfall, required
+    );
+    if tx_options.require_funded_feature {
+        Err(eyre!("{} (fund it, e.g. with --prefund-vault)", message))
+    } else {
+        Output::warning(&message);
+        Ok(())
+    }
+}
+
+/// Lamports `balance` (after any top-up) falls short of `required`, if any
+fn funding_shortfall(balance: u64, topped_up_to: Option<u64>, required: u64) -> Option<u64> {
+    let balance = balance.max(topped_up_to.unwrap_or(0));
+    (balance < required).then(|| required - balance)
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn create_multisig(
     rpc_url: String,
     program_id: Option<String>,
     fee_payer_keypair: &dyn Signer,
     create_key: &Keypair,
-    members: Vec<Member>,
-    threshold: u16,
-    priority_fee_lamports: Option<u64>,
+    args: MultisigCreateArgsV2,
+    tx_options: &TransactionOptions,
+    retry_budget: &RetryBudget,
+    assume_yes: bool,
 ) -> eyre::Result<(Pubkey, String)> {
-    let program_id = program_id.unwrap_or_else(|| SQUADS_PROGRAM_ID_STR.to_string());
-    let program_id = Pubkey::from_str(&program_id).expect("Invalid program ID");
-    let multisig_address = crate::squads::get_multisig_pda(&create_key.pubkey(), None).0;
-    let vault_address = get_vault_pda(&multisig_address, 0, None).0;
+    let program_id = match program_id {
+        Some(program_id) => parse_pubkey(&program_id)?,
+        None => SQUADS_MULTISIG_PROGRAM_ID,
+    };
+    let multisig_address = get_multisig_pda(&create_key.pubkey(), Some(&program_id)).0;
+    let vault_address = get_vault_pda(&multisig_address, 0, Some(&program_id)).0;
 
     let transaction_creator = fee_payer_keypair.pubkey();
 
-    println!();
-    println!(
-        "{}",
-        "👀 Review Feature Gate Multisig Details"
-            .bright_yellow()
-            .bold()
-    );
-    println!();
-    println!("{}: {}", "Network".cyan(), rpc_url.bright_white());
-    println!(
+    Output::separator();
+    Output::plain(&"👀 Review Feature Gate Multisig Details".bright_yellow().bold().to_string());
+    Output::separator();
+    Output::plain(&format!("{}: {}", "Network".cyan(), rpc_url.bright_white()));
+    Output::plain(&format!(
         "{}: {}",
         "Program ID".cyan(),
         program_id.to_string().bright_white()
-    );
-    println!(
+    ));
+    Output::plain(&format!(
         "{}: {}",
         "Fee Payer".cyan(),
         transaction_creator.to_string().bright_white()
-    );
-    println!();
-    println!("{}", "⚙️ General Info".bright_white().bold());
-    println!();
-    println!(
+    ));
+    if let Some(note) = fee_payer_role_note(&transaction_creator, &args.members) {
+        Output::info(&note);
+    }
+    Output::separator();
+    Output::plain(&"⚙️ General Info".bright_white().bold().to_string());
+    Output::separator();
+    Output::plain(&format!(
         "{}: {}",
         "Feature Gate Multisig".cyan(),
         multisig_address.to_string().bright_white()
-    );
-    println!(
+    ));
+    Output::plain(&format!(
         "{}: {}",
         "Feature Gate ID".cyan(),
         vault_address.to_string().bright_white()
-    );
-    println!();
-    println!("{}", "⚙️ Config Parameters".bright_white().bold());
-    println!();
-    println!(
+    ));
+    Output::separator();
+    Output::plain(&"⚙️ Config Parameters".bright_white().bold().to_string());
+    Output::separator();
+    Output::plain(&format!(
         "{}: {}",
         "Members".cyan(),
-        members.len().to_string().bright_green()
-    );
-    for (i, member) in members.iter().enumerate() {
+        args.members.len().to_string().bright_green()
+    ));
+    for (i, member) in args.members.iter().enumerate() {
         let perms = decode_permissions(member.permissions.mask);
         if perms.len() == 1 && perms[0] == "Initiate" {
-            println!(
+            Output::plain(&format!(
                 "  {} Temporary Setup Keypair: {} ({})",
                 "✓".bright_green(),
                 member.key.to_string().bright_white(),
                 "Initiate".bright_cyan()
-            );
+            ));
         } else {
-            println!(
+            Output::plain(&format!(
                 "  {} Member {}: {} ({})",
                 "✓".bright_green(),
                 i + 1,
                 member.key.to_string().bright_white(),
                 perms.join(", ").bright_cyan()
-            );
+            ));
         }
     }
-    println!("");
-    println!(
+    Output::separator();
+    Output::plain(&format!(
         "{}: {}",
         "Threshold".cyan(),
-        threshold.to_string().bright_green()
-    );
-    println!();
+        args.threshold.to_string().bright_green()
+    ));
+    if args.time_lock > 0 {
+        Output::plain(&format!(
+            "{}: {}s",
+            "Time Lock".cyan(),
+            args.time_lock.to_string().bright_green()
+        ));
+    }
+    if let Some(config_authority) = args.config_authority {
+        Output::plain(&format!(
+            "{}: {}",
+            "Config Authority".cyan(),
+            config_authority.to_string().bright_white()
+        ));
+    }
+    if let Some(rent_collector) = args.rent_collector {
+        Output::plain(&format!(
+            "{}: {}",
+            "Rent Collector".cyan(),
+            rent_collector.to_string().bright_white()
+        ));
+    }
+    Output::separator();
 
-    let proceed = Confirm::new()
-        .with_prompt("Do you want to proceed?")
-        .default(true)
-        .interact()?;
+    let proceed = assume_yes
+        || Confirm::new()
+            .with_prompt("Do you want to proceed?")
+            .default(true)
+            .interact()?;
     if !proceed {
-        println!("{}", "OK, aborting.".bright_red());
-        return Err(eyre!("User aborted"));
+        Output::plain(&"OK, aborting.".bright_red().to_string());
+        return Err(ToolError::Cancelled("User aborted".to_string()).into());
     }
-    println!();
+    Output::separator();
 
     let rpc_client = create_rpc_client(&rpc_url);
 
-    let progress = ProgressBar::new_spinner().with_message("Sending transactions...");
-    progress.enable_steady_tick(Duration::from_millis(100));
-
-    let blockhash = rpc_client
-        .get_latest_blockhash()
-        .expect("Failed to get blockhash");
+    let progress = Output::spinner("Sending transactions...");
 
     let multisig_key = get_multisig_pda(&create_key.pubkey(), Some(&program_id));
 
     let program_config_pda = get_program_config_pda(Some(&program_id));
 
-    let program_config = rpc_client
-        .get_account(&program_config_pda.0)
-        .expect("Failed to fetch program config account");
+    // An override skips the program config entirely, for programs without one
+    let treasury = match tx_options.treasury {
+        Some(treasury) => treasury,
+        None => fetch_program_treasury(&rpc_client, &program_id, retry_budget)?,
+    };
 
-    let program_config_data = program_config.data.as_slice();
+    let priority_fee = tx_options.resolve_priority_fee(
+        &rpc_client,
+        &rpc_url,
+        &[multisig_key.0, transaction_creator, treasury],
+    );
 
-    // Skip the first 8 bytes (discriminator) before deserializing
-    let config_data_without_discriminator = &program_config_data[8..];
+    let mut instructions =
+        compute_budget_instructions(Some(CREATE_MULTISIG_COMPUTE_UNITS), Some(priority_fee));
+    instructions.push(Instruction {
+        accounts: MultisigCreateV2Accounts {
+            create_key: create_key.pubkey(),
+            creator: transaction_creator,
+            multisig: multisig_key.0,
+            system_program: solana_system_interface::program::ID,
+            program_config: program_config_pda.0,
+            treasury,
+        }
+        .to_account_metas(Some(false)),
+        data: MultisigCreateV2Data { args }.data(),
+        program_id,
+    });
+
+    let signature = send_and_confirm_rebuildable_transaction(
+        &rpc_client,
+        tx_options.send_strategy,
+        retry_budget,
+        |blockhash| {
+            let message =
+                Message::try_compile(&transaction_creator, &instructions, &[], blockhash)?;
+            if tx_options.dump_instructions {
+                progress.suspend(|| dump_message_instructions(&message));
+            }
+            if tx_options.validate_accounts {
+                progress.suspend(|| {
+                    check_writable_account_owners(&rpc_client, &message, &program_id)
+                });
+            }
+            VersionedTransaction::try_new(
+                VersionedMessage::V0(message),
+                &unique_signers(&[fee_payer_keypair, create_key as &dyn Signer]),
+            )
+            .map_err(|e| eyre!("Failed to create signed transaction: {}", e))
+        },
+    )?;
 
-    let treasury = borsh::from_slice::<ProgramConfig>(config_data_without_discriminator)
-        .unwrap()
-        .treasury;
+    let network_display = NetworkCluster::from_url(&rpc_url).label();
 
-    let message = Message::try_compile(
-        &transaction_creator,
-        &[
-            ComputeBudgetInstruction::set_compute_unit_limit(CREATE_MULTISIG_COMPUTE_UNITS),
-            ComputeBudgetInstruction::set_compute_unit_price(
-                priority_fee_lamports.unwrap_or(DEFAULT_PRIORITY_FEE),
-            ),
-            Instruction {
-                accounts: MultisigCreateV2Accounts {
-                    create_key: create_key.pubkey(),
-                    creator: transaction_creator,
-                    multisig: multisig_key.0,
-                    system_program: solana_system_interface::program::ID,
-                    program_config: program_config_pda.0,
-                    treasury,
-                }
-                .to_account_metas(Some(false)),
-                data: MultisigCreateV2Data {
-                    args: MultisigCreateArgsV2 {
-                        config_authority: None,
-                        members,
-                        threshold,
-                        time_lock: 0,
-                        memo: None,
-                        rent_collector: None,
-                    },
-                }
-                .data(),
-                program_id,
-            },
-        ],
-        &[],
-        blockhash,
-    )
-    .unwrap();
+    progress.finish_with_message(format!(
+        "Multisig creation confirmed: {} ({})",
+        signature.to_string().bright_green(),
+        network_display
+    ));
+
+    Ok((multisig_key.0, signature))
+}
+
+/// Tops up the default vault to `target_lamports`, transferring only the
+/// difference from the fee payer. Returns the transfer signature, or `None`
+/// when the vault already holds enough.
+pub fn prefund_vault(
+    rpc_url: &str,
+    fee_payer: &dyn Signer,
+    vault_address: &Pubkey,
+    target_lamports: u64,
+    tx_options: &TransactionOptions,
+    retry_budget: &RetryBudget,
+) -> eyre::Result<Option<String>> {
+    let rpc_client = create_rpc_client(rpc_url);
+
+    let current_lamports = rpc_client
+        .get_balance(vault_address)
+        .map_err(|e| eyre!("Failed to fetch vault balance: {}", e))?;
+
+    if current_lamports >= target_lamports {
+        println!(
+            "Vault already holds {} SOL, no pre-funding needed",
+            (current_lamports as f64 / LAMPORTS_PER_SOL as f64)
+                .to_string()
+                .bright_green()
+        );
+        return Ok(None);
+    }
 
-    let transaction = VersionedTransaction::try_new(
-        VersionedMessage::V0(message),
-        &[fee_payer_keypair, create_key as &dyn Signer],
-    )
-    .expect("Failed to create transaction");
+    let shortfall = target_lamports - current_lamports;
 
-    let signature = send_and_confirm_transaction(&transaction, &rpc_client)?;
+    let progress = Output::spinner(format!(
+        "Pre-funding vault with {} SOL...",
+        shortfall as f64 / LAMPORTS_PER_SOL as f64
+    ));
 
-    let network_display = if rpc_url.contains("devnet") {
-        "Devnet"
-    } else if rpc_url.contains("mainnet") {
-        "Mainnet"
-    } else if rpc_url.contains("testnet") {
-        "Testnet"
-    } else {
-        "Custom"
-    };
+    let transfer =
+        solana_system_interface::instruction::transfer(&fee_payer.pubkey(), vault_address, shortfall);
+    let signature = send_and_confirm_rebuildable_transaction(
+        &rpc_client,
+        tx_options.send_strategy,
+        retry_budget,
+        |blockhash| {
+            let message = Message::try_compile(
+                &fee_payer.pubkey(),
+                std::slice::from_ref(&transfer),
+                &[],
+                blockhash,
+            )?;
+            if tx_options.dump_instructions {
+                progress.suspend(|| dump_message_instructions(&message));
+            }
+            if tx_options.validate_accounts {
+                let program_id = tx_options.program_id.unwrap_or(SQUADS_MULTISIG_PROGRAM_ID);
+                progress.suspend(|| {
+                    check_writable_account_owners(&rpc_client, &message, &program_id)
+                });
+            }
+            VersionedTransaction::try_new(VersionedMessage::V0(message), &[fee_payer])
+                .map_err(|e| eyre!("Failed to create signed transaction: {}", e))
+        },
+    )?;
 
     progress.finish_with_message(format!(
-        "Multisig creation confirmed: {} ({})",
-        signature.to_string().bright_green(),
-        network_display
+        "Vault pre-funding confirmed: {}",
+        signature.bright_green()
     ));
 
-    Ok((multisig_key.0, signature))
+    Ok(Some(signature))
 }
 
 pub async fn create_feature_gate_proposal(
@@ -454,8 +1564,10 @@ pub async fn create_feature_gate_proposal(
     contributor_keypair: &dyn Signer,
     priority_fee_lamports: Option<u64>,
 ) -> eyre::Result<()> {
-    let program_id = program_id.unwrap_or_else(|| SQUADS_PROGRAM_ID_STR.to_string());
-    let program_id = Pubkey::from_str(&program_id).expect("Invalid program ID");
+    let program_id = match program_id {
+        Some(program_id) => parse_pubkey(&program_id)?,
+        None => SQUADS_MULTISIG_PROGRAM_ID,
+    };
 
     let transaction_creator = contributor_keypair.pubkey();
     let vault_pda = get_vault_pda(&multisig_pubkey, 0, Some(&program_id));
@@ -492,7 +1604,7 @@ pub async fn create_feature_gate_proposal(
         .interact()?;
     if !proceed {
         println!("{}", "OK, aborting.".bright_red());
-        return Err(eyre!("User aborted"));
+        return Err(ToolError::Cancelled("User aborted".to_string()).into());
     }
     println!();
 
@@ -505,9 +1617,8 @@ pub async fn create_feature_gate_proposal(
         );
 
         let rpc_client = create_rpc_client(rpc_url);
-        let progress =
-            ProgressBar::new_spinner().with_message("Processing feature gate transactions...");
-        progress.enable_steady_tick(Duration::from_millis(100));
+        let priority_fee = priority_fee_lamports.unwrap_or_else(|| default_priority_fee(rpc_url));
+        let progress = Output::spinner("Processing feature gate transactions...");
 
         let blockhash = rpc_client
             .get_latest_blockhash()
@@ -533,22 +1644,25 @@ pub async fn create_feature_gate_proposal(
         let revocation_tx_index = base_tx_index + 2;
 
         // Create transaction messages using utility functions
-        let activation_message = crate::utils::create_feature_activation_transaction_message(vault_pda.0);
-        let revocation_message = crate::utils::create_feature_revocation_transaction_message(vault_pda.0);
+        let activation_message =
+            crate::utils::create_feature_activation_transaction_message(vault_pda.0);
+        let revocation_message = crate::utils::create_feature_revocation_transaction_message(
+            vault_pda.0,
+            crate::feature_gate_program::INCINERATOR_ID,
+        );
 
         // Transaction 1: Create activation transaction and proposal in one step
         let (activation_combined_message, activation_transaction_pda, activation_proposal_pda) =
             create_transaction_and_proposal_message(
-                Some(&program_id),
-                &transaction_creator,
-                &transaction_creator,
-                &multisig_pubkey,
-                activation_tx_index,
-                0, // vault_index
+                &TransactionBuildContext::new(
+                    multisig_pubkey,
+                    activation_tx_index,
+                    transaction_creator,
+                    blockhash,
+                )
+                .with_program_id(Some(&program_id))
+                .with_compute_budget(Some(priority_fee as u32), Some(DEFAULT_COMPUTE_UNITS)),
                 activation_message,
-                priority_fee_lamports.map(|fee| fee as u32),
-                Some(DEFAULT_COMPUTE_UNITS), // compute_unit_limit
-                blockhash,
             )?;
 
         let activation_combined_transaction = VersionedTransaction::try_new(
@@ -558,21 +1672,24 @@ pub async fn create_feature_gate_proposal(
         .expect("Failed to create activation combined transaction");
 
         let activation_combined_signature =
-            send_and_confirm_transaction(&activation_combined_transaction, &rpc_client)?;
+            send_and_confirm_transaction(
+                &activation_combined_transaction,
+                &rpc_client,
+                SendStrategy::default(),
+            )?;
 
         // Transaction 2: Create revocation transaction and proposal in one step
         let (revocation_combined_message, revocation_transaction_pda, revocation_proposal_pda) =
             create_transaction_and_proposal_message(
-                Some(&program_id),
-                &transaction_creator,
-                &transaction_creator,
-                &multisig_pubkey,
-                revocation_tx_index,
-                0, // vault_index
+                &TransactionBuildContext::new(
+                    multisig_pubkey,
+                    revocation_tx_index,
+                    transaction_creator,
+                    blockhash,
+                )
+                .with_program_id(Some(&program_id))
+                .with_compute_budget(Some(priority_fee as u32), Some(DEFAULT_COMPUTE_UNITS)),
                 revocation_message,
-                priority_fee_lamports.map(|fee| fee as u32),
-                Some(DEFAULT_COMPUTE_UNITS), // compute_unit_limit
-                blockhash,
             )?;
 
         let revocation_combined_transaction = VersionedTransaction::try_new(
@@ -582,7 +1699,11 @@ pub async fn create_feature_gate_proposal(
         .expect("Failed to create revocation combined transaction");
 
         let revocation_combined_signature =
-            send_and_confirm_transaction(&revocation_combined_transaction, &rpc_client)?;
+            send_and_confirm_transaction(
+                &revocation_combined_transaction,
+                &rpc_client,
+                SendStrategy::default(),
+            )?;
 
         progress.finish_with_message("Network completed!");
 
@@ -625,19 +1746,192 @@ pub async fn create_feature_gate_proposal(
     Ok(())
 }
 
+/// Signer and writable flags of the account at `index` in `message`, as the
+/// runtime will see them (lookup table accounts are never signers)
+fn message_account_flags(message: &Message, index: usize) -> (bool, bool) {
+    let is_signer = index < usize::from(message.header.num_required_signatures);
+    (is_signer, message.is_maybe_writable(index, None))
+}
+
+/// Prints every instruction in `message` with its program, resolved account
+/// metas and data in hex, for auditing what is about to be signed
+pub fn dump_message_instructions(message: &Message) {
+    println!();
+    Output::subheader(&format!(
+        "🔬 Instructions ({}) for fee payer {}",
+        message.instructions.len(),
+        message.account_keys[0]
+    ));
+    for (i, instruction) in message.instructions.iter().enumerate() {
+        let program_id = message.account_keys[usize::from(instruction.program_id_index)];
+        println!();
+        println!(
+            "  {} {}",
+            format!("#{} Program:", i + 1).cyan(),
+            program_id.to_string().bright_white()
+        );
+        for &account_index in &instruction.accounts {
+            let account_index = usize::from(account_index);
+            let key = match message.account_keys.get(account_index) {
+                Some(key) => key.to_string(),
+                None => format!("lookup table account #{}", account_index),
+            };
+            let (is_signer, is_writable) = message_account_flags(message, account_index);
+            let flags = match (is_signer, is_writable) {
+                (true, true) => "signer, writable",
+                (true, false) => "signer",
+                (false, true) => "writable",
+                (false, false) => "readonly",
+            };
+            println!("    {} [{}]", key, flags.dimmed());
+        }
+        let data_hex: String = instruction.data.iter().map(|b| format!("{:02x}", b)).collect();
+        println!("    {} {} ({} bytes)", "Data:".cyan(), data_hex, instruction.data.len());
+    }
+    println!();
+}
+
+/// Why a writable account is unexpected in a message sent to the Squads
+/// `program_id`, if it is. The tool's transactions only write accounts that
+/// don't exist yet, system accounts and the Squads program's own accounts.
+fn unexpected_writable_account(account: Option<&Account>, program_id: &Pubkey) -> Option<String> {
+    // Not created yet, e.g. a new transaction or proposal PDA
+    let account = account?;
+    if account.executable {
+        Some("is an executable program".to_string())
+    } else if account.owner == solana_system_interface::program::ID || account.owner == *program_id
+    {
+        None
+    } else {
+        Some(format!("is owned by {}", account.owner))
+    }
+}
+
+/// Fetches every writable non-signer account of `message` and warns about
+/// any the runtime would likely refuse to let it write. Advisory only: a
+/// failed fetch is reported and the message still sent.
+pub fn check_writable_account_owners(
+    rpc_client: &RpcClient,
+    message: &Message,
+    program_id: &Pubkey,
+) {
+    let writable: Vec<Pubkey> = (0..message.account_keys.len())
+        .filter(|&i| matches!(message_account_flags(message, i), (false, true)))
+        .map(|i| message.account_keys[i])
+        .collect();
+    let accounts = match fetch_accounts_batched(rpc_client, &writable, MAX_ACCOUNTS_PER_REQUEST) {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            Output::warning(&format!("Could not validate writable accounts: {}", e));
+            return;
+        }
+    };
+
+    let mut unexpected = 0;
+    for (key, account) in writable.iter().zip(&accounts) {
+        if let Some(reason) = unexpected_writable_account(account.as_ref(), program_id) {
+            Output::warning(&format!("Writable account {} {}", key, reason));
+            unexpected += 1;
+        }
+    }
+    if unexpected == 0 {
+        Output::info(&format!("Validated {} writable account owners", writable.len()));
+    }
+}
+
+/// Where and by whom a vault transaction proposal is created, for
+/// `create_transaction_and_proposal_message`
+#[derive(Debug, Clone, Copy)]
+pub struct TransactionBuildContext {
+    pub program_id: Pubkey,
+    /// Pays the transaction fee and the rent of the new accounts
+    pub fee_payer: Pubkey,
+    /// Member recorded as the creator of the transaction and proposal
+    pub creator: Pubkey,
+    pub multisig: Pubkey,
+    pub transaction_index: u64,
+    pub vault_index: u8,
+    /// Compute unit price in micro-lamports; no price instruction when unset
+    pub priority_fee: Option<u32>,
+    /// No limit instruction when unset
+    pub compute_unit_limit: Option<u32>,
+    pub recent_blockhash: Hash,
+    pub is_draft: bool,
+}
+
+impl TransactionBuildContext {
+    /// Proposal `transaction_index` on vault 0 of `multisig` under the
+    /// canonical program, created and paid for by `creator`, without compute
+    /// budget instructions
+    pub fn new(
+        multisig: Pubkey,
+        transaction_index: u64,
+        creator: Pubkey,
+        recent_blockhash: Hash,
+    ) -> Self {
+        Self {
+            program_id: SQUADS_MULTISIG_PROGRAM_ID,
+            fee_payer: creator,
+            creator,
+            multisig,
+            transaction_index,
+            vault_index: 0,
+            priority_fee: None,
+            compute_unit_limit: None,
+            recent_blockhash,
+            is_draft: false,
+        }
+    }
+
+    /// Targets `program_id` instead of the canonical program when given
+    pub fn with_program_id(mut self, program_id: Option<&Pubkey>) -> Self {
+        self.program_id = *program_id.unwrap_or(&SQUADS_MULTISIG_PROGRAM_ID);
+        self
+    }
+
+    pub fn with_fee_payer(mut self, fee_payer: Pubkey) -> Self {
+        self.fee_payer = fee_payer;
+        self
+    }
+
+    pub fn with_compute_budget(
+        mut self,
+        priority_fee: Option<u32>,
+        compute_unit_limit: Option<u32>,
+    ) -> Self {
+        self.priority_fee = priority_fee;
+        self.compute_unit_limit = compute_unit_limit;
+        self
+    }
+
+    pub fn with_draft(mut self, is_draft: bool) -> Self {
+        self.is_draft = is_draft;
+        self
+    }
+}
+
+/// Builds a `vault_transaction_create` + `proposal_create` message for
+/// `transaction_message`, returning it with the transaction and proposal PDAs
 pub fn create_transaction_and_proposal_message(
-    program_id: Option<&Pubkey>,
-    fee_payer_pubkey: &Pubkey,
-    contributor_pubkey: &Pubkey,
-    multisig_address: &Pubkey,
-    transaction_index: u64,
-    vault_index: u8,
+    context: &TransactionBuildContext,
     transaction_message: TransactionMessage,
-    priority_fee: Option<u32>,
-    compute_unit_limit: Option<u32>,
-    recent_blockhash: Hash,
 ) -> eyre::Result<(Message, Pubkey, Pubkey)> {
-    let program_id = program_id.unwrap_or(&crate::squads::SQUADS_MULTISIG_PROGRAM_ID);
+    let TransactionBuildContext {
+        program_id,
+        fee_payer: fee_payer_pubkey,
+        creator: contributor_pubkey,
+        multisig: multisig_address,
+        transaction_index,
+        vault_index,
+        priority_fee,
+        compute_unit_limit,
+        recent_blockhash,
+        is_draft,
+    } = *context;
+    let program_id = &program_id;
+    let fee_payer_pubkey = &fee_payer_pubkey;
+    let contributor_pubkey = &contributor_pubkey;
+    let multisig_address = &multisig_address;
 
     // Derive transaction and proposal PDAs
     let (transaction_pda, _transaction_bump) =
@@ -654,6 +1948,10 @@ pub fn create_transaction_and_proposal_message(
         system_program: solana_system_interface::program::ID,
     };
 
+    transaction_message
+        .validate()
+        .map_err(|e| eyre!("Invalid transaction message: {}", e))?;
+
     // Serialize the TransactionMessage to bytes as expected by the on-chain program
     let transaction_message_bytes = borsh::to_vec(&transaction_message)?;
 
@@ -684,7 +1982,7 @@ pub fn create_transaction_and_proposal_message(
     let create_proposal_data = MultisigCreateProposalData {
         args: MultisigCreateProposalArgs {
             transaction_index,
-            is_draft: false, // Not a draft, ready for voting
+            is_draft,
         },
     };
 
@@ -694,20 +1992,9 @@ pub fn create_transaction_and_proposal_message(
         create_proposal_accounts.to_account_metas(None),
     );
 
-    // Build instructions list
-    let mut instructions = Vec::new();
-
-    // Add compute unit price if specified
-    if let Some(microlamports) = priority_fee {
-        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
-            microlamports as u64,
-        ));
-    }
-
-    // Add compute unit limit if specified
-    if let Some(units) = compute_unit_limit {
-        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
-    }
+    // Build instructions list, compute budget first
+    let mut instructions =
+        compute_budget_instructions(compute_unit_limit, priority_fee.map(u64::from));
 
     // Add both create transaction and create proposal instructions
     instructions.push(create_transaction_instruction);
@@ -719,6 +2006,103 @@ pub fn create_transaction_and_proposal_message(
     Ok((message, transaction_pda, proposal_pda))
 }
 
+/// Which way a member votes on a proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vote {
+    Approve,
+    Reject,
+}
+
+/// Builds a message voting on the proposal at `transaction_index`
+pub fn create_vote_transaction_message(
+    program_id: &Pubkey,
+    multisig_address: &Pubkey,
+    transaction_index: u64,
+    member_pubkey: &Pubkey,
+    fee_payer_pubkey: &Pubkey,
+    vote: Vote,
+    recent_blockhash: Hash,
+) -> eyre::Result<Message> {
+    let (proposal_pda, _proposal_bump) =
+        get_proposal_pda(multisig_address, transaction_index, Some(program_id));
+
+    let account_keys = MultisigVoteOnProposalAccounts {
+        multisig: *multisig_address,
+        member: *member_pubkey,
+        proposal: proposal_pda,
+    };
+    let args = MultisigVoteOnProposalArgs { memo: None };
+    let data = match vote {
+        Vote::Approve => MultisigApproveProposalData { args }.data(),
+        Vote::Reject => MultisigRejectProposalData { args }.data(),
+    };
+
+    let vote_instruction =
+        Instruction::new_with_bytes(*program_id, &data, account_keys.to_account_metas());
+
+    let message =
+        Message::try_compile(fee_payer_pubkey, &[vote_instruction], &[], recent_blockhash)?;
+
+    Ok(message)
+}
+
+/// Builds a message opening voting on the draft proposal at `transaction_index`
+pub fn create_activate_proposal_message(
+    program_id: &Pubkey,
+    multisig_address: &Pubkey,
+    transaction_index: u64,
+    member_pubkey: &Pubkey,
+    fee_payer_pubkey: &Pubkey,
+    recent_blockhash: Hash,
+) -> eyre::Result<Message> {
+    let account_keys = MultisigActivateProposalAccounts {
+        multisig: *multisig_address,
+        member: *member_pubkey,
+        proposal: get_proposal_pda(multisig_address, transaction_index, Some(program_id)).0,
+    };
+
+    let activate_instruction = Instruction::new_with_bytes(
+        *program_id,
+        &MultisigActivateProposalData.data(),
+        account_keys.to_account_metas(),
+    );
+
+    let message =
+        Message::try_compile(fee_payer_pubkey, &[activate_instruction], &[], recent_blockhash)?;
+
+    Ok(message)
+}
+
+/// Builds a message closing the vault transaction and proposal accounts at
+/// `transaction_index`, returning their rent to `rent_collector`
+pub fn create_close_transaction_message(
+    program_id: &Pubkey,
+    multisig_address: &Pubkey,
+    transaction_index: u64,
+    rent_collector: &Pubkey,
+    fee_payer_pubkey: &Pubkey,
+    recent_blockhash: Hash,
+) -> eyre::Result<Message> {
+    let account_keys = MultisigCloseTransactionAccounts {
+        multisig: *multisig_address,
+        proposal: get_proposal_pda(multisig_address, transaction_index, Some(program_id)).0,
+        transaction: get_transaction_pda(multisig_address, transaction_index, Some(program_id)).0,
+        rent_collector: *rent_collector,
+        system_program: solana_system_interface::program::ID,
+    };
+
+    let close_instruction = Instruction::new_with_bytes(
+        *program_id,
+        &MultisigCloseTransactionData.data(),
+        account_keys.to_account_metas(),
+    );
+
+    let message =
+        Message::try_compile(fee_payer_pubkey, &[close_instruction], &[], recent_blockhash)?;
+
+    Ok(message)
+}
+
 pub fn create_approve_activation_transaction_message(
     program_id: &Pubkey,
     feature_gate_multisig_address: &Pubkey,
@@ -726,8 +2110,11 @@ pub fn create_approve_activation_transaction_message(
     fee_payer_pubkey: &Pubkey,
     recent_blockhash: Hash,
 ) -> eyre::Result<Message> {
-    let (proposal_pda, _proposal_bump) =
-        get_proposal_pda(feature_gate_multisig_address, 0, Some(program_id));
+    let (proposal_pda, _proposal_bump) = get_proposal_pda(
+        feature_gate_multisig_address,
+        ACTIVATION_TRANSACTION_INDEX,
+        Some(program_id),
+    );
 
     let account_keys = MultisigVoteOnProposalAccounts {
         multisig: *feature_gate_multisig_address,
@@ -762,8 +2149,11 @@ pub fn create_approve_activation_revocation_transaction_message(
     fee_payer_pubkey: &Pubkey,
     recent_blockhash: Hash,
 ) -> eyre::Result<Message> {
-    let (proposal_pda, _proposal_bump) =
-        get_proposal_pda(feature_gate_multisig_address, 1, Some(program_id));
+    let (proposal_pda, _proposal_bump) = get_proposal_pda(
+        feature_gate_multisig_address,
+        REVOCATION_TRANSACTION_INDEX,
+        Some(program_id),
+    );
 
     let account_keys = MultisigVoteOnProposalAccounts {
         multisig: *feature_gate_multisig_address,
@@ -798,15 +2188,21 @@ pub async fn create_execute_activation_transaction_message(
     rpc_client: &nonblocking::rpc_client::RpcClient,
     recent_blockhash: Hash,
 ) -> eyre::Result<Message> {
-    let (proposal_pda, _proposal_bump) =
-        get_proposal_pda(feature_gate_multisig_address, 0, Some(program_id));
-    let (transaction_pda, _transaction_bump) =
-        get_transaction_pda(feature_gate_multisig_address, 0, Some(program_id));
+    let (proposal_pda, _proposal_bump) = get_proposal_pda(
+        feature_gate_multisig_address,
+        ACTIVATION_TRANSACTION_INDEX,
+        Some(program_id),
+    );
+    let (transaction_pda, _transaction_bump) = get_transaction_pda(
+        feature_gate_multisig_address,
+        ACTIVATION_TRANSACTION_INDEX,
+        Some(program_id),
+    );
     let vault_pda = get_vault_pda(feature_gate_multisig_address, 0, Some(program_id));
 
     let transaction_account_data = rpc_client.get_account_data(&transaction_pda).await?;
-    let transaction_contents =
-        VaultTransaction::try_from_slice(&transaction_account_data[8..]).unwrap();
+    let transaction_contents = VaultTransaction::try_from_slice(&transaction_account_data[8..])
+        .map_err(|e| eyre!("Failed to deserialize vault transaction {}: {}", transaction_pda, e))?;
     let transaction_message = transaction_contents.message;
 
     let mut execution_account_metas = Vec::new();
@@ -869,9 +2265,9 @@ pub fn parse_members(member_strings: Vec<String>) -> Result<Vec<Member>, String>
                 );
             }
 
-            let key =
-                Pubkey::from_str(parts[0]).map_err(|_| "Invalid public key format".to_string())?;
+            let key = parse_pubkey(parts[0]).map_err(|e| e.to_string())?;
             let permissions = parts[1]
+                .trim()
                 .parse::<u8>()
                 .map_err(|_| "Invalid permission format".to_string())?;
 
@@ -886,9 +2282,403 @@ pub fn parse_members(member_strings: Vec<String>) -> Result<Vec<Member>, String>
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::squads::{CompiledInstruction, SmallVec, TransactionMessage};
+    use crate::squads::{CompiledInstruction, Permission, SmallVec, TransactionMessage};
     use borsh::BorshDeserialize;
 
+    #[test]
+    fn test_multisig_slice_holds_sixteen_members() {
+        let multisig = |members: usize| {
+            let multisig = Multisig {
+                create_key: Pubkey::new_unique(),
+                config_authority: Pubkey::new_unique(),
+                threshold: 1,
+                time_lock: 0,
+                transaction_index: 0,
+                stale_transaction_index: 0,
+                rent_collector: Some(Pubkey::new_unique()),
+                bump: 255,
+                members: (0..members)
+                    .map(|_| Member {
+                        key: Pubkey::new_unique(),
+                        permissions: Permissions { mask: 7 },
+                    })
+                    .collect(),
+            };
+            let mut data = crate::squads::MULTISIG_DISCRIMINATOR.to_vec();
+            data.extend(borsh::to_vec(&multisig).unwrap());
+            data
+        };
+
+        let sixteen = multisig(16);
+        assert_eq!(sixteen.len(), MULTISIG_SLICE_LEN);
+        assert_eq!(Multisig::truncated_len(&sixteen), None);
+        // One more member needs the exact-length refetch
+        let seventeen = multisig(17);
+        assert_eq!(
+            Multisig::truncated_len(&seventeen[..MULTISIG_SLICE_LEN]),
+            Some(seventeen.len())
+        );
+    }
+
+    #[test]
+    fn test_index_gaps_groups_consecutive_states() {
+        use IndexAccounts::*;
+        let states = [
+            (1, Complete),
+            (2, Missing),
+            (3, Missing),
+            (4, TransactionOnly),
+            (5, Complete),
+            (6, Missing),
+            (8, Missing),
+        ];
+        assert_eq!(
+            index_gaps(&states),
+            vec![
+                (2..=3, Missing),
+                (4..=4, TransactionOnly),
+                (6..=6, Missing),
+                (8..=8, Missing),
+            ]
+        );
+        assert!(index_gaps(&[(1, Complete), (2, Complete)]).is_empty());
+        assert_eq!(IndexAccounts::from_presence(true, false), TransactionOnly);
+    }
+
+    #[test]
+    fn test_unsafe_overrides_are_refused_on_mainnet() {
+        let mainnet = "https://api.mainnet-beta.solana.com";
+        let localnet = "http://127.0.0.1:8899";
+
+        let canonical = TransactionOptions {
+            program_id: Some(SQUADS_MULTISIG_PROGRAM_ID),
+            revocation_destination: Some(crate::feature_gate_program::INCINERATOR_ID),
+            ..Default::default()
+        };
+        assert!(canonical.unsafe_overrides().is_empty());
+        assert!(canonical.ensure_safe_for(mainnet).is_ok());
+
+        let mut overridden = TransactionOptions {
+            program_id: Some(Pubkey::new_unique()),
+            treasury: Some(Pubkey::new_unique()),
+            revocation_destination: Some(Pubkey::new_unique()),
+            ..Default::default()
+        };
+        assert_eq!(
+            overridden.unsafe_overrides(),
+            ["--program-id", "--treasury", "--revocation-destination"]
+        );
+        assert!(overridden.ensure_safe_for(localnet).is_ok());
+        let error = overridden.ensure_safe_for(mainnet).unwrap_err();
+        assert!(error.to_string().contains("--i-understand-mainnet-risk"));
+
+        overridden.mainnet_risk_acknowledged = true;
+        assert!(overridden.ensure_safe_for(mainnet).is_ok());
+    }
+
+    #[test]
+    fn test_mainnet_confirmation_covers_unidentified_custom_rpcs() {
+        assert!(needs_mainnet_confirmation("https://api.mainnet-beta.solana.com", false));
+        assert!(!needs_mainnet_confirmation("https://api.mainnet-beta.solana.com", true));
+        assert!(!needs_mainnet_confirmation("https://api.devnet.solana.com", false));
+        assert!(!needs_mainnet_confirmation("http://127.0.0.1:8899", false));
+        // A custom host whose genesis hash can't be fetched might be mainnet
+        assert!(needs_mainnet_confirmation("http://127.0.0.2:1", false));
+    }
+
+    #[test]
+    fn test_unverified_program_is_refused_off_localnet() {
+        let mock_program = Pubkey::new_unique();
+        let devnet = "https://api.devnet.solana.com";
+
+        assert!(verify_program(devnet, &SQUADS_MULTISIG_PROGRAM_ID, None, false).is_ok());
+        assert!(verify_program("http://127.0.0.1:8899", &mock_program, None, false).is_ok());
+        let error = verify_program(devnet, &mock_program, None, false).unwrap_err();
+        assert!(error.to_string().contains("--expected-program-hash"));
+        assert!(verify_program(devnet, &mock_program, None, true).is_ok());
+    }
+
+    #[test]
+    fn test_retry_delay_ms_backs_off_further_when_rate_limited() {
+        assert_eq!(retry_delay_ms(1, 500, false), 500);
+        assert_eq!(retry_delay_ms(3, 500, false), 2000);
+        assert_eq!(retry_delay_ms(1, 500, true), RATE_LIMIT_RETRY_DELAY_MS);
+        assert_eq!(retry_delay_ms(5, 500, true), 8000);
+    }
+
+    #[test]
+    fn test_unexpected_writable_account() {
+        let program_id = SQUADS_MULTISIG_PROGRAM_ID;
+        let account = |owner, executable| Account {
+            lamports: 1,
+            data: vec![],
+            owner,
+            executable,
+            rent_epoch: 0,
+        };
+
+        assert_eq!(unexpected_writable_account(None, &program_id), None);
+        let system_account = account(solana_system_interface::program::ID, false);
+        assert_eq!(unexpected_writable_account(Some(&system_account), &program_id), None);
+        let squads_account = account(program_id, false);
+        assert_eq!(unexpected_writable_account(Some(&squads_account), &program_id), None);
+
+        let token_program = Pubkey::new_unique();
+        let token_account = account(token_program, false);
+        assert_eq!(
+            unexpected_writable_account(Some(&token_account), &program_id),
+            Some(format!("is owned by {}", token_program))
+        );
+        let program = account(Pubkey::new_unique(), true);
+        assert_eq!(
+            unexpected_writable_account(Some(&program), &program_id),
+            Some("is an executable program".to_string())
+        );
+    }
+
+    #[test]
+    fn test_verify_created_multisig() {
+        let member = |permissions| Member {
+            key: Pubkey::new_unique(),
+            permissions: Permissions::from_actions(permissions),
+        };
+        let requested = MultisigCreateArgsV2 {
+            config_authority: None,
+            threshold: 2,
+            members: vec![
+                member(&[Permission::Initiate]),
+                member(&Permission::ALL),
+                member(&Permission::ALL),
+            ],
+            time_lock: 0,
+            rent_collector: None,
+            memo: None,
+        };
+        // The program may store members in a different order
+        let mut created = Multisig {
+            create_key: Pubkey::new_unique(),
+            config_authority: Pubkey::default(),
+            threshold: 2,
+            time_lock: 0,
+            transaction_index: 0,
+            stale_transaction_index: 0,
+            rent_collector: None,
+            bump: 255,
+            members: requested.members.iter().rev().cloned().collect(),
+        };
+        assert!(verify_created_multisig(&created, &requested).is_ok());
+
+        created.threshold = 1;
+        created.members[0].permissions = Permissions::from_actions(&[Permission::Vote]);
+        let unexpected = created.members.pop().unwrap();
+        created.members.push(member(&Permission::ALL));
+        let error = verify_created_multisig(&created, &requested).unwrap_err().to_string();
+        assert!(error.contains("threshold is 1, requested 2"));
+        let downgraded = requested.members[2].key;
+        assert!(error.contains(&format!("member {} has permission mask 2", downgraded)));
+        assert!(error.contains(&format!("member {} is missing", unexpected.key)));
+        assert!(error.contains("was not requested"));
+    }
+
+    #[test]
+    fn test_fetch_in_chunks_keeps_input_order_with_missing_accounts() {
+        let pubkeys: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        // Every other account exists, holding its position in `pubkeys`
+        let existing: std::collections::HashMap<Pubkey, usize> =
+            pubkeys.iter().enumerate().step_by(2).map(|(i, key)| (*key, i)).collect();
+
+        let mut requests = Vec::new();
+        let accounts = fetch_in_chunks(&pubkeys, 2, |chunk| {
+            requests.push(chunk.len());
+            Ok(chunk.iter().map(|key| existing.get(key).copied()).collect())
+        })
+        .unwrap();
+
+        assert_eq!(requests, vec![2, 2, 1]);
+        assert_eq!(accounts, vec![Some(0), None, Some(2), None, Some(4)]);
+    }
+
+    #[test]
+    fn test_fetch_in_chunks_caps_chunk_size_and_rejects_short_responses() {
+        let pubkeys: Vec<Pubkey> = (0..150).map(|_| Pubkey::new_unique()).collect();
+        let mut requests = Vec::new();
+        fetch_in_chunks(&pubkeys, 500, |chunk| {
+            requests.push(chunk.len());
+            Ok(vec![None::<()>; chunk.len()])
+        })
+        .unwrap();
+        assert_eq!(requests, vec![100, 50]);
+
+        let short = fetch_in_chunks(&pubkeys, 100, |_| Ok(vec![None::<()>]));
+        assert!(short.is_err());
+    }
+
+    #[test]
+    fn test_retry_budget_caps_retries_and_time() {
+        let budget = RetryBudget::new(2, Duration::from_secs(60));
+        assert!(budget.try_spend());
+        assert!(budget.try_spend());
+        assert!(!budget.try_spend());
+        assert!(budget.to_string().starts_with("0 retries"));
+
+        let expired = RetryBudget::new(5, Duration::ZERO);
+        assert!(expired.is_exhausted());
+        assert!(!expired.try_spend());
+
+        let unlimited = RetryBudget::default();
+        assert!(unlimited.try_spend());
+        assert!(!unlimited.is_exhausted());
+    }
+
+    #[test]
+    fn test_compute_budget_limit_precedes_price() {
+        // ComputeBudgetInstruction tags: 2 = SetComputeUnitLimit, 3 = SetComputeUnitPrice
+        let tags = |instructions: &[Instruction]| -> Vec<u8> {
+            instructions.iter().map(|instruction| instruction.data[0]).collect()
+        };
+        assert_eq!(tags(&compute_budget_instructions(Some(200_000), Some(1_000))), vec![2, 3]);
+        assert_eq!(tags(&compute_budget_instructions(None, Some(1_000))), vec![3]);
+        assert!(compute_budget_instructions(None, None).is_empty());
+
+        let creator = Pubkey::new_unique();
+        let context = TransactionBuildContext::new(Pubkey::new_unique(), 1, creator, Hash::default())
+            .with_fee_payer(Pubkey::new_unique())
+            .with_compute_budget(Some(5_000), Some(300_000));
+        let (message, _, _) =
+            create_transaction_and_proposal_message(&context, create_test_transaction_message())
+                .unwrap();
+        let compute_budget_index = message
+            .account_keys
+            .iter()
+            .position(|key| *key == solana_compute_budget_interface::ID)
+            .unwrap() as u8;
+        let budget_tags: Vec<u8> = message
+            .instructions
+            .iter()
+            .filter(|instruction| instruction.program_id_index == compute_budget_index)
+            .map(|instruction| instruction.data[0])
+            .collect();
+        assert_eq!(budget_tags, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_expired_blockhash_rebuilds_transaction() {
+        let payer = Keypair::new();
+        let blockhashes = [Hash::new_unique(), Hash::new_unique()];
+        let mut fetched = 0;
+        let mut sent = Vec::new();
+
+        let signature = send_with_fresh_blockhashes(
+            || {
+                fetched += 1;
+                Ok(blockhashes[fetched - 1])
+            },
+            |blockhash| {
+                let message = Message::try_compile(&payer.pubkey(), &[], &[], blockhash)?;
+                Ok(VersionedTransaction::try_new(VersionedMessage::V0(message), &[&payer])?)
+            },
+            |transaction| {
+                sent.push(*transaction.message.recent_blockhash());
+                if sent.len() == 1 {
+                    Err(SendFailure {
+                        report: eyre!("blockhash expired"),
+                        blockhash_expired: true,
+                    })
+                } else {
+                    Ok(transaction.signatures[0].to_string())
+                }
+            },
+        )
+        .unwrap();
+
+        assert_eq!(sent, blockhashes);
+        assert!(!signature.is_empty());
+
+        // Other failures are returned without rebuilding
+        let mut attempts = 0;
+        let err = send_with_fresh_blockhashes(
+            || Ok(Hash::new_unique()),
+            |blockhash| {
+                let message = Message::try_compile(&payer.pubkey(), &[], &[], blockhash)?;
+                Ok(VersionedTransaction::try_new(VersionedMessage::V0(message), &[&payer])?)
+            },
+            |_| {
+                attempts += 1;
+                Err(SendFailure {
+                    report: eyre!("failed on chain"),
+                    blockhash_expired: false,
+                })
+            },
+        )
+        .unwrap_err();
+        assert_eq!(attempts, 1);
+        assert_eq!(err.to_string(), "failed on chain");
+    }
+
+    #[test]
+    fn test_funding_shortfall_counts_top_up() {
+        assert_eq!(funding_shortfall(0, None, 946_560), Some(946_560));
+        assert_eq!(funding_shortfall(900_000, None, 946_560), Some(46_560));
+        assert_eq!(funding_shortfall(0, Some(10_000_000), 946_560), None);
+        assert_eq!(funding_shortfall(946_560, None, 946_560), None);
+        // A top-up never lowers an existing balance
+        assert_eq!(funding_shortfall(2_000_000, Some(1), 946_560), None);
+    }
+
+    #[test]
+    fn test_message_account_flags_match_instruction_metas() {
+        let fee_payer = Pubkey::new_unique();
+        let co_signer = Pubkey::new_unique();
+        let writable = Pubkey::new_unique();
+        let readonly = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(co_signer, true),
+                AccountMeta::new(writable, false),
+                AccountMeta::new_readonly(readonly, false),
+            ],
+            data: vec![1, 2, 3],
+        };
+        let message = Message::try_compile(&fee_payer, &[instruction], &[], Hash::default())
+            .unwrap();
+
+        let flags = |key: &Pubkey| {
+            let index = message.account_keys.iter().position(|k| k == key).unwrap();
+            message_account_flags(&message, index)
+        };
+        assert_eq!(flags(&fee_payer), (true, true));
+        assert_eq!(flags(&co_signer), (true, false));
+        assert_eq!(flags(&writable), (false, true));
+        assert_eq!(flags(&readonly), (false, false));
+        assert_eq!(flags(&program_id), (false, false));
+    }
+
+    #[test]
+    fn test_parse_program_config_checks_owner_and_discriminator() {
+        let program_id = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let mut data = PROGRAM_CONFIG_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(Pubkey::new_unique().as_ref()); // authority
+        data.extend_from_slice(&0u64.to_le_bytes()); // multisig_creation_fee
+        data.extend_from_slice(treasury.as_ref());
+        data.extend_from_slice(&[0; 64]); // _reserved
+
+        let config = parse_program_config(&program_id, &data, &program_id).unwrap();
+        assert_eq!(config.treasury, treasury);
+
+        let wrong_owner = parse_program_config(&Pubkey::new_unique(), &data, &program_id);
+        assert!(matches!(
+            wrong_owner,
+            Err(e) if e.to_string().contains("Program config not initialized")
+        ));
+
+        data[0] ^= 0xff;
+        assert!(parse_program_config(&program_id, &data, &program_id).is_err());
+        assert!(parse_program_config(&program_id, &[], &program_id).is_err());
+    }
+
     fn create_test_transaction_message() -> TransactionMessage {
         use crate::feature_gate_program::create_feature_activation;
 
@@ -1136,22 +2926,18 @@ mod tests {
 
         let transaction_message = create_test_transaction_message();
         let transaction_index = 1u64;
-        let vault_index = 0u8;
         let priority_fee = Some(5000u32);
 
         // Test message creation
-        let result = create_transaction_and_proposal_message(
-            None, // Use default program ID
-            &fee_payer_pubkey,
-            &contributor_pubkey,
-            &multisig_address,
+        let context = TransactionBuildContext::new(
+            multisig_address,
             transaction_index,
-            vault_index,
-            transaction_message,
-            priority_fee,
-            Some(200000u32), // compute_unit_limit
+            contributor_pubkey,
             recent_blockhash,
-        );
+        )
+        .with_fee_payer(fee_payer_pubkey)
+        .with_compute_budget(priority_fee, Some(200000u32));
+        let result = create_transaction_and_proposal_message(&context, transaction_message);
 
         assert!(result.is_ok());
         let (message, transaction_pda, proposal_pda) = result.unwrap();
@@ -1183,21 +2969,16 @@ mod tests {
 
         let transaction_message = create_test_transaction_message();
         let transaction_index = 1u64;
-        let vault_index = 0u8;
-
-        // Test message creation without priority fee
-        let result = create_transaction_and_proposal_message(
-            None, // Use default program ID
-            &fee_payer_pubkey,
-            &contributor_pubkey,
-            &multisig_address,
+
+        // Test message creation without priority fee or compute unit limit
+        let context = TransactionBuildContext::new(
+            multisig_address,
             transaction_index,
-            vault_index,
-            transaction_message,
-            None, // No priority fee
-            None, // No compute unit limit
+            contributor_pubkey,
             recent_blockhash,
-        );
+        )
+        .with_fee_payer(fee_payer_pubkey);
+        let result = create_transaction_and_proposal_message(&context, transaction_message);
 
         assert!(result.is_ok());
         let (message, _transaction_pda, _proposal_pda) = result.unwrap();
@@ -1206,6 +2987,17 @@ mod tests {
         assert_eq!(message.instructions.len(), 2);
     }
 
+    #[test]
+    fn test_fee_percentile() {
+        assert_eq!(fee_percentile(vec![], 75), None);
+        assert_eq!(fee_percentile(vec![42], 75), Some(42));
+        // Nearest rank: ceil(0.75 * 4) = 3rd smallest
+        assert_eq!(fee_percentile(vec![400, 100, 300, 200], 75), Some(300));
+        assert_eq!(fee_percentile(vec![0, 0, 0, 5000], 75), Some(0));
+        assert_eq!(fee_percentile(vec![1, 2, 3], 100), Some(3));
+        assert_eq!(fee_percentile(vec![1, 2, 3], 0), Some(1));
+    }
+
     #[test]
     fn test_debug_serialization() {
         let transaction_message = create_test_transaction_message();
@@ -0,0 +1,94 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Limits the rate of RPC requests: each request takes a token, and tokens
+/// refill at `rate` per second up to one second's worth
+#[derive(Debug)]
+pub struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Shared by the send, confirm and account fetch helpers, so their retries and
+/// multi-network runs together stay under the limit. Unlimited until
+/// `configure` is given a rate.
+static RPC_RATE_LIMIT: Mutex<Option<TokenBucket>> = Mutex::new(None);
+
+impl TokenBucket {
+    pub fn new(requests_per_second: u32, now: Instant) -> Self {
+        Self {
+            rate: f64::from(requests_per_second),
+            tokens: f64::from(requests_per_second),
+            refilled_at: now,
+        }
+    }
+
+    /// Takes a token at `now`, returning how long the request must wait for
+    /// it. A token can be taken before it has refilled, so concurrent callers
+    /// are queued one refill apart instead of all waking at once.
+    fn take(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate) - 1.0;
+        self.refilled_at = now;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+/// Limits RPC requests to `requests_per_second`, or lifts the limit
+pub fn configure(requests_per_second: Option<u32>) {
+    let bucket = requests_per_second.map(|rps| TokenBucket::new(rps, Instant::now()));
+    *RPC_RATE_LIMIT.lock().unwrap_or_else(|e| e.into_inner()) = bucket;
+}
+
+fn reserve() -> Duration {
+    RPC_RATE_LIMIT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_mut()
+        .map_or(Duration::ZERO, |bucket| bucket.take(Instant::now()))
+}
+
+/// Waits for a token before an RPC request
+pub fn acquire() {
+    let wait = reserve();
+    if !wait.is_zero() {
+        std::thread::sleep(wait);
+    }
+}
+
+/// `acquire`, without blocking the runtime
+pub async fn acquire_async() {
+    let wait = reserve();
+    if !wait.is_zero() {
+        tokio::time::sleep(wait).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket_bursts_then_spaces_requests() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(2, start);
+
+        // A second's worth of requests go straight through
+        assert_eq!(bucket.take(start), Duration::ZERO);
+        assert_eq!(bucket.take(start), Duration::ZERO);
+        // Then each waits one refill longer than the one before
+        assert_eq!(bucket.take(start), Duration::from_millis(500));
+        assert_eq!(bucket.take(start), Duration::from_millis(1000));
+
+        // Tokens refill with time, but never beyond one second's worth
+        let later = start + Duration::from_secs(10);
+        assert_eq!(bucket.take(later), Duration::ZERO);
+        assert_eq!(bucket.take(later), Duration::ZERO);
+        assert_eq!(bucket.take(later), Duration::from_millis(500));
+    }
+}
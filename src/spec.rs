@@ -0,0 +1,335 @@
+use crate::squads::{Member, Permission, Permissions};
+use eyre::Result;
+use serde::Deserialize;
+use solana_pubkey::Pubkey;
+use std::fs;
+use std::path::Path;
+
+/// A complete multisig description, kept in version control and read by
+/// `create --spec` in place of the interactive prompts. JSON, or TOML when
+/// the file name ends in `.toml`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MultisigSpec {
+    pub threshold: u16,
+    pub members: Vec<SpecMember>,
+    /// RPC URLs to deploy to
+    pub networks: Vec<String>,
+    #[serde(default)]
+    pub time_lock: u32,
+    #[serde(default)]
+    pub config_authority: Option<String>,
+    #[serde(default)]
+    pub rent_collector: Option<String>,
+    /// Derive the create key from this seed, as `--create-key-seed` does
+    #[serde(default)]
+    pub create_key_seed: Option<String>,
+    /// Saved with the multisig in the config, as `--label` is
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpecMember {
+    pub key: String,
+    /// Permission names (`initiate`, `vote`, `execute`); all three when omitted
+    #[serde(default)]
+    pub permissions: Option<Vec<String>>,
+}
+
+/// A spec whose every field has been checked
+#[derive(Clone)]
+pub struct ValidatedSpec {
+    pub threshold: u16,
+    pub members: Vec<Member>,
+    pub networks: Vec<String>,
+    pub time_lock: u32,
+    pub config_authority: Option<Pubkey>,
+    pub rent_collector: Option<Pubkey>,
+    pub create_key_seed: Option<String>,
+    pub label: Option<String>,
+}
+
+impl MultisigSpec {
+    /// Reads and validates the spec at `path`
+    pub fn load(path: &Path) -> Result<ValidatedSpec> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("Failed to read spec {}: {}", path.display(), e))?;
+        let is_toml = path.extension().is_some_and(|extension| extension == "toml");
+        Self::parse(&contents, is_toml)
+            .and_then(|spec| spec.validate())
+            .map_err(|e| eyre::eyre!("Invalid spec {}: {}", path.display(), e))
+    }
+
+    /// Reads and validates the JSON array of specs at `path`, for
+    /// `batch-create`. Every entry is checked before anything is deployed.
+    pub fn load_manifest(path: &Path) -> Result<Vec<ValidatedSpec>> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("Failed to read manifest {}: {}", path.display(), e))?;
+        Self::parse_manifest(&contents)
+            .map_err(|e| eyre::eyre!("Invalid manifest {}: {}", path.display(), e))
+    }
+
+    fn parse_manifest(contents: &str) -> Result<Vec<ValidatedSpec>> {
+        let specs: Vec<Self> = serde_json::from_str(contents).map_err(|e| eyre::eyre!("{}", e))?;
+        if specs.is_empty() {
+            return Err(eyre::eyre!("at least one multisig spec is required"));
+        }
+
+        let mut validated: Vec<ValidatedSpec> = Vec::with_capacity(specs.len());
+        for (i, spec) in specs.iter().enumerate() {
+            let spec = spec.validate().map_err(|e| eyre::eyre!("[{}] {}", i, e))?;
+            if let Some(j) = validated.iter().position(|other| {
+                spec.label.is_some() && other.label == spec.label
+            }) {
+                return Err(eyre::eyre!("[{}] `label`: already used by [{}]", i, j));
+            }
+            if let Some(j) = validated.iter().position(|other| {
+                spec.create_key_seed.is_some() && other.create_key_seed == spec.create_key_seed
+            }) {
+                return Err(eyre::eyre!(
+                    "[{}] `create_key_seed`: already used by [{}], which would give the same \
+                     multisig address",
+                    i,
+                    j
+                ));
+            }
+            validated.push(spec);
+        }
+        Ok(validated)
+    }
+
+    fn parse(contents: &str, is_toml: bool) -> Result<Self> {
+        if is_toml {
+            toml::from_str(contents).map_err(|e| eyre::eyre!("{}", e))
+        } else {
+            serde_json::from_str(contents).map_err(|e| eyre::eyre!("{}", e))
+        }
+    }
+
+    /// Checks every field, naming the offending key in the error
+    pub fn validate(&self) -> Result<ValidatedSpec> {
+        if self.members.is_empty() {
+            return Err(eyre::eyre!("`members`: at least one member is required"));
+        }
+        let mut members: Vec<Member> = Vec::with_capacity(self.members.len());
+        for (i, member) in self.members.iter().enumerate() {
+            let key = parse_pubkey(&format!("members[{}].key", i), &member.key)?;
+            if members.iter().any(|m| m.key == key) {
+                return Err(eyre::eyre!("`members[{}].key`: {} is listed twice", i, key));
+            }
+            let permissions = match &member.permissions {
+                None => Permissions::from_actions(&Permission::ALL),
+                Some(names) => parse_permissions(&format!("members[{}].permissions", i), names)?,
+            };
+            members.push(Member { key, permissions });
+        }
+
+        let voters = members
+            .iter()
+            .filter(|member| member.permissions.has(Permission::Vote))
+            .count();
+        if self.threshold == 0 || usize::from(self.threshold) > voters {
+            return Err(eyre::eyre!(
+                "`threshold`: must be between 1 and the members with vote permission ({})",
+                voters
+            ));
+        }
+
+        if self.networks.is_empty() {
+            return Err(eyre::eyre!("`networks`: at least one RPC URL is required"));
+        }
+        let mut networks: Vec<String> = Vec::with_capacity(self.networks.len());
+        for (i, network) in self.networks.iter().enumerate() {
+            let url = network.trim();
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(eyre::eyre!(
+                    "`networks[{}]`: '{}' must start with http:// or https://",
+                    i,
+                    url
+                ));
+            }
+            if networks.iter().any(|n| n == url) {
+                return Err(eyre::eyre!("`networks[{}]`: {} is listed twice", i, url));
+            }
+            networks.push(url.to_string());
+        }
+
+        if let Some(label) = &self.label {
+            if label.trim().is_empty() || crate::utils::parse_pubkey(label).is_ok() {
+                return Err(eyre::eyre!("`label`: must be a non-empty name, not an address"));
+            }
+        }
+        if self.create_key_seed.as_deref().is_some_and(|seed| seed.is_empty()) {
+            return Err(eyre::eyre!("`create_key_seed`: must not be empty"));
+        }
+
+        Ok(ValidatedSpec {
+            threshold: self.threshold,
+            members,
+            networks,
+            time_lock: self.time_lock,
+            config_authority: self
+                .config_authority
+                .as_deref()
+                .map(|key| parse_pubkey("config_authority", key))
+                .transpose()?,
+            rent_collector: self
+                .rent_collector
+                .as_deref()
+                .map(|key| parse_pubkey("rent_collector", key))
+                .transpose()?,
+            create_key_seed: self.create_key_seed.clone(),
+            label: self.label.as_deref().map(|label| label.trim().to_string()),
+        })
+    }
+}
+
+fn parse_pubkey(field: &str, value: &str) -> Result<Pubkey> {
+    crate::utils::parse_pubkey(value)
+        .map_err(|_| eyre::eyre!("`{}`: invalid public key '{}'", field, value))
+}
+
+fn parse_permissions(field: &str, names: &[String]) -> Result<Permissions> {
+    if names.is_empty() {
+        return Err(eyre::eyre!("`{}`: at least one permission is required", field));
+    }
+    let actions = names
+        .iter()
+        .map(|name| {
+            Permission::ALL
+                .into_iter()
+                .find(|permission| permission.name().eq_ignore_ascii_case(name.trim()))
+                .ok_or_else(|| {
+                    eyre::eyre!(
+                        "`{}`: unknown permission '{}' (expected initiate, vote or execute)",
+                        field,
+                        name
+                    )
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Permissions::from_actions(&actions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec_json(threshold: u16, second_key: &str) -> String {
+        format!(
+            r#"{{
+                "threshold": {},
+                "members": [
+                    {{ "key": "{}" }},
+                    {{ "key": "{}", "permissions": ["Vote", "execute"] }},
+                    {{ "key": "{}", "permissions": ["initiate"] }}
+                ],
+                "networks": ["https://api.devnet.solana.com"],
+                "time_lock": 3600
+            }}"#,
+            threshold,
+            Pubkey::new_unique(),
+            second_key,
+            Pubkey::new_unique()
+        )
+    }
+
+    #[test]
+    fn test_validate_json_spec() {
+        let second = Pubkey::new_unique();
+        let spec = MultisigSpec::parse(&spec_json(2, &second.to_string()), false)
+            .unwrap()
+            .validate()
+            .unwrap();
+
+        assert_eq!(spec.threshold, 2);
+        assert_eq!(spec.time_lock, 3600);
+        assert_eq!(spec.members[0].permissions, Permissions::from_actions(&Permission::ALL));
+        assert_eq!(spec.members[1].key, second);
+        assert_eq!(
+            spec.members[1].permissions,
+            Permissions::from_actions(&[Permission::Vote, Permission::Execute])
+        );
+        assert_eq!(spec.config_authority, None);
+    }
+
+    #[test]
+    fn test_validate_toml_spec() {
+        let rent_collector = Pubkey::new_unique();
+        let toml = format!(
+            r#"
+            threshold = 1
+            networks = ["https://api.mainnet-beta.solana.com"]
+            rent_collector = "{}"
+
+            [[members]]
+            key = "{}"
+            "#,
+            rent_collector,
+            Pubkey::new_unique()
+        );
+        let spec = MultisigSpec::parse(&toml, true).unwrap().validate().unwrap();
+        assert_eq!(spec.rent_collector, Some(rent_collector));
+        assert_eq!(spec.time_lock, 0);
+    }
+
+    #[test]
+    fn test_validate_names_the_offending_key() {
+        let error = |spec: &str| {
+            MultisigSpec::parse(spec, false)
+                .and_then(|spec| spec.validate())
+                .map(|_| ())
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert!(error(&spec_json(2, "not-a-key")).contains("`members[1].key`"));
+        // Only two members can vote
+        assert!(error(&spec_json(3, &Pubkey::new_unique().to_string())).contains("`threshold`"));
+        assert!(error(&spec_json(2, &Pubkey::new_unique().to_string())
+            .replace("\"initiate\"", "\"admin\""))
+        .contains("`members[2].permissions`"));
+        assert!(error(r#"{"threshold": 1, "members": [], "networks": [], "quorum": 1}"#)
+            .contains("quorum"));
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let key = Pubkey::new_unique().to_string();
+        let entry = |extra: &str| {
+            format!(
+                r#"{{ "threshold": 1, "members": [{{ "key": "{}" }}],
+                    "networks": ["https://api.devnet.solana.com"]{} }}"#,
+                key, extra
+            )
+        };
+        let manifest = |entries: &[String]| format!("[{}]", entries.join(","));
+        let error = |contents: &str| {
+            MultisigSpec::parse_manifest(contents)
+                .map(|_| ())
+                .unwrap_err()
+                .to_string()
+        };
+
+        let specs = MultisigSpec::parse_manifest(&manifest(&[
+            entry(r#", "label": "core", "create_key_seed": "core-2026""#),
+            entry(r#", "label": " validators ""#),
+        ]))
+        .unwrap();
+        assert_eq!(specs.len(), 2);
+        assert_eq!(specs[0].create_key_seed.as_deref(), Some("core-2026"));
+        assert_eq!(specs[1].label.as_deref(), Some("validators"));
+        assert_eq!(specs[1].create_key_seed, None);
+
+        assert!(error("[]").contains("at least one"));
+        assert!(error(&manifest(&[entry(""), entry(r#", "label": """#)]))
+            .starts_with("[1] `label`"));
+        assert!(error(&manifest(&[
+            entry(r#", "create_key_seed": "same""#),
+            entry(r#", "create_key_seed": "same""#),
+        ]))
+        .contains("[1] `create_key_seed`: already used by [0]"));
+    }
+}
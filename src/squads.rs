@@ -13,8 +13,12 @@ pub const PROPOSAL_APPROVE_DISCRIMINATOR: &[u8] = &[144, 37, 164, 136, 188, 216,
 
 pub const PROPOSAL_REJECT_DISCRIMINATOR: &[u8] = &[243, 62, 134, 156, 230, 106, 246, 135];
 
+pub const PROPOSAL_ACTIVATE_DISCRIMINATOR: &[u8] = &[11, 34, 92, 248, 154, 27, 51, 106];
+
 pub const EXECUTE_TRANSACTION_DISCRIMINATOR: &[u8] = &[194, 8, 161, 87, 153, 164, 25, 171];
 
+pub const CLOSE_TRANSACTION_ACCOUNTS_DISCRIMINATOR: &[u8] = &[196, 71, 187, 176, 2, 35, 170, 165];
+
 pub const SQUADS_MULTISIG_PROGRAM_ID: Pubkey =
     Pubkey::from_str_const("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf");
 
@@ -25,6 +29,9 @@ pub const SEED_PROPOSAL: &[u8] = b"proposal";
 pub const SEED_TRANSACTION: &[u8] = b"transaction";
 pub const SEED_VAULT: &[u8] = b"vault";
 
+/// Anchor account discriminator of `Multisig` (sha256("account:Multisig")[..8])
+pub const MULTISIG_DISCRIMINATOR: &[u8] = &[224, 116, 121, 186, 68, 161, 79, 236];
+
 #[derive(BorshDeserialize, BorshSerialize, Eq, PartialEq, Clone)]
 pub struct Multisig {
     pub create_key: Pubkey,
@@ -38,28 +45,142 @@ pub struct Multisig {
     pub members: Vec<Member>,
 }
 
+impl Multisig {
+    /// Whether a config change has made the transaction at
+    /// `transaction_index` stale: proposals at or below the stale index can
+    /// no longer be voted on, though one already approved can still be
+    /// executed (see `ProposalStatus::is_stopped_when_stale`)
+    pub fn is_stale(&self, transaction_index: u64) -> bool {
+        transaction_index <= self.stale_transaction_index
+    }
+
+    /// Approvals `proposal` still needs to reach the threshold
+    pub fn approvals_needed(&self, proposal: &Proposal) -> usize {
+        usize::from(self.threshold).saturating_sub(proposal.approved.len())
+    }
+
+    /// Members with vote permission that haven't approved `proposal`
+    pub fn pending_approvers(&self, proposal: &Proposal) -> Vec<Pubkey> {
+        self.members
+            .iter()
+            .filter(|member| member.permissions.has(Permission::Vote))
+            .map(|member| member.key)
+            .filter(|key| !proposal.approved.contains(key))
+            .collect()
+    }
+
+    /// Whether `key` is a member with `permission`
+    pub fn has_permission(&self, key: &Pubkey, permission: Permission) -> bool {
+        self.members
+            .iter()
+            .any(|member| member.key == *key && member.permissions.has(permission))
+    }
+
+    /// Refuses a proposal creator without Initiate permission, which the
+    /// program would reject only after the fees are spent
+    pub fn ensure_can_initiate(&self, creator: &Pubkey) -> eyre::Result<()> {
+        if !self.has_permission(creator, Permission::Initiate) {
+            return Err(eyre::eyre!(
+                "creator {} lacks Initiate permission in this multisig",
+                creator
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether an account owned by `owner` holding `data` is a multisig of
+    /// `program_id`, as opposed to a wallet or another Squads account
+    pub fn is_multisig_account(owner: &Pubkey, data: &[u8], program_id: &Pubkey) -> bool {
+        owner == program_id && data.starts_with(MULTISIG_DISCRIMINATOR)
+    }
+
+    /// When `account_data` is a multisig account cut short (e.g. by an RPC's
+    /// response size limit), the length it needs at least, read from its rent
+    /// collector tag and member count. `None` when it is complete or isn't a
+    /// multisig account.
+    pub fn truncated_len(account_data: &[u8]) -> Option<usize> {
+        if !account_data.starts_with(MULTISIG_DISCRIMINATOR) {
+            return None;
+        }
+        // Discriminator, create_key, config_authority, threshold, time_lock,
+        // transaction_index, stale_transaction_index
+        const RENT_COLLECTOR_AT: usize = 8 + 32 + 32 + 2 + 4 + 8 + 8;
+        const MEMBER_LEN: usize = 32 + 1;
+
+        let required = match account_data.get(RENT_COLLECTOR_AT) {
+            None => RENT_COLLECTOR_AT + 1,
+            Some(tag) => {
+                let rent_collector_len = if *tag == 0 { 1 } else { 33 };
+                // After the rent collector and bump
+                let members_at = RENT_COLLECTOR_AT + rent_collector_len + 1;
+                match account_data.get(members_at..members_at + 4) {
+                    None => members_at + 4,
+                    Some(count) => {
+                        let count = u32::from_le_bytes(count.try_into().unwrap()) as usize;
+                        members_at + 4 + count * MEMBER_LEN
+                    }
+                }
+            }
+        };
+        (account_data.len() < required).then_some(required)
+    }
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Eq, PartialEq, Clone)]
 pub struct Member {
     pub key: Pubkey,
     pub permissions: Permissions,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Permission {
     Initiate = 1 << 0,
     Vote = 1 << 1,
     Execute = 1 << 2,
 }
 
+impl Permission {
+    /// Every permission, in mask bit order
+    pub const ALL: [Permission; 3] = [Permission::Initiate, Permission::Vote, Permission::Execute];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Permission::Initiate => "Initiate",
+            Permission::Vote => "Vote",
+            Permission::Execute => "Execute",
+        }
+    }
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Eq, PartialEq, Clone, Copy, Default, Debug)]
 pub struct Permissions {
     pub mask: u8,
 }
 
+impl Permissions {
+    pub fn from_actions(actions: &[Permission]) -> Self {
+        Self {
+            mask: actions.iter().fold(0, |mask, action| mask | *action as u8),
+        }
+    }
+
+    pub fn has(&self, action: Permission) -> bool {
+        self.mask & action as u8 != 0
+    }
+
+    /// The permissions set in the mask, in bit order; unknown bits are ignored
+    pub fn actions(&self) -> Vec<Permission> {
+        Permission::ALL
+            .into_iter()
+            .filter(|action| self.has(*action))
+            .collect()
+    }
+}
+
 pub const SEED_EPHEMERAL_SIGNER: &[u8] = b"ephemeral_signer";
 
-pub const SQUADS_MULTISIG_PROGRAM: Pubkey =
-    Pubkey::from_str_const("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf");
+/// Anchor account discriminator of `ProgramConfig` (sha256("account:ProgramConfig")[..8])
+pub const PROGRAM_CONFIG_DISCRIMINATOR: &[u8] = &[196, 210, 90, 231, 144, 149, 140, 63];
 
 #[derive(BorshDeserialize)]
 pub struct ProgramConfig {
@@ -68,7 +189,7 @@ pub struct ProgramConfig {
     pub treasury: Pubkey,
     pub _reserved: [u8; 64],
 }
-#[derive(BorshSerialize)]
+#[derive(BorshSerialize, Clone)]
 pub struct MultisigCreateArgsV2 {
     pub config_authority: Option<Pubkey>,
     pub threshold: u16,
@@ -401,6 +522,75 @@ impl MultisigApproveProposalData {
     }
 }
 
+pub struct MultisigRejectProposalData {
+    pub args: MultisigVoteOnProposalArgs,
+}
+
+impl MultisigRejectProposalData {
+    pub fn data(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(PROPOSAL_REJECT_DISCRIMINATOR);
+        data.extend_from_slice(&borsh::to_vec(&self.args).unwrap());
+        data
+    }
+}
+
+/// Accounts for `proposal_activate`, which opens voting on a draft proposal
+pub struct MultisigActivateProposalAccounts {
+    pub multisig: Pubkey,
+    pub member: Pubkey,
+    pub proposal: Pubkey,
+}
+
+impl MultisigActivateProposalAccounts {
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.multisig, false),
+            AccountMeta::new(self.member, true),
+            AccountMeta::new(self.proposal, false),
+        ]
+    }
+}
+
+pub struct MultisigActivateProposalData;
+
+impl MultisigActivateProposalData {
+    pub fn data(&self) -> Vec<u8> {
+        PROPOSAL_ACTIVATE_DISCRIMINATOR.to_vec()
+    }
+}
+
+/// Accounts for `vault_transaction_accounts_close`. Rent from the proposal and
+/// transaction accounts goes to `rent_collector`, which must be the one set on
+/// the multisig.
+pub struct MultisigCloseTransactionAccounts {
+    pub multisig: Pubkey,
+    pub proposal: Pubkey,
+    pub transaction: Pubkey,
+    pub rent_collector: Pubkey,
+    pub system_program: Pubkey,
+}
+
+impl MultisigCloseTransactionAccounts {
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.multisig, false),
+            AccountMeta::new(self.proposal, false),
+            AccountMeta::new(self.transaction, false),
+            AccountMeta::new(self.rent_collector, false),
+            AccountMeta::new_readonly(self.system_program, false),
+        ]
+    }
+}
+
+pub struct MultisigCloseTransactionData;
+
+impl MultisigCloseTransactionData {
+    pub fn data(&self) -> Vec<u8> {
+        CLOSE_TRANSACTION_ACCOUNTS_DISCRIMINATOR.to_vec()
+    }
+}
+
 // transaction wire structs
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub struct TransactionMessage {
@@ -419,6 +609,175 @@ pub struct TransactionMessage {
     pub address_table_lookups: SmallVec<u8, MessageAddressTableLookup>,
 }
 
+impl TransactionMessage {
+    /// Checks the header counts agree with `account_keys` and every instruction
+    /// only references accounts the message provides, mirroring the checks the
+    /// Squads program runs in `vault_transaction_create`
+    pub fn validate(&self) -> eyre::Result<()> {
+        let num_account_keys = self.account_keys.len();
+        let num_signers = usize::from(self.num_signers);
+
+        if num_signers > num_account_keys {
+            return Err(eyre::eyre!(
+                "Message declares {} signers but has only {} account keys",
+                num_signers,
+                num_account_keys
+            ));
+        }
+        if self.num_writable_signers > self.num_signers {
+            return Err(eyre::eyre!(
+                "Message declares {} writable signers but only {} signers",
+                self.num_writable_signers,
+                self.num_signers
+            ));
+        }
+        if usize::from(self.num_writable_non_signers) > num_account_keys - num_signers {
+            return Err(eyre::eyre!(
+                "Message declares {} writable non-signers but only {} non-signer account keys",
+                self.num_writable_non_signers,
+                num_account_keys - num_signers
+            ));
+        }
+
+        let num_all_account_keys = num_account_keys
+            + self
+                .address_table_lookups
+                .iter()
+                .map(|lookup| lookup.writable_indexes.len() + lookup.readonly_indexes.len())
+                .sum::<usize>();
+
+        for (i, instruction) in self.instructions.iter().enumerate() {
+            if usize::from(instruction.program_id_index) >= num_all_account_keys {
+                return Err(eyre::eyre!(
+                    "Instruction {} program id index {} is out of range ({} account keys)",
+                    i,
+                    instruction.program_id_index,
+                    num_all_account_keys
+                ));
+            }
+            if let Some(index) = instruction
+                .account_indexes
+                .iter()
+                .find(|&&index| usize::from(index) >= num_all_account_keys)
+            {
+                return Err(eyre::eyre!(
+                    "Instruction {} account index {} is out of range ({} account keys)",
+                    i,
+                    index,
+                    num_all_account_keys
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Compiles instructions into a `TransactionMessage`, working out the
+/// account order and header counts that are easy to get wrong by hand.
+/// Accounts are deduplicated (a key that is a signer or writable anywhere is
+/// so everywhere) and ordered writable signers, readonly signers, writable
+/// non-signers, readonly non-signers, each in order of first use.
+pub struct TransactionMessageBuilder {
+    authority: Pubkey,
+    instructions: Vec<solana_instruction::Instruction>,
+    accounts: Vec<AccountMeta>,
+}
+
+impl TransactionMessageBuilder {
+    /// `authority` signs and pays for the message (normally the vault), so
+    /// it is always the first account
+    pub fn new(authority: Pubkey) -> Self {
+        Self {
+            authority,
+            instructions: Vec::new(),
+            accounts: Vec::new(),
+        }
+    }
+
+    pub fn instructions(
+        mut self,
+        instructions: impl IntoIterator<Item = solana_instruction::Instruction>,
+    ) -> Self {
+        self.instructions.extend(instructions);
+        self
+    }
+
+    /// Lists an account in the message even if no instruction uses it
+    pub fn account(mut self, account: AccountMeta) -> Self {
+        self.accounts.push(account);
+        self
+    }
+
+    pub fn build(self) -> eyre::Result<TransactionMessage> {
+        // (key, is_signer, is_writable), in order of first use
+        let mut keys: Vec<(Pubkey, bool, bool)> = vec![(self.authority, true, true)];
+        let mut add = |key: Pubkey, is_signer: bool, is_writable: bool| {
+            match keys.iter_mut().find(|(existing, _, _)| *existing == key) {
+                Some((_, signer, writable)) => {
+                    *signer |= is_signer;
+                    *writable |= is_writable;
+                }
+                None => keys.push((key, is_signer, is_writable)),
+            }
+        };
+        for instruction in &self.instructions {
+            for meta in &instruction.accounts {
+                add(meta.pubkey, meta.is_signer, meta.is_writable);
+            }
+            add(instruction.program_id, false, false);
+        }
+        for meta in &self.accounts {
+            add(meta.pubkey, meta.is_signer, meta.is_writable);
+        }
+
+        // Stable, so each group keeps the order of first use
+        keys.sort_by_key(|&(_, is_signer, is_writable)| (!is_signer, !is_writable));
+        if keys.len() > usize::from(u8::MAX) {
+            return Err(eyre::eyre!(
+                "Message uses {} accounts; at most {} fit",
+                keys.len(),
+                u8::MAX
+            ));
+        }
+        let count = |group: (bool, bool)| {
+            keys.iter()
+                .filter(|&&(_, is_signer, is_writable)| (is_signer, is_writable) == group)
+                .count() as u8
+        };
+        let num_writable_signers = count((true, true));
+        let num_signers = num_writable_signers + count((true, false));
+        let num_writable_non_signers = count((false, true));
+
+        let account_keys: Vec<Pubkey> = keys.into_iter().map(|(key, _, _)| key).collect();
+        let index_of = |key: &Pubkey| account_keys.iter().position(|k| k == key).unwrap() as u8;
+        let instructions = self
+            .instructions
+            .into_iter()
+            .map(|instruction| CompiledInstruction {
+                program_id_index: index_of(&instruction.program_id),
+                account_indexes: SmallVec::from(
+                    instruction
+                        .accounts
+                        .iter()
+                        .map(|meta| index_of(&meta.pubkey))
+                        .collect::<Vec<_>>(),
+                ),
+                data: SmallVec::from(instruction.data),
+            })
+            .collect::<Vec<_>>();
+
+        Ok(TransactionMessage {
+            num_signers,
+            num_writable_signers,
+            num_writable_non_signers,
+            account_keys: SmallVec::from(account_keys),
+            instructions: SmallVec::from(instructions),
+            address_table_lookups: SmallVec::from(vec![]),
+        })
+    }
+}
+
 // Concise serialization schema for instructions that make up transaction.
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub struct CompiledInstruction {
@@ -480,6 +839,64 @@ pub enum ProposalStatus {
     Cancelled { timestamp: i64 },
 }
 
+impl ProposalStatus {
+    /// Variant name without the timestamp
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProposalStatus::Draft { .. } => "Draft",
+            ProposalStatus::Active { .. } => "Active",
+            ProposalStatus::Rejected { .. } => "Rejected",
+            ProposalStatus::Approved { .. } => "Approved",
+            #[allow(deprecated)]
+            ProposalStatus::Executing => "Executing",
+            ProposalStatus::Executed { .. } => "Executed",
+            ProposalStatus::Cancelled { .. } => "Cancelled",
+        }
+    }
+
+    /// Whether the Squads program lets its vault transaction accounts be
+    /// closed. Unapproved proposals are only closeable once a config change
+    /// has made them stale; an approved one never is, as it can still be
+    /// executed.
+    pub fn is_closeable(&self, is_stale: bool) -> bool {
+        match self {
+            ProposalStatus::Draft { .. } | ProposalStatus::Active { .. } => is_stale,
+            ProposalStatus::Rejected { .. }
+            | ProposalStatus::Executed { .. }
+            | ProposalStatus::Cancelled { .. } => true,
+            #[allow(deprecated)]
+            ProposalStatus::Approved { .. } | ProposalStatus::Executing => false,
+        }
+    }
+
+    /// Whether being stale stops the proposal for good. Squads refuses votes
+    /// on stale proposals, but still executes one that was already approved.
+    pub fn is_stopped_when_stale(&self) -> bool {
+        matches!(self, ProposalStatus::Draft { .. } | ProposalStatus::Active { .. })
+    }
+
+    /// Whether the proposal can no longer change status
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            ProposalStatus::Rejected { .. }
+                | ProposalStatus::Executed { .. }
+                | ProposalStatus::Cancelled { .. }
+        )
+    }
+
+    /// Seconds left at unix time `now` before the multisig's `time_lock` lets
+    /// an approved proposal execute, or `None` when it isn't approved
+    pub fn time_lock_remaining(&self, time_lock: u32, now: i64) -> Option<u64> {
+        match self {
+            ProposalStatus::Approved { timestamp } => {
+                Some((timestamp + i64::from(time_lock) - now).max(0) as u64)
+            }
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct SmallVec<L, T>(Vec<T>, PhantomData<L>);
 
@@ -594,3 +1011,286 @@ fn serialize_slice<T: BorshSerialize, W: Write>(data: &[T], writer: &mut W) -> s
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multisig_is_stale_at_or_below_stale_index() {
+        let multisig = Multisig {
+            create_key: Pubkey::new_unique(),
+            config_authority: Pubkey::default(),
+            threshold: 1,
+            time_lock: 0,
+            transaction_index: 5,
+            stale_transaction_index: 3,
+            rent_collector: None,
+            bump: 255,
+            members: vec![],
+        };
+        assert!(multisig.is_stale(1));
+        assert!(multisig.is_stale(3));
+        assert!(!multisig.is_stale(4));
+        assert!(!multisig.is_stale(5));
+    }
+
+    #[test]
+    fn test_approvals_needed_and_pending_approvers() {
+        let voter = |key| Member {
+            key,
+            permissions: Permissions::from_actions(&[Permission::Vote]),
+        };
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let multisig = Multisig {
+            create_key: Pubkey::new_unique(),
+            config_authority: Pubkey::default(),
+            threshold: 2,
+            time_lock: 0,
+            transaction_index: 1,
+            stale_transaction_index: 0,
+            rent_collector: None,
+            bump: 255,
+            members: vec![
+                voter(a),
+                voter(b),
+                voter(c),
+                Member {
+                    key: Pubkey::new_unique(),
+                    permissions: Permissions::from_actions(&[Permission::Initiate]),
+                },
+            ],
+        };
+        let mut proposal = Proposal {
+            multisig: Pubkey::new_unique(),
+            transaction_index: 1,
+            status: ProposalStatus::Active { timestamp: 0 },
+            bump: 255,
+            approved: vec![b],
+            rejected: vec![c],
+            cancelled: vec![],
+        };
+
+        assert!(multisig.has_permission(&a, Permission::Vote));
+        assert!(!multisig.has_permission(&a, Permission::Execute));
+        assert!(multisig.ensure_can_initiate(&multisig.members[3].key).is_ok());
+        assert_eq!(
+            multisig.ensure_can_initiate(&a).unwrap_err().to_string(),
+            format!("creator {} lacks Initiate permission in this multisig", a)
+        );
+
+        assert_eq!(multisig.approvals_needed(&proposal), 1);
+        // Initiate-only members can't approve
+        assert_eq!(multisig.pending_approvers(&proposal), vec![a, c]);
+
+        proposal.approved = vec![b, a, c];
+        assert_eq!(multisig.approvals_needed(&proposal), 0);
+        assert!(multisig.pending_approvers(&proposal).is_empty());
+    }
+
+    #[test]
+    fn test_transaction_message_builder_orders_and_counts_accounts() {
+        use solana_instruction::Instruction;
+
+        let (vault, program, signer, writable, readonly) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let instruction = |accounts| Instruction::new_with_bytes(program, &[7], accounts);
+        let message = TransactionMessageBuilder::new(vault)
+            .instructions([
+                instruction(vec![
+                    AccountMeta::new_readonly(readonly, false),
+                    AccountMeta::new_readonly(writable, false),
+                    AccountMeta::new_readonly(signer, true),
+                ]),
+                // Writable here, so writable everywhere; the vault isn't repeated
+                instruction(vec![
+                    AccountMeta::new(writable, false),
+                    AccountMeta::new(vault, true),
+                ]),
+            ])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            Vec::from(message.account_keys.clone()),
+            vec![vault, signer, writable, readonly, program]
+        );
+        assert_eq!(message.num_signers, 2);
+        assert_eq!(message.num_writable_signers, 1);
+        assert_eq!(message.num_writable_non_signers, 1);
+        assert_eq!(Vec::from(message.instructions[0].account_indexes.clone()), vec![3, 2, 1]);
+        assert_eq!(Vec::from(message.instructions[1].account_indexes.clone()), vec![2, 0]);
+        assert_eq!(message.instructions[1].program_id_index, 4);
+        message.validate().unwrap();
+    }
+
+    #[test]
+    fn test_permissions_for_every_mask() {
+        use Permission::*;
+        let expected: [&[Permission]; 8] = [
+            &[],
+            &[Initiate],
+            &[Vote],
+            &[Initiate, Vote],
+            &[Execute],
+            &[Initiate, Execute],
+            &[Vote, Execute],
+            &[Initiate, Vote, Execute],
+        ];
+        for (mask, actions) in expected.iter().enumerate() {
+            let permissions = Permissions { mask: mask as u8 };
+            assert_eq!(permissions.actions(), *actions, "mask {}", mask);
+            assert_eq!(Permissions::from_actions(actions), permissions, "mask {}", mask);
+            for action in Permission::ALL {
+                assert_eq!(permissions.has(action), actions.contains(&action), "mask {}", mask);
+            }
+        }
+        // Bits beyond Execute aren't permissions
+        assert_eq!(Permissions { mask: 0b1111_1000 }.actions(), vec![]);
+    }
+
+    fn message(
+        num_signers: u8,
+        num_writable_signers: u8,
+        num_writable_non_signers: u8,
+    ) -> TransactionMessage {
+        TransactionMessage {
+            num_signers,
+            num_writable_signers,
+            num_writable_non_signers,
+            account_keys: SmallVec::from(vec![
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ]),
+            instructions: SmallVec::from(vec![CompiledInstruction {
+                program_id_index: 2,
+                account_indexes: SmallVec::from(vec![0, 1]),
+                data: SmallVec::from(vec![]),
+            }]),
+            address_table_lookups: SmallVec::from(vec![]),
+        }
+    }
+
+    #[test]
+    fn test_truncated_len() {
+        let member = |byte| Member {
+            key: Pubkey::new_from_array([byte; 32]),
+            permissions: Permissions::from_actions(&Permission::ALL),
+        };
+        let multisig = Multisig {
+            create_key: Pubkey::new_unique(),
+            config_authority: Pubkey::default(),
+            threshold: 2,
+            time_lock: 0,
+            transaction_index: 0,
+            stale_transaction_index: 0,
+            rent_collector: Some(Pubkey::new_unique()),
+            bump: 255,
+            members: (1..=3).map(member).collect(),
+        };
+        let mut data = MULTISIG_DISCRIMINATOR.to_vec();
+        data.extend(borsh::to_vec(&multisig).unwrap());
+        let full_len = data.len();
+
+        assert_eq!(Multisig::truncated_len(&data), None);
+        // Pre-allocated member slots leave padding, which isn't truncation
+        let mut padded = data.clone();
+        padded.extend([0; 66]);
+        assert_eq!(Multisig::truncated_len(&padded), None);
+
+        // Cut inside the member list, before the member count, and in the header
+        assert_eq!(Multisig::truncated_len(&data[..full_len - 10]), Some(full_len));
+        assert_eq!(Multisig::truncated_len(&data[..120]), Some(8 + 86 + 33 + 1 + 4));
+        assert_eq!(Multisig::truncated_len(&data[..50]), Some(8 + 86 + 1));
+
+        // Other accounts aren't multisigs
+        assert_eq!(Multisig::truncated_len(&data[8..]), None);
+        assert_eq!(Multisig::truncated_len(&[]), None);
+    }
+
+    #[test]
+    fn test_is_multisig_account() {
+        let program_id = SQUADS_MULTISIG_PROGRAM_ID;
+        let mut data = MULTISIG_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[0; 32]);
+
+        assert!(Multisig::is_multisig_account(&program_id, &data, &program_id));
+        // A wallet, or a Squads account of another type
+        let system_program = solana_system_interface::program::ID;
+        assert!(!Multisig::is_multisig_account(&system_program, &[], &program_id));
+        assert!(!Multisig::is_multisig_account(
+            &program_id,
+            PROGRAM_CONFIG_DISCRIMINATOR,
+            &program_id
+        ));
+    }
+
+    #[test]
+    fn test_time_lock_remaining() {
+        let approved = ProposalStatus::Approved { timestamp: 1_000 };
+        assert_eq!(approved.time_lock_remaining(0, 1_000), Some(0));
+        assert_eq!(approved.time_lock_remaining(60, 1_030), Some(30));
+        assert_eq!(approved.time_lock_remaining(60, 1_060), Some(0));
+        assert_eq!(approved.time_lock_remaining(60, 2_000), Some(0));
+        assert_eq!(ProposalStatus::Active { timestamp: 0 }.time_lock_remaining(0, 1_000), None);
+    }
+
+    #[test]
+    fn test_proposal_is_closeable() {
+        let active = ProposalStatus::Active { timestamp: 0 };
+        assert!(!active.is_closeable(false));
+        assert!(active.is_closeable(true));
+        assert!(ProposalStatus::Executed { timestamp: 0 }.is_closeable(false));
+        assert!(ProposalStatus::Rejected { timestamp: 0 }.is_closeable(false));
+        // Still executable, so Squads refuses to close it even when stale
+        let approved = ProposalStatus::Approved { timestamp: 0 };
+        assert!(!approved.is_closeable(false));
+        assert!(!approved.is_closeable(true));
+    }
+
+    #[test]
+    fn test_validate_accepts_consistent_counts() {
+        assert!(message(1, 1, 1).validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_miscounted_writable_signer() {
+        let err = message(1, 2, 1).validate().unwrap_err();
+        assert!(err.to_string().contains("writable signers"));
+    }
+
+    #[test]
+    fn test_validate_rejects_miscounted_non_signers() {
+        assert!(message(4, 1, 0).validate().is_err());
+        assert!(message(1, 1, 3).validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range_account_index() {
+        let mut message = message(1, 1, 1);
+        message.instructions = SmallVec::from(vec![CompiledInstruction {
+            program_id_index: 2,
+            account_indexes: SmallVec::from(vec![0, 3]),
+            data: SmallVec::from(vec![]),
+        }]);
+        assert!(message.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range_program_id_index() {
+        let mut message = message(1, 1, 1);
+        message.instructions = SmallVec::from(vec![CompiledInstruction {
+            program_id_index: 3,
+            account_indexes: SmallVec::from(vec![0, 1]),
+            data: SmallVec::from(vec![]),
+        }]);
+        let err = message.validate().unwrap_err();
+        assert!(err.to_string().contains("program id index"));
+    }
+}
@@ -1,33 +1,97 @@
 use crate::constants::*;
-use crate::feature_gate_program::{activate_feature_funded, create_feature_activation};
-use crate::provision::create_rpc_client;
-use crate::squads::{get_vault_pda, CompiledInstruction, Member, Permissions, TransactionMessage};
+use crate::error::ToolError;
+use crate::feature_gate_program::activate_feature_funded;
+use crate::network::NetworkCluster;
+use crate::output::Output;
+use crate::provision::{create_rpc_client, RetryBudget, TransactionOptions};
+use crate::squads::{
+    get_multisig_pda, get_vault_pda, CompiledInstruction, Member, Permission, Permissions,
+    TransactionMessage, TransactionMessageBuilder,
+};
 use colored::*;
 use dirs;
-use eyre::Result;
-use indicatif::ProgressBar;
+use eyre::{Result, WrapErr};
 use inquire::{Confirm, Select, Text};
 use serde::{Deserialize, Serialize};
+use solana_client::rpc_client::RpcClient;
+use solana_hash::Hash;
+use solana_instruction::AccountMeta;
 use solana_keypair::Keypair;
 use solana_message::VersionedMessage;
 use solana_pubkey::Pubkey;
-use solana_signer::{EncodableKey, Signer};
+use solana_signer::Signer;
 use solana_transaction::versioned::VersionedTransaction;
 use std::fmt::Display;
 use std::fs;
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::time::Duration;
+use zeroize::Zeroizing;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub threshold: u16,
+    /// Either a plain pubkey (`default_member_permissions`) or `pubkey,mask`
     #[serde(default)]
     pub members: Vec<String>,
+    /// Permission mask (0-7) of members saved as a plain pubkey
+    #[serde(default = "default_member_permissions")]
+    pub default_member_permissions: u8,
     #[serde(default)]
     pub networks: Vec<String>,
     #[serde(default)]
     pub fee_payer_path: Option<String>,
+    /// Multisigs created by this tool, one entry per network deployed to
+    #[serde(default)]
+    pub multisigs: Vec<SavedMultisig>,
+    /// RPC requests per second, for free-tier RPCs (unlimited when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rps: Option<u32>,
+    /// Single network from configs written before `networks`; folded into
+    /// `networks` on load and never written back, so it can't go stale
+    #[serde(default, skip_serializing)]
+    network: Option<String>,
+}
+
+/// A created multisig remembered in the config so it can be found by label
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedMultisig {
+    pub address: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    pub network: String,
+}
+
+impl Config {
+    /// Label given to `address` at creation, if any
+    pub fn label_for(&self, address: &Pubkey) -> Option<&str> {
+        let address = address.to_string();
+        self.multisigs
+            .iter()
+            .find(|saved| saved.address == address && saved.label.is_some())
+            .and_then(|saved| saved.label.as_deref())
+    }
+
+    /// Accepts either a multisig address or the label of a saved multisig
+    pub fn resolve_multisig(&self, address_or_label: &str) -> Result<Pubkey> {
+        let input = address_or_label.trim();
+        if let Ok(pubkey) = parse_pubkey(input) {
+            return Ok(pubkey);
+        }
+        let saved = self
+            .multisigs
+            .iter()
+            .find(|saved| saved.label.as_deref() == Some(input))
+            .ok_or_else(|| {
+                eyre::eyre!("Invalid multisig address format or unknown label: {}", input)
+            })?;
+        parse_pubkey(&saved.address)
+            .map_err(|_| eyre::eyre!("Saved multisig {} has an invalid address", input))
+    }
+}
+
+fn default_member_permissions() -> u8 {
+    Permissions::from_actions(&Permission::ALL).mask
 }
 
 impl Default for Config {
@@ -35,8 +99,12 @@ impl Default for Config {
         Self {
             threshold: 1,
             members: Vec::new(),
+            default_member_permissions: default_member_permissions(),
             networks: vec![DEFAULT_DEVNET_URL.to_string()],
             fee_payer_path: None,
+            multisigs: Vec::new(),
+            rps: None,
+            network: None,
         }
     }
 }
@@ -47,11 +115,13 @@ pub struct DeploymentResult {
     pub multisig_address: Pubkey,
     pub vault_address: Pubkey,
     pub transaction_signature: String,
+    pub fee_payer: Pubkey,
 }
 
 // Config management functions
 pub fn get_config_path() -> Result<PathBuf> {
-    let home_dir = dirs::home_dir().ok_or_else(|| eyre::eyre!("Could not find home directory"))?;
+    let home_dir = dirs::home_dir()
+        .ok_or_else(|| ToolError::Config("Could not find home directory".to_string()))?;
     Ok(home_dir
         .join(".feature-gate-multisig-tool")
         .join("config.json"))
@@ -67,45 +137,216 @@ pub fn load_config() -> Result<Config> {
     }
 
     let config_str = fs::read_to_string(&config_path)
-        .map_err(|e| eyre::eyre!("Failed to read config file: {}", e))?;
+        .map_err(|e| ToolError::Config(format!("Failed to read config file: {}", e)))?;
+
+    parse_config(&config_str)
+}
 
-    let mut config: Config = serde_json::from_str(&config_str)
-        .map_err(|e| eyre::eyre!("Failed to parse config file: {}", e))?;
+/// Parses a saved config, migrating the legacy single `network` into
+/// `networks` so callers can rely on at least one network being present
+fn parse_config(config_str: &str) -> Result<Config> {
+    let mut config: Config = serde_json::from_str(config_str)
+        .map_err(|e| ToolError::Config(format!("Failed to parse config file: {}", e)))?;
+
+    if let Some(legacy) = config.network.take() {
+        if config.networks.is_empty() {
+            config.networks.push(legacy);
+        }
+    }
+    if config.networks.is_empty() {
+        config.networks.push(DEFAULT_DEVNET_URL.to_string());
+    }
+    if config.default_member_permissions > default_member_permissions() {
+        return Err(ToolError::Config(format!(
+            "default_member_permissions must be a permission mask from 0 to 7, got {}",
+            config.default_member_permissions
+        ))
+        .into());
+    }
+    if config.rps == Some(0) {
+        return Err(
+            ToolError::Config("rps must be at least 1 request per second".to_string()).into(),
+        );
+    }
+    let duplicates = dedup_networks(&mut config.networks);
+    if !duplicates.is_empty() {
+        Output::warning(&format!(
+            "Ignoring duplicate networks in config: {}",
+            duplicates.join(", ")
+        ));
+    }
 
     Ok(config)
 }
 
+/// Drops repeated network URLs (ignoring surrounding whitespace and a trailing
+/// slash), keeping the first occurrence so the deployment order is unchanged.
+/// Returns the entries removed.
+fn dedup_networks(networks: &mut Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    let mut duplicates = Vec::new();
+    networks.retain(|url| {
+        let unique = seen.insert(url.trim().trim_end_matches('/').to_string());
+        if !unique {
+            duplicates.push(url.clone());
+        }
+        unique
+    });
+    duplicates
+}
+
 pub fn save_config(config: &Config) -> Result<()> {
     let config_path = get_config_path()?;
 
     if let Some(parent) = config_path.parent() {
         fs::create_dir_all(parent)
-            .map_err(|e| eyre::eyre!("Failed to create config directory: {}", e))?;
+            .map_err(|e| ToolError::Config(format!("Failed to create config directory: {}", e)))?;
     }
 
     let config_str = serde_json::to_string_pretty(config)
         .map_err(|e| eyre::eyre!("Failed to serialize config: {}", e))?;
 
     fs::write(&config_path, config_str)
-        .map_err(|e| eyre::eyre!("Failed to write config file: {}", e))?;
+        .map_err(|e| ToolError::Config(format!("Failed to write config file: {}", e)))?;
 
     Ok(())
 }
 
+/// Reads a config shared by `config export`, applying the same migration as
+/// the local config
+pub fn read_config_file(path: &Path) -> Result<Config> {
+    let config_str = fs::read_to_string(path).map_err(|e| {
+        ToolError::Config(format!("Failed to read config {}: {}", path.display(), e))
+    })?;
+    parse_config(&config_str)
+}
+
+/// A difference between the local config and an imported one that needs a
+/// decision before merging
+#[derive(Debug, PartialEq, Eq)]
+pub enum ConfigConflict {
+    /// The same member saved with different permissions
+    MemberPermissions { member: String, local: String, imported: String },
+    /// The same label given to different multisigs
+    Label { label: String, local: String, imported: String },
+}
+
+/// Merges `imported` into `local`: networks, members and saved multisigs are
+/// unioned and the imported threshold is taken. Each conflict is settled by
+/// `prefer_imported`; the local fee payer and default member permissions are
+/// always kept, imported plain members being given the imported default.
+pub fn merge_config(
+    local: &Config,
+    imported: Config,
+    mut prefer_imported: impl FnMut(&ConfigConflict) -> bool,
+) -> Config {
+    // Saved members are `pubkey` (the config's default permissions) or `pubkey,mask`
+    let member_key =
+        |member: &str| member.split(',').next().unwrap_or_default().trim().to_string();
+    let local_default = local.default_member_permissions.to_string();
+    let member_mask = |member: &str| {
+        member.split(',').nth(1).unwrap_or(&local_default).trim().to_string()
+    };
+    let imported_default = imported.default_member_permissions;
+
+    let mut merged = local.clone();
+    merged.threshold = imported.threshold;
+
+    for network in imported.networks {
+        if !merged.networks.contains(&network) {
+            merged.networks.push(network);
+        }
+    }
+
+    for member in imported.members {
+        let member = if member.contains(',') || imported_default == local.default_member_permissions
+        {
+            member
+        } else {
+            format!("{},{}", member.trim(), imported_default)
+        };
+        let key = member_key(&member);
+        match merged.members.iter_mut().find(|existing| member_key(existing) == key) {
+            None => merged.members.push(member),
+            Some(existing) if member_mask(existing) == member_mask(&member) => {}
+            Some(existing) => {
+                let conflict = ConfigConflict::MemberPermissions {
+                    member: key,
+                    local: member_mask(existing),
+                    imported: member_mask(&member),
+                };
+                if prefer_imported(&conflict) {
+                    *existing = member;
+                }
+            }
+        }
+    }
+
+    for saved in imported.multisigs {
+        if merged
+            .multisigs
+            .iter()
+            .any(|existing| existing.address == saved.address && existing.network == saved.network)
+        {
+            continue;
+        }
+        let clashing_label = saved.label.as_ref().and_then(|label| {
+            merged
+                .multisigs
+                .iter()
+                .position(|existing| existing.label.as_ref() == Some(label))
+        });
+        match clashing_label {
+            None => merged.multisigs.push(saved),
+            Some(index) => {
+                let conflict = ConfigConflict::Label {
+                    label: saved.label.clone().unwrap_or_default(),
+                    local: merged.multisigs[index].address.clone(),
+                    imported: saved.address.clone(),
+                };
+                if prefer_imported(&conflict) {
+                    merged.multisigs[index].label = None;
+                    merged.multisigs.push(saved);
+                } else {
+                    merged.multisigs.push(SavedMultisig { label: None, ..saved });
+                }
+            }
+        }
+    }
+
+    merged
+}
+
 // Member management functions
+
+/// Sorts members by pubkey (`--members-sort`), so the review and summary list
+/// them in the order Squads stores them on-chain rather than in entry order.
+/// The setup key stays recognisable by its Initiate-only permissions.
+pub fn sort_members(members: &mut [Member]) {
+    members.sort_by_key(|member| member.key);
+}
+
+/// The config's saved members; plain pubkeys get `default_member_permissions`
 pub fn parse_saved_members(config: &Config) -> Vec<Member> {
     let mut parsed_members = Vec::new();
     for member_str in &config.members {
-        match Pubkey::from_str(member_str) {
-            Ok(pubkey) => {
-                parsed_members.push(Member {
-                    key: pubkey,
-                    permissions: Permissions { mask: 7 }, // Full permissions for saved members
-                });
-            }
-            Err(_) => {
+        let parsed = if member_str.contains(',') {
+            crate::provision::parse_members(vec![member_str.clone()])
+                .ok()
+                .and_then(|members| members.into_iter().next())
+        } else {
+            parse_pubkey(member_str).ok().map(|pubkey| Member {
+                key: pubkey,
+                permissions: Permissions {
+                    mask: config.default_member_permissions,
+                },
+            })
+        };
+        match parsed {
+            Some(member) => parsed_members.push(member),
+            None => {
                 println!(
-                    "  {} Invalid saved member key: {}, skipping...",
+                    "  {} Invalid saved member: {}, skipping...",
                     "⚠️".bright_yellow(),
                     member_str
                 );
@@ -115,6 +356,17 @@ pub fn parse_saved_members(config: &Config) -> Vec<Member> {
     parsed_members
 }
 
+/// Inverse of `parse_saved_members`: members with the config's
+/// `default_permissions` are saved as a plain pubkey, anything else as
+/// `pubkey,mask`
+pub fn format_saved_member(member: &Member, default_permissions: u8) -> String {
+    if member.permissions.mask == default_permissions {
+        member.key.to_string()
+    } else {
+        format!("{},{}", member.key, member.permissions.mask)
+    }
+}
+
 pub fn parse_saved_threshold(config: &Config) -> Option<u16> {
     if config.threshold > 0 {
         Some(config.threshold)
@@ -123,7 +375,27 @@ pub fn parse_saved_threshold(config: &Config) -> Option<u16> {
     }
 }
 
-pub fn collect_members_interactively() -> Result<Vec<Member>> {
+/// The running "members: N, current threshold default: T (valid)" line shown
+/// while members are added, and whether `threshold` is reachable by the
+/// `voters` added so far (the setup member only initiates, so doesn't count)
+fn member_preview(voters: usize, threshold: u16) -> (String, bool) {
+    let valid = threshold >= 1 && usize::from(threshold) <= voters;
+    let status = if valid {
+        "valid".to_string()
+    } else if threshold == 0 {
+        "invalid: must be at least 1".to_string()
+    } else {
+        format!("invalid: needs {} more member(s)", usize::from(threshold) - voters)
+    };
+    (
+        format!("members: {}, current threshold default: {} ({})", voters, threshold, status),
+        valid,
+    )
+}
+
+/// Prompts for members one at a time, showing after each whether
+/// `threshold_default` is reachable yet
+pub fn collect_members_interactively(threshold_default: u16) -> Result<Vec<Member>> {
     let mut interactive_members = Vec::new();
 
     loop {
@@ -137,7 +409,7 @@ pub fn collect_members_interactively() -> Result<Vec<Member>> {
             Ok(member_key) => {
                 interactive_members.push(Member {
                     key: member_key,
-                    permissions: Permissions { mask: 7 },
+                    permissions: Permissions::from_actions(&Permission::ALL),
                 });
                 println!(
                     "  {} Added member: {} ({})",
@@ -145,6 +417,13 @@ pub fn collect_members_interactively() -> Result<Vec<Member>> {
                     member_key.to_string().bright_white(),
                     "Initiate, Vote, Execute".bright_cyan()
                 );
+                let (preview, valid) =
+                    member_preview(interactive_members.len(), threshold_default);
+                if valid {
+                    println!("    {}", preview.dimmed());
+                } else {
+                    println!("    {} {}", "⚠️".bright_yellow(), preview.bright_yellow());
+                }
             }
             Err(e) => {
                 println!(
@@ -170,32 +449,43 @@ pub fn review_and_collect_configuration(
         let parsed_members = parse_saved_members(config);
         Ok((config.threshold, parsed_members))
     } else {
-        println!(
+        Output::plain(&format!(
             "{} Collecting configuration interactively",
             "🔄".bright_cyan()
-        );
+        ));
 
         // First collect members
-        let interactive_members = collect_members_interactively()?;
-
-        // Then get threshold based on member count
+        let interactive_members =
+            collect_members_interactively(threshold.unwrap_or(config.threshold))?;
+
+        // Then get threshold based on member count. The setup member only
+        // initiates, so like the running preview only the added voters count.
+        let voters = interactive_members.len();
+        if voters == 0 {
+            return Err(ToolError::Config(
+                "At least one member is required to vote on proposals".to_string(),
+            )
+            .into());
+        }
         let final_threshold =
             if let Some(t) = threshold {
                 // Validate CLI threshold against member count
-                let max_members = interactive_members.len() + 1; // +1 for contributor
-                if t as usize > max_members {
-                    println!(
+                if t as usize > voters {
+                    Output::plain(&format!(
                     "  {} CLI threshold ({}) exceeds member count ({}), prompting for new value",
-                    "⚠️".bright_yellow(), t, max_members
-                );
-                    prompt_for_threshold_with_max(max_members)?
+                    "⚠️".bright_yellow(), t, voters
+                ));
+                    prompt_for_threshold_with_max(voters)?
                 } else {
-                    println!("  {} Using threshold from CLI: {}", "✓".bright_green(), t);
+                    Output::plain(&format!(
+                        "  {} Using threshold from CLI: {}",
+                        "✓".bright_green(),
+                        t
+                    ));
                     t
                 }
             } else {
-                let max_members = interactive_members.len() + 1; // +1 for contributor
-                prompt_for_threshold_with_max(max_members)?
+                prompt_for_threshold_with_max(voters)?
             };
 
         Ok((final_threshold, interactive_members))
@@ -212,29 +502,109 @@ pub fn expand_tilde_path(path: &str) -> Result<String> {
     }
 }
 
+/// Reads a JSON keypair file, scrubbing the raw file contents and decoded
+/// secret bytes from memory once the `Keypair` has been constructed.
+///
+/// The `Keypair` itself zeroizes its secret key on drop (ed25519-dalek), so
+/// only the intermediate buffers need explicit handling here.
+pub fn read_keypair_file(path: &str) -> Result<Keypair> {
+    let contents = Zeroizing::new(fs::read_to_string(path)?);
+    let bytes: Zeroizing<Vec<u8>> = Zeroizing::new(serde_json::from_str(contents.trim())?);
+    Keypair::try_from(bytes.as_slice()).map_err(|e| eyre::eyre!("Invalid keypair data: {}", e))
+}
+
+/// Domain separator for the create key derived from a `--create-key-seed`
+pub const CREATE_KEY_SEED_DOMAIN: &str = "feature-gate-multisig-tool:create-key";
+/// Domain separator for the contributor derived from a `--create-key-seed`
+pub const CONTRIBUTOR_SEED_DOMAIN: &str = "feature-gate-multisig-tool:contributor";
+
+/// PBKDF2 rounds for seed-derived keys, OWASP's recommendation for
+/// HMAC-SHA256, so guessing a weak seed costs as much as it can afford to
+pub const SEED_KDF_ROUNDS: u32 = 600_000;
+
+/// Derives the keypair whose ed25519 secret key is
+/// `PBKDF2-HMAC-SHA256(seed, salt = domain, SEED_KDF_ROUNDS)`. Separate
+/// domains give unrelated keys from one seed, so the create key and
+/// contributor can both be rebuilt from it without one revealing the other.
+pub fn derive_keypair_from_seed(domain: &str, seed: &str) -> Keypair {
+    let mut secret_key = Zeroizing::new([0u8; 32]);
+    pbkdf2::pbkdf2::<hmac::Hmac<sha2::Sha256>>(
+        seed.as_bytes(),
+        domain.as_bytes(),
+        SEED_KDF_ROUNDS,
+        secret_key.as_mut(),
+    );
+    Keypair::new_from_array(*secret_key)
+}
+
 pub fn load_fee_payer_keypair(
     config: &Config,
     keypair_path: Option<String>,
 ) -> Result<Option<Keypair>> {
     if let Some(path) = keypair_path {
-        let keypair = Keypair::read_from_file(&path)
+        let keypair = read_keypair_file(&path)
             .map_err(|e| eyre::eyre!("Failed to load keypair from {}: {}", path, e))?;
         Ok(Some(keypair))
     } else if let Some(path) = &config.fee_payer_path {
-        println!(
+        Output::plain(&format!(
             "{} Loading fee payer keypair from config: {}",
             "💰".bright_blue(),
             path.bright_white()
-        );
-        let keypair = Keypair::read_from_file(path)
+        ));
+        let keypair = read_keypair_file(path)
             .map_err(|e| eyre::eyre!("Failed to load keypair from config path {}: {}", path, e))?;
         Ok(Some(keypair))
     } else {
-        println!("{} No fee payer keypair provided", "⚠️".bright_yellow());
+        Output::plain(&format!("{} No fee payer keypair provided", "⚠️".bright_yellow()));
         Ok(None)
     }
 }
 
+/// Loads every `*.json` keypair in `dir`, sorted by file name so the
+/// round-robin order across networks is stable between runs
+pub fn load_fee_payer_keypairs_from_dir(dir: &Path) -> Result<Vec<Keypair>> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| eyre::eyre!("Failed to read fee payer directory {}: {}", dir.display(), e))?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    let keypairs = paths
+        .iter()
+        .map(|path| {
+            read_keypair_file(&path.to_string_lossy())
+                .map_err(|e| eyre::eyre!("Failed to load keypair from {}: {}", path.display(), e))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if keypairs.is_empty() {
+        return Err(eyre::eyre!("No *.json keypairs found in {}", dir.display()));
+    }
+
+    println!(
+        "{} Rotating across {} fee payer keypairs from {}",
+        "💰".bright_blue(),
+        keypairs.len(),
+        dir.display().to_string().bright_white()
+    );
+    Ok(keypairs)
+}
+
+/// Fails fast when a flow needs prompts but stdin isn't a terminal (e.g. piped
+/// input in CI), rather than hanging or misreading the pipe. `hint` names the
+/// flags that avoid the prompts.
+pub fn ensure_interactive(hint: &str) -> Result<()> {
+    if std::io::stdin().is_terminal() {
+        return Ok(());
+    }
+    Err(ToolError::Config(format!(
+        "interactive prompt needed but stdin is not a terminal; {}",
+        hint
+    ))
+    .into())
+}
+
 // CLI input helpers
 pub fn prompt_for_threshold(config: &Config) -> Result<u16> {
     loop {
@@ -242,8 +612,7 @@ pub fn prompt_for_threshold(config: &Config) -> Result<u16> {
             "Enter threshold (required signatures) [{}]:",
             config.threshold
         ))
-        .prompt()
-        .unwrap_or_default();
+        .prompt()?;
 
         match validate_threshold(&input, 10, config.threshold) {
             Ok(t) => return Ok(t),
@@ -261,8 +630,7 @@ pub fn prompt_for_threshold_with_max(max_members: usize) -> Result<u16> {
             "Enter threshold (required signatures) [max: {}]:",
             max_members
         ))
-        .prompt()
-        .unwrap_or_default();
+        .prompt()?;
 
         match validate_threshold(&input, max_members, 1) {
             Ok(t) => return Ok(t),
@@ -276,7 +644,7 @@ pub fn prompt_for_threshold_with_max(max_members: usize) -> Result<u16> {
 
 pub fn prompt_for_pubkey(prompt: &str) -> Result<Pubkey> {
     let input = Text::new(prompt).prompt()?;
-    match Pubkey::from_str(&input) {
+    match parse_pubkey(&input) {
         Ok(pubkey) => Ok(pubkey),
         Err(_) => {
             println!(
@@ -315,21 +683,48 @@ pub fn prompt_for_network(config: &Config) -> Result<String> {
                 println!("  {} {}", "❌".bright_red(), e.to_string().bright_red());
                 let retry = Confirm::new("Try again?").with_default(true).prompt()?;
                 if !retry {
-                    return Err(eyre::eyre!("User cancelled network entry"));
+                    return Err(ToolError::Cancelled("User cancelled network entry".into()).into());
                 }
             }
         }
     }
 }
 
+/// Prompts for RPC URLs until the user is done, for flows that need every
+/// network before deploying anything
+pub fn collect_networks_interactively(config: &Config) -> Result<Vec<String>> {
+    let mut networks = Vec::new();
+    loop {
+        let network = prompt_for_network(config)?;
+        if networks.contains(&network) {
+            println!("  {} Network already added", "⚠️".bright_yellow());
+        } else {
+            networks.push(network);
+        }
+
+        let add_another = Confirm::new("Add another network?")
+            .with_default(false)
+            .prompt()?;
+        if !add_another {
+            return Ok(networks);
+        }
+    }
+}
+
 // Display functions
+/// Shows what is about to be deployed, including the multisig and vault
+/// addresses `create_key` gives on every network, before any is touched
 pub fn display_final_configuration(
     contributor_pubkey: &Pubkey,
     create_key: &Pubkey,
-    fee_payer_keypair: &Option<Keypair>,
+    program_id: &Pubkey,
+    fee_payers: &[Option<Keypair>],
     threshold: u16,
     members: &[Member],
 ) {
+    let multisig = get_multisig_pda(create_key, Some(program_id)).0;
+    let vault = get_vault_pda(&multisig, 0, Some(program_id)).0;
+
     println!("\n{}", "📋 Final Configuration:".bright_yellow().bold());
     println!(
         "  {}: {}",
@@ -341,18 +736,32 @@ pub fn display_final_configuration(
         "Create key".cyan(),
         create_key.to_string().bright_white()
     );
-    if let Some(fee_payer) = fee_payer_keypair {
-        println!(
+    println!(
+        "  {}: {} (same on every network)",
+        "Multisig".cyan(),
+        multisig.to_string().bright_white()
+    );
+    println!(
+        "  {}: {}",
+        "Vault / Feature Gate ID".cyan(),
+        vault.to_string().bright_white()
+    );
+    match fee_payers {
+        [Some(fee_payer)] => println!(
             "  {}: {}",
             "Fee payer".cyan(),
             fee_payer.pubkey().to_string().bright_green()
-        );
-    } else {
-        println!(
+        ),
+        [None] => println!(
             "  {}: {} (same as contributor)",
             "Fee payer".cyan(),
             contributor_pubkey.to_string().bright_yellow()
-        );
+        ),
+        _ => println!(
+            "  {}: {} keys, rotated per network",
+            "Fee payers".cyan(),
+            fee_payers.len().to_string().bright_green()
+        ),
     }
     println!(
         "  {}: {}",
@@ -361,14 +770,21 @@ pub fn display_final_configuration(
     );
 
     println!("\n{}", "👥 All Members:".bright_yellow().bold());
+    let contributor_permissions = members
+        .iter()
+        .find(|member| member.key == *contributor_pubkey)
+        .map_or(vec!["Initiate".to_string()], |member| {
+            decode_permissions(member.permissions.mask)
+        });
     println!(
         "  {} Contributor: {} ({})",
         "✓".bright_green(),
         contributor_pubkey.to_string().bright_white(),
-        "Initiate".bright_cyan()
+        contributor_permissions.join(", ").bright_cyan()
     );
 
-    for (i, member) in members.iter().skip(1).enumerate() {
+    let others = members.iter().filter(|member| member.key != *contributor_pubkey);
+    for (i, member) in others.enumerate() {
         let perms = decode_permissions(member.permissions.mask);
         println!(
             "  {} Member {}: {} ({})",
@@ -459,214 +875,404 @@ pub fn display_deployment_info(
 }
 
 // Transaction creation functions
-pub fn create_feature_activation_transaction_message(feature_id: Pubkey) -> TransactionMessage {
-    use crate::squads::SmallVec;
-
-    let instructions = activate_feature_funded(&feature_id);
-
-    // Build account keys list for the message
-    let mut account_keys = vec![
-        feature_id,                           // 1: Feature account (writable)
-        solana_system_interface::program::ID, // 2: System program
-        crate::feature_gate_program::FEATURE_GATE_PROGRAM_ID, // 3: Feature gate program
-    ];
-
-    // Compile instructions into CompiledInstructions with SmallVec
-    let mut compiled_instructions = Vec::new();
-
-    for instruction in instructions {
-        // Find program_id index in account_keys
-        let program_id_index = account_keys
-            .iter()
-            .position(|key| *key == instruction.program_id)
-            .unwrap_or_else(|| {
-                account_keys.push(instruction.program_id);
-                account_keys.len() - 1
-            }) as u8;
-
-        // Map account pubkeys to indices
-        let account_indexes: Vec<u8> = instruction
-            .accounts
-            .iter()
-            .map(|account_meta| {
-                account_keys
-                    .iter()
-                    .position(|key| *key == account_meta.pubkey)
-                    .unwrap_or_else(|| {
-                        account_keys.push(account_meta.pubkey);
-                        account_keys.len() - 1
-                    }) as u8
-            })
-            .collect();
 
-        compiled_instructions.push(CompiledInstruction {
-            program_id_index,
-            account_indexes: SmallVec::from(account_indexes),
-            data: SmallVec::from(instruction.data),
-        });
-    }
+/// Builds the vault transaction that queues `feature_id` for activation. The
+/// feature account is vault 0, so it is expected to be pre-funded and only
+/// signs for its own allocation.
+pub fn create_feature_activation_transaction_message(feature_id: Pubkey) -> TransactionMessage {
+    TransactionMessageBuilder::new(feature_id)
+        .instructions(activate_feature_funded(&feature_id))
+        // Not invoked, but listed so the message names the program the
+        // feature account is assigned to
+        .account(AccountMeta::new_readonly(
+            crate::feature_gate_program::FEATURE_GATE_PROGRAM_ID,
+            false,
+        ))
+        .build()
+        .expect("feature gate messages use only a few accounts")
+}
 
-    TransactionMessage {
-        num_signers: 1,              // feature_id is the signer
-        num_writable_signers: 1,     // feature_id is writable signer
-        num_writable_non_signers: 0,
-        account_keys: SmallVec::from(account_keys),
-        instructions: SmallVec::from(compiled_instructions),
-        address_table_lookups: SmallVec::from(vec![]),
-    }
+pub fn create_feature_revocation_transaction_message(
+    feature_id: Pubkey,
+    destination: Pubkey,
+) -> TransactionMessage {
+    TransactionMessageBuilder::new(feature_id)
+        .instructions([crate::feature_gate_program::revoke_pending_activation(
+            &feature_id,
+            &destination,
+        )])
+        .build()
+        .expect("feature gate messages use only a few accounts")
 }
 
-pub fn create_feature_revocation_transaction_message(feature_id: Pubkey) -> TransactionMessage {
-    use crate::squads::SmallVec;
+/// Decodes a base64 serialized Solana message (as produced by
+/// `VersionedMessage` / web3.js `message.serialize()`) into the Squads
+/// `TransactionMessage` wire format, so arbitrary instructions can be proposed
+pub fn transaction_message_from_base64(encoded: &str) -> Result<TransactionMessage> {
+    use base64::Engine;
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| eyre::eyre!("Message is not valid base64: {}", e))?;
+    let message: VersionedMessage = bincode::deserialize(&bytes)
+        .map_err(|e| eyre::eyre!("Failed to decode Solana message: {}", e))?;
+    transaction_message_from_versioned(&message)
+}
 
-    // Create feature revocation instruction for a test feature
-    let instruction = crate::feature_gate_program::revoke_pending_activation(&feature_id);
+/// Decodes a serialized `VersionedTransaction`, such as those printed by the
+/// transaction generation commands, from base64 or (with `base58`) base58
+pub fn decode_versioned_transaction(encoded: &str, base58: bool) -> Result<VersionedTransaction> {
+    use base64::Engine;
 
-    // Build account keys list for the message
-    let mut account_keys = vec![
-        feature_id,                                  // 0: Feature account (signer, writable)
-        crate::feature_gate_program::INCINERATOR_ID, // 1: Incinerator (writable)
-        solana_system_interface::program::ID,        // 2: System program
-        crate::feature_gate_program::FEATURE_GATE_PROGRAM_ID, // 3: Feature gate program
-    ];
+    let bytes = if base58 {
+        bs58::decode(encoded.trim())
+            .into_vec()
+            .map_err(|e| eyre::eyre!("Transaction is not valid base58: {}", e))?
+    } else {
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| eyre::eyre!("Transaction is not valid base64: {}", e))?
+    };
+    bincode::deserialize(&bytes).map_err(|e| eyre::eyre!("Failed to decode transaction: {}", e))
+}
 
-    // Find program_id index in account_keys
-    let program_id_index = account_keys
+/// Maps a compiled Solana message onto the Squads `TransactionMessage`. The
+/// header's readonly counts become writable counts and address table lookups
+/// carry over unchanged.
+pub fn transaction_message_from_versioned(
+    message: &VersionedMessage,
+) -> Result<TransactionMessage> {
+    use crate::squads::{MessageAddressTableLookup, SmallVec};
+
+    let header = message.header();
+    let account_keys = message.static_account_keys();
+    let num_signers = header.num_required_signatures;
+    let num_writable_signers = num_signers
+        .checked_sub(header.num_readonly_signed_accounts)
+        .ok_or_else(|| eyre::eyre!("Message header has more readonly signers than signers"))?;
+    let num_writable_non_signers = account_keys
+        .len()
+        .checked_sub(num_signers as usize + header.num_readonly_unsigned_accounts as usize)
+        .ok_or_else(|| eyre::eyre!("Message header counts exceed its account keys"))?;
+    let num_writable_non_signers = u8::try_from(num_writable_non_signers)
+        .map_err(|_| eyre::eyre!("Message has too many account keys"))?;
+
+    let instructions = message
+        .instructions()
         .iter()
-        .position(|key| *key == instruction.program_id)
-        .unwrap_or_else(|| {
-            account_keys.push(instruction.program_id);
-            account_keys.len() - 1
-        }) as u8;
-
-    // Map account pubkeys to indices
-    let account_indexes: Vec<u8> = instruction
-        .accounts
+        .map(|instruction| CompiledInstruction {
+            program_id_index: instruction.program_id_index,
+            account_indexes: SmallVec::from(instruction.accounts.clone()),
+            data: SmallVec::from(instruction.data.clone()),
+        })
+        .collect::<Vec<_>>();
+
+    let address_table_lookups = message
+        .address_table_lookups()
+        .unwrap_or_default()
         .iter()
-        .map(|account_meta| {
-            account_keys
-                .iter()
-                .position(|key| *key == account_meta.pubkey)
-                .unwrap_or_else(|| {
-                    account_keys.push(account_meta.pubkey);
-                    account_keys.len() - 1
-                }) as u8
+        .map(|lookup| MessageAddressTableLookup {
+            account_key: lookup.account_key,
+            writable_indexes: SmallVec::from(lookup.writable_indexes.clone()),
+            readonly_indexes: SmallVec::from(lookup.readonly_indexes.clone()),
         })
-        .collect();
+        .collect::<Vec<_>>();
+
+    let transaction_message = TransactionMessage {
+        num_signers,
+        num_writable_signers,
+        num_writable_non_signers,
+        account_keys: SmallVec::from(account_keys.to_vec()),
+        instructions: SmallVec::from(instructions),
+        address_table_lookups: SmallVec::from(address_table_lookups),
+    };
+    transaction_message.validate()?;
+    Ok(transaction_message)
+}
 
-    let compiled_instructions = vec![CompiledInstruction {
-        program_id_index,
-        account_indexes: SmallVec::from(account_indexes),
-        data: SmallVec::from(instruction.data),
-    }];
+/// A kind of vault transaction the tool proposes on a feature gate multisig
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionType {
+    /// Queue the feature (the vault) for activation
+    Activation,
+    /// Revoke the pending activation
+    Revocation,
+}
+
+impl TransactionType {
+    /// Name of the proposal in summaries
+    pub fn proposal_label(self) -> &'static str {
+        match self {
+            TransactionType::Activation => "Feature Gate Activation Proposal",
+            TransactionType::Revocation => "Feature Gate Revocation Proposal",
+        }
+    }
 
-    TransactionMessage {
-        num_signers: 1,              // feature_id is the signer
-        num_writable_signers: 1,     // feature_id is writable signer
-        num_writable_non_signers: 1, // incinerator is writable non-signer
-        account_keys: SmallVec::from(account_keys),
-        instructions: SmallVec::from(compiled_instructions),
-        address_table_lookups: SmallVec::from(vec![]),
+    /// The vault transaction message for this proposal on `multisig_address`
+    fn message(
+        self,
+        multisig_address: &Pubkey,
+        tx_options: &TransactionOptions,
+    ) -> TransactionMessage {
+        let vault_address = get_vault_pda(multisig_address, 0, tx_options.program_id.as_ref()).0;
+        match self {
+            TransactionType::Activation => {
+                create_feature_activation_transaction_message(vault_address)
+            }
+            TransactionType::Revocation => create_feature_revocation_transaction_message(
+                vault_address,
+                tx_options.revocation_destination(),
+            ),
+        }
     }
 }
 
-pub async fn create_and_send_transaction_proposal(
+/// The feature gate proposal types to create, in the order they take
+/// transaction indices
+pub fn feature_gate_proposal_types(activation: bool, revocation: bool) -> Vec<TransactionType> {
+    [
+        (TransactionType::Activation, activation),
+        (TransactionType::Revocation, revocation),
+    ]
+    .into_iter()
+    .filter_map(|(transaction_type, wanted)| wanted.then_some(transaction_type))
+    .collect()
+}
+
+/// Creates one proposal per entry in `proposal_types`, starting at
+/// `first_index` and taking consecutive indices. Squads only accepts the
+/// multisig's next transaction index, so a later proposal can't land first;
+/// each is sent as soon as the previous one is processed instead of after it
+/// confirms, and they are then confirmed together.
+#[allow(clippy::too_many_arguments)]
+pub async fn create_and_send_feature_gate_proposals(
     rpc_url: &str,
     fee_payer_keypair: &Option<Keypair>,
     contributor_keypair: &Keypair,
     multisig_address: &Pubkey,
-    transaction_type: &str,
-    transaction_index: u64,
+    first_index: u64,
+    proposal_types: &[TransactionType],
+    tx_options: &TransactionOptions,
+    retry_budget: &RetryBudget,
 ) -> Result<()> {
-    let vault_address = get_vault_pda(multisig_address, 0, None).0;
-
-    let transaction_message = match transaction_type {
-        "activation" => create_feature_activation_transaction_message(vault_address),
-        "revocation" => create_feature_revocation_transaction_message(vault_address),
-        _ => {
-            return Err(eyre::eyre!(
-                "Invalid transaction type: {}",
-                transaction_type
-            ))
-        }
-    };
+    let proposals: Vec<(TransactionType, u64)> =
+        proposal_types.iter().copied().zip(first_index..).collect();
 
     let rpc_client = create_rpc_client(rpc_url);
     let recent_blockhash = rpc_client
         .get_latest_blockhash()
-        .map_err(|e| eyre::eyre!("Failed to get recent blockhash: {}", e))?;
+        .map_err(|e| ToolError::Network(format!("Failed to get recent blockhash: {}", e)))?;
 
-    let fee_payer_pubkey = fee_payer_keypair
-        .as_ref()
-        .map(|kp| kp.pubkey())
-        .unwrap_or_else(|| contributor_keypair.pubkey());
+    let transactions = proposals
+        .iter()
+        .map(|(transaction_type, transaction_index)| {
+            build_vault_transaction_proposal(
+                &rpc_client,
+                rpc_url,
+                fee_payer_keypair,
+                contributor_keypair,
+                multisig_address,
+                *transaction_index,
+                transaction_type.message(multisig_address, tx_options),
+                tx_options,
+                recent_blockhash,
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
 
-    // Use the integrated create_transaction_and_proposal_message function from provision.rs
-    let (message, _transaction_pda, _proposal_pda) =
-        crate::provision::create_transaction_and_proposal_message(
-            None, // Use default program ID
-            &fee_payer_pubkey,
-            &contributor_keypair.pubkey(),
-            multisig_address,
-            transaction_index,
-            0, // Vault index 0 (default vault for feature gates)
-            transaction_message,
-            Some(5000),                  // Priority fee
-            Some(DEFAULT_COMPUTE_UNITS), // Compute unit limit
-            recent_blockhash,
-        )
-        .map_err(|e| eyre::eyre!("Failed to create transaction and proposal message: {}", e))?;
+    let progress = Output::spinner("Sending transactions...");
 
-    let signers: &[&dyn Signer] = if fee_payer_pubkey == contributor_keypair.pubkey() {
-        &[contributor_keypair]
-    } else {
-        &[
-            fee_payer_keypair.as_ref().unwrap() as &dyn Signer,
-            contributor_keypair as &dyn Signer,
-        ]
-    };
+    let signatures = crate::provision::send_and_confirm_transactions_in_order(
+        &transactions,
+        rpc_url,
+        tx_options.send_strategy,
+        retry_budget,
+    )
+    .wrap_err("Failed to send feature gate proposals")?;
+
+    let network_display = NetworkCluster::from_url(rpc_url).label();
+    progress.finish_and_clear();
+    for ((transaction_type, _), signature) in proposals.iter().zip(signatures) {
+        Output::result(&format!(
+            "{} Confirmed ({}): {}",
+            transaction_type.proposal_label(),
+            network_display,
+            signature.bright_green()
+        ));
+    }
+    Output::separator();
+    Ok(())
+}
 
-    let transaction = VersionedTransaction::try_new(VersionedMessage::V0(message), signers)
-        .map_err(|e| eyre::eyre!("Failed to create signed transaction: {}", e))?;
+/// The two signing roles in multisig setup transactions. The fee payer pays
+/// the transaction fee and the rent for new accounts; the member only signs
+/// as a readonly account, so it never needs lamports unless it is also the
+/// fee payer (when no separate fee payer was given).
+pub struct SignerRoles<'a> {
+    pub fee_payer: &'a Keypair,
+    pub member: &'a Keypair,
+}
 
-    let progress = ProgressBar::new_spinner().with_message("Sending transaction...");
-    progress.enable_steady_tick(Duration::from_millis(100));
+impl<'a> SignerRoles<'a> {
+    pub fn new(fee_payer: &'a Option<Keypair>, member: &'a Keypair) -> Self {
+        Self {
+            fee_payer: fee_payer.as_ref().unwrap_or(member),
+            member,
+        }
+    }
 
-    let signature = crate::provision::send_and_confirm_transaction(&transaction, &rpc_client)
-        .map_err(|e| eyre::eyre!("Failed to send transaction and proposal: {}", e))?;
+    pub fn is_shared(&self) -> bool {
+        self.fee_payer.pubkey() == self.member.pubkey()
+    }
 
-    // Simple signature output with description and network
-    let description = match transaction_type {
-        "activation" => "Feature Gate Activation Proposal Confirmed",
-        "revocation" => "Feature Gate Revocation Proposal Confirmed",
-        _ => "Transaction",
-    };
+    /// Signers in message order: the fee payer first, then the member unless
+    /// it is the same key
+    pub fn signers(&self) -> Vec<&'a dyn Signer> {
+        if self.is_shared() {
+            vec![self.fee_payer]
+        } else {
+            vec![self.fee_payer, self.member]
+        }
+    }
+}
 
-    let network_display = if rpc_url.contains("devnet") {
-        "Devnet"
-    } else if rpc_url.contains("mainnet") {
-        "Mainnet"
-    } else if rpc_url.contains("testnet") {
-        "Testnet"
+/// The setup member added ahead of the others: Initiate only, enough to
+/// create the feature gate proposals, or every permission when the setup key
+/// is the fee payer and is to vote as well
+pub fn setup_member(key: Pubkey, fee_payer_is_member: bool) -> Member {
+    let permissions = if fee_payer_is_member {
+        Permissions::from_actions(&Permission::ALL)
     } else {
-        "Custom"
+        Permissions::from_actions(&[Permission::Initiate])
     };
+    Member { key, permissions }
+}
 
-    progress.finish_with_message(format!(
-        "{} ({}): {}",
-        description,
-        network_display,
-        signature.to_string().bright_green()
-    ));
-    println!("");
-    Ok(())
+/// `signers` with repeated keys dropped, keeping the first of each: a key
+/// holding several roles signs once, as a transaction takes one signature
+/// per key
+pub fn unique_signers<'a>(signers: &[&'a dyn Signer]) -> Vec<&'a dyn Signer> {
+    let mut unique: Vec<&'a dyn Signer> = Vec::with_capacity(signers.len());
+    for signer in signers {
+        if !unique.iter().any(|kept| kept.pubkey() == signer.pubkey()) {
+            unique.push(*signer);
+        }
+    }
+    unique
+}
+
+/// A note for a review screen when the fee payer is also a member. It then
+/// signs once for both roles and its lamports pay for everything, which is
+/// easy to miss when the same keypair file is reused.
+pub fn fee_payer_role_note(fee_payer: &Pubkey, members: &[Member]) -> Option<String> {
+    let i = members.iter().position(|member| member.key == *fee_payer)?;
+    Some(format!(
+        "Fee payer {} is also member {} of the multisig",
+        fee_payer,
+        i + 1
+    ))
+}
+
+/// Sends a `vault_transaction_create` + `proposal_create` for `transaction_message`
+/// on vault 0, signed by the fee payer (or the creator when there is none) and
+/// the creating member. Returns the confirmed signature.
+#[allow(clippy::too_many_arguments)]
+pub fn send_vault_transaction_proposal(
+    rpc_url: &str,
+    fee_payer_keypair: &Option<Keypair>,
+    contributor_keypair: &Keypair,
+    multisig_address: &Pubkey,
+    transaction_index: u64,
+    transaction_message: TransactionMessage,
+    tx_options: &TransactionOptions,
+    retry_budget: &RetryBudget,
+) -> Result<String> {
+    let rpc_client = create_rpc_client(rpc_url);
+
+    crate::provision::send_and_confirm_rebuildable_transaction(
+        &rpc_client,
+        tx_options.send_strategy,
+        retry_budget,
+        |recent_blockhash| {
+            build_vault_transaction_proposal(
+                &rpc_client,
+                rpc_url,
+                fee_payer_keypair,
+                contributor_keypair,
+                multisig_address,
+                transaction_index,
+                transaction_message.clone(),
+                tx_options,
+                recent_blockhash,
+            )
+        },
+    )
+    .wrap_err("Failed to send transaction and proposal")
+}
+
+/// Signs a `vault_transaction_create` + `proposal_create` for `transaction_message`
+/// on vault 0, as sent by `send_vault_transaction_proposal`
+#[allow(clippy::too_many_arguments)]
+fn build_vault_transaction_proposal(
+    rpc_client: &RpcClient,
+    rpc_url: &str,
+    fee_payer_keypair: &Option<Keypair>,
+    contributor_keypair: &Keypair,
+    multisig_address: &Pubkey,
+    transaction_index: u64,
+    transaction_message: TransactionMessage,
+    tx_options: &TransactionOptions,
+    recent_blockhash: Hash,
+) -> Result<VersionedTransaction> {
+    let roles = SignerRoles::new(fee_payer_keypair, contributor_keypair);
+    let fee_payer_pubkey = roles.fee_payer.pubkey();
+
+    let priority_fee = tx_options.resolve_priority_fee(
+        rpc_client,
+        rpc_url,
+        &[*multisig_address, fee_payer_pubkey],
+    );
+
+    // Vault index 0 (default vault for feature gates)
+    let context = crate::provision::TransactionBuildContext::new(
+        *multisig_address,
+        transaction_index,
+        contributor_keypair.pubkey(),
+        recent_blockhash,
+    )
+    .with_program_id(tx_options.program_id.as_ref())
+    .with_fee_payer(fee_payer_pubkey)
+    .with_compute_budget(Some(priority_fee as u32), Some(DEFAULT_COMPUTE_UNITS))
+    .with_draft(tx_options.draft);
+    let (message, _transaction_pda, _proposal_pda) =
+        crate::provision::create_transaction_and_proposal_message(&context, transaction_message)
+            .map_err(|e| {
+                eyre::eyre!("Failed to create transaction and proposal message: {}", e)
+            })?;
+    if tx_options.dump_instructions {
+        crate::provision::dump_message_instructions(&message);
+    }
+    if tx_options.validate_accounts {
+        crate::provision::check_writable_account_owners(rpc_client, &message, &context.program_id);
+    }
+
+    VersionedTransaction::try_new(VersionedMessage::V0(message), &roles.signers())
+        .map_err(|e| eyre::eyre!("Failed to create signed transaction: {}", e))
 }
 
 // Validation functions
+
+/// Parses a base58 pubkey, ignoring surrounding whitespace left over from
+/// copy-pasting
+pub fn parse_pubkey(input: &str) -> Result<Pubkey> {
+    let input = input.trim();
+    Pubkey::from_str(input).map_err(|_| eyre::eyre!("Invalid public key: '{}'", input))
+}
+
 pub fn validate_pubkey_with_retry(prompt: &str) -> Result<Pubkey> {
     loop {
         let input = Text::new(prompt).prompt()?;
-        match Pubkey::from_str(&input.trim()) {
+        match parse_pubkey(&input) {
             Ok(pubkey) => {
                 println!(
                     "  {} Valid public key: {}",
@@ -687,7 +1293,7 @@ pub fn validate_pubkey_with_retry(prompt: &str) -> Result<Pubkey> {
 
                 let retry = Confirm::new("Try again?").with_default(true).prompt()?;
                 if !retry {
-                    return Err(eyre::eyre!("User cancelled public key entry"));
+                    return Err(ToolError::Cancelled("User cancelled public key entry".into()).into());
                 }
             }
         }
@@ -737,11 +1343,7 @@ pub fn validate_rpc_url(url: &str) -> Result<String> {
     }
 
     // Check for common Solana RPC patterns
-    if url.contains("solana.com")
-        || url.contains("localhost")
-        || url.contains("127.0.0.1")
-        || url.contains("rpc")
-    {
+    if NetworkCluster::from_url(url) != NetworkCluster::Custom || url.contains("rpc") {
         println!("  {} Valid RPC URL format detected", "✓".bright_green());
     } else {
         println!(
@@ -752,7 +1354,7 @@ pub fn validate_rpc_url(url: &str) -> Result<String> {
             .with_default(false)
             .prompt()?;
         if !confirm {
-            return Err(eyre::eyre!("User cancelled due to unusual URL"));
+            return Err(ToolError::Cancelled("User cancelled due to unusual URL".into()).into());
         }
     }
 
@@ -792,11 +1394,11 @@ pub fn choose_network_from_config(config: &Config) -> Result<String> {
     let available_networks = if !config.networks.is_empty() {
         config.networks.clone()
     } else {
-        vec![
-            "https://api.devnet.solana.com".to_string(),
-            "https://api.testnet.solana.com".to_string(),
-            "https://api.mainnet.solana.com".to_string(),
-        ]
+        [NetworkCluster::Devnet, NetworkCluster::Testnet, NetworkCluster::MainnetBeta]
+            .iter()
+            .filter_map(|cluster| cluster.canonical_url())
+            .map(str::to_string)
+            .collect()
     };
 
     if available_networks.is_empty() {
@@ -821,30 +1423,22 @@ pub fn choose_network_mode(config: &Config, use_saved_config: bool) -> Result<(b
         return Ok((false, Vec::new()));
     }
 
-    println!("");
-    println!("{}", "Saved Network Configuration".bold().bright_yellow(),);
-    println!("");
-    println!(
+    Output::separator();
+    Output::plain(&"Saved Network Configuration".bold().bright_yellow().to_string());
+    Output::separator();
+    Output::plain(&format!(
         "  {}: {}",
         "Networks".cyan(),
         available_networks.len().to_string().cyan()
-    );
+    ));
     for (i, network) in available_networks.iter().enumerate() {
-        let network_name = if network.contains("devnet") {
-            "Devnet"
-        } else if network.contains("testnet") {
-            "Testnet"
-        } else if network.contains("mainnet") {
-            "Mainnet"
-        } else {
-            "Custom"
-        };
-        println!(
+        let network_name = NetworkCluster::from_url(network).label();
+        Output::plain(&format!(
             "    {}: {} ({})",
             format!("Network {}", i + 1).cyan(),
             network_name.bright_white(),
             network.bright_white()
-        );
+        ));
     }
     let use_saved_networks = Confirm::new("Use saved networks for deployment?")
         .with_default(true)
@@ -858,66 +1452,66 @@ pub fn review_config(config: &Config) -> Result<bool> {
         return Ok(false);
     }
 
-    println!(
+    Output::plain(&format!(
         "\n{}",
         "📋 Found existing configuration:".bright_yellow().bold()
-    );
-    println!("");
+    ));
+    Output::separator();
     if !config.members.is_empty() {
-        println!(
+        Output::plain(&format!(
             "  {}: {}",
             "Saved members".cyan(),
             config.members.len().to_string().bright_green()
-        );
+        ));
         for (i, member) in config.members.iter().enumerate() {
-            println!(
+            Output::plain(&format!(
                 "    {}: {}",
                 format!("Member {}", i + 1).cyan(),
                 member.bright_white()
-            );
+            ));
         }
     }
-    println!("");
-    println!(
+    Output::separator();
+    Output::plain(&format!(
         "  {}: {}",
         "Threshold".cyan(),
         config.threshold.to_string().bright_green()
-    );
+    ));
 
     // Show fee payer path
-    println!("");
+    Output::separator();
     if let Some(fee_payer_path) = &config.fee_payer_path {
-        println!(
+        Output::plain(&format!(
             "  {}: {}",
             "Fee payer keypair".cyan(),
             fee_payer_path.bright_green()
-        );
+        ));
     } else {
-        println!(
+        Output::plain(&format!(
             "  {}: {}",
             "Fee payer keypair".cyan(),
             "Not configured".bright_yellow()
-        );
+        ));
     }
 
     // Show networks
     let networks_to_show = &config.networks;
 
-    println!("");
-    println!(
+    Output::separator();
+    Output::plain(&format!(
         "  {}: {}",
         "Saved networks".cyan(),
         networks_to_show.len().to_string().bright_green()
-    );
+    ));
     for (i, network) in networks_to_show.iter().enumerate() {
-        println!(
+        Output::plain(&format!(
             "    {}: {}",
             format!("Network {}", i + 1).cyan(),
             network.bright_white()
-        );
+        ));
     }
 
-    println!();
+    Output::separator();
     let use_config = Confirm::new("Use these saved members and settings?")
         .with_default(true)
         .prompt()?;
@@ -926,46 +1520,34 @@ pub fn review_config(config: &Config) -> Result<bool> {
 }
 
 pub fn decode_permissions(mask: u8) -> Vec<String> {
-    let mut permissions = Vec::new();
-    if mask & 1 != 0 {
-        permissions.push("Initiate".to_string());
-    }
-    if mask & 2 != 0 {
-        permissions.push("Vote".to_string());
-    }
-    if mask & 4 != 0 {
-        permissions.push("Execute".to_string());
-    }
-    permissions
+    Permissions { mask }
+        .actions()
+        .into_iter()
+        .map(|action| action.name().to_string())
+        .collect()
 }
 
-/// Check that the fee payer has sufficient SOL balance on all networks
-/// Returns a Result indicating success or failure with network-specific errors
+/// Checks each network's fee payer holds `required_balance_sol`. Fee payers are
+/// assigned to networks round-robin, matching `deploy_to_saved_networks`.
 pub async fn check_fee_payer_balance_on_networks(
-    fee_payer_pubkey: &Pubkey,
+    fee_payer_pubkeys: &[Pubkey],
     networks: &[String],
     required_balance_sol: f64,
 ) -> Result<()> {
-    use crate::output::Output;
-
-    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
     let required_lamports = (required_balance_sol * LAMPORTS_PER_SOL as f64) as u64;
 
     Output::header("💰 Checking Fee Payer Balance");
-    println!();
+    Output::separator();
 
     let mut insufficient_balance_networks = Vec::new();
     let mut network_errors = Vec::new();
 
-    for network in networks {
-        let network_display = if network.contains("devnet") {
-            "Devnet"
-        } else if network.contains("mainnet") {
-            "Mainnet"
-        } else if network.contains("testnet") {
-            "Testnet"
+    for (i, network) in networks.iter().enumerate() {
+        let fee_payer_pubkey = &fee_payer_pubkeys[i % fee_payer_pubkeys.len()];
+        let network_display = if fee_payer_pubkeys.len() > 1 {
+            format!("{} ({})", NetworkCluster::from_url(network).label(), fee_payer_pubkey)
         } else {
-            "Custom"
+            NetworkCluster::from_url(network).label().to_string()
         };
 
         // Create RPC client for this network
@@ -977,36 +1559,36 @@ pub async fn check_fee_payer_balance_on_networks(
                 let balance_sol = balance_lamports as f64 / LAMPORTS_PER_SOL as f64;
 
                 if balance_lamports >= required_lamports {
-                    println!(
+                    Output::plain(&format!(
                         "  {} {}: {} SOL",
                         "✓".bright_green(),
                         network_display.bright_white(),
                         format!("{:.4}", balance_sol).bright_green()
-                    );
+                    ));
                 } else {
-                    println!(
+                    Output::plain(&format!(
                         "  {} {}: {} SOL (insufficient - need {:.3})",
                         "❌".bright_red(),
                         network_display.bright_white(),
                         format!("{:.4}", balance_sol).bright_red(),
                         required_balance_sol.to_string().bright_yellow()
-                    );
+                    ));
                     insufficient_balance_networks.push((network_display.to_string(), balance_sol));
                 }
             }
             Err(e) => {
-                println!(
+                Output::plain(&format!(
                     "  {} {}: {}",
                     "⚠️".bright_yellow(),
                     network_display.bright_white(),
                     "Could not check balance".bright_red()
-                );
+                ));
                 network_errors.push((network_display.to_string(), e.to_string()));
             }
         }
     }
 
-    println!();
+    Output::separator();
 
     // Report any issues
     if !insufficient_balance_networks.is_empty() {
@@ -1015,19 +1597,20 @@ pub async fn check_fee_payer_balance_on_networks(
             insufficient_balance_networks.len()
         ));
         for (network, balance) in &insufficient_balance_networks {
-            println!(
+            Output::plain(&format!(
                 "  {} {}: {:.4} SOL (need {:.3} SOL)",
                 "•".bright_red(),
                 network.bright_white(),
                 balance,
                 required_balance_sol.to_string().bright_yellow()
-            );
+            ));
         }
-        println!();
-        return Err(eyre::eyre!(
+        Output::separator();
+        return Err(ToolError::InsufficientFunds(format!(
             "Fee payer needs at least {:.3} SOL on all networks for deployment",
             required_balance_sol
-        ));
+        ))
+        .into());
     }
 
     if !network_errors.is_empty() {
@@ -1036,9 +1619,14 @@ pub async fn check_fee_payer_balance_on_networks(
             network_errors.len()
         ));
         for (network, error) in &network_errors {
-            println!("  {} {}: {}", "•".bright_yellow(), network.bright_white(), error);
+            Output::plain(&format!(
+                "  {} {}: {}",
+                "•".bright_yellow(),
+                network.bright_white(),
+                error
+            ));
         }
-        println!();
+        Output::separator();
     }
 
     if insufficient_balance_networks.is_empty() && network_errors.is_empty() {
@@ -1047,3 +1635,576 @@ pub async fn check_fee_payer_balance_on_networks(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature_gate_program::{
+        revoke_pending_activation, FEATURE_GATE_PROGRAM_ID, INCINERATOR_ID,
+    };
+    use solana_instruction::Instruction;
+
+    /// Asserts every compiled instruction resolves back to the program id and
+    /// account pubkeys of the instruction it was compiled from, in order.
+    fn assert_compiled_matches(message: &TransactionMessage, instructions: &[Instruction]) {
+        assert_eq!(message.instructions.len(), instructions.len());
+
+        for (i, (compiled, original)) in message
+            .instructions
+            .iter()
+            .zip(instructions.iter())
+            .enumerate()
+        {
+            assert_eq!(
+                message.account_keys[compiled.program_id_index as usize], original.program_id,
+                "Instruction {} program id index points at the wrong key",
+                i
+            );
+            assert_eq!(
+                compiled.account_indexes.len(),
+                original.accounts.len(),
+                "Instruction {} has a different number of accounts",
+                i
+            );
+            for (j, (&index, meta)) in compiled
+                .account_indexes
+                .iter()
+                .zip(original.accounts.iter())
+                .enumerate()
+            {
+                assert_eq!(
+                    message.account_keys[index as usize], meta.pubkey,
+                    "Instruction {} account {} points at the wrong key",
+                    i, j
+                );
+            }
+            assert_eq!(Vec::from(compiled.data.clone()), original.data);
+        }
+    }
+
+    #[test]
+    fn test_derive_keypair_from_seed() {
+        let create_key = derive_keypair_from_seed(CREATE_KEY_SEED_DOMAIN, "council-2026");
+        let contributor = derive_keypair_from_seed(CONTRIBUTOR_SEED_DOMAIN, "council-2026");
+
+        // Reproducible from the seed, and a different key per domain and seed
+        assert_eq!(
+            create_key.pubkey(),
+            derive_keypair_from_seed(CREATE_KEY_SEED_DOMAIN, "council-2026").pubkey()
+        );
+        assert_ne!(create_key.pubkey(), contributor.pubkey());
+        assert_ne!(
+            create_key.pubkey(),
+            derive_keypair_from_seed(CREATE_KEY_SEED_DOMAIN, "council-2027").pubkey()
+        );
+    }
+
+    #[test]
+    fn test_activation_message_account_indexes_match_instructions() {
+        let feature_id = Pubkey::new_unique();
+        let message = create_feature_activation_transaction_message(feature_id);
+        let instructions = activate_feature_funded(&feature_id);
+
+        assert_compiled_matches(&message, &instructions);
+        message.validate().unwrap();
+
+        // The feature account is the only signer and must come first
+        assert_eq!(message.account_keys[0], feature_id);
+        assert_eq!(message.num_signers, 1);
+        assert!(message.account_keys.contains(&FEATURE_GATE_PROGRAM_ID));
+    }
+
+
+    #[test]
+    fn test_signer_roles_shared_fee_payer() {
+        let member = Keypair::new();
+
+        let roles = SignerRoles::new(&None, &member);
+        assert!(roles.is_shared());
+        assert_eq!(roles.fee_payer.pubkey(), member.pubkey());
+        assert_eq!(roles.signers().len(), 1);
+
+        // An explicit fee payer that is the member itself is still shared
+        let same = Some(member.insecure_clone());
+        let roles = SignerRoles::new(&same, &member);
+        assert!(roles.is_shared());
+        assert_eq!(roles.signers().len(), 1);
+    }
+
+    #[test]
+    fn test_unique_signers_keeps_first_of_each_key() {
+        let fee_payer = Keypair::new();
+        let create_key = Keypair::new();
+
+        let signers = unique_signers(&[&fee_payer, &create_key, &fee_payer]);
+        let keys: Vec<Pubkey> = signers.iter().map(|s| s.pubkey()).collect();
+        assert_eq!(keys, vec![fee_payer.pubkey(), create_key.pubkey()]);
+        assert_eq!(unique_signers(&[&fee_payer, &fee_payer]).len(), 1);
+    }
+
+    #[test]
+    fn test_fee_payer_role_note() {
+        let fee_payer = Pubkey::new_unique();
+        let other = Member {
+            key: Pubkey::new_unique(),
+            permissions: Permissions::from_actions(&Permission::ALL),
+        };
+        let as_member = Member {
+            key: fee_payer,
+            permissions: Permissions::from_actions(&Permission::ALL),
+        };
+
+        assert!(fee_payer_role_note(&fee_payer, std::slice::from_ref(&other)).is_none());
+        assert!(fee_payer_role_note(&fee_payer, &[]).is_none());
+
+        // Fee payer equals a member
+        let note = fee_payer_role_note(&fee_payer, &[other, as_member]).unwrap();
+        assert!(note.contains("member 2"));
+    }
+
+    #[test]
+    fn test_signer_roles_distinct_fee_payer_pays_everything() {
+        let member = Keypair::new();
+        let feature_id = Pubkey::new_unique();
+        let fee_payer = Some(Keypair::new());
+        let fee_payer_pubkey = fee_payer.as_ref().unwrap().pubkey();
+
+        let roles = SignerRoles::new(&fee_payer, &member);
+        assert!(!roles.is_shared());
+        let signers: Vec<Pubkey> = roles.signers().iter().map(|s| s.pubkey()).collect();
+        assert_eq!(signers, vec![fee_payer_pubkey, member.pubkey()]);
+
+        let context = crate::provision::TransactionBuildContext::new(
+            Pubkey::new_unique(),
+            1,
+            roles.member.pubkey(),
+            solana_hash::Hash::default(),
+        )
+        .with_fee_payer(roles.fee_payer.pubkey())
+        .with_compute_budget(Some(1), Some(DEFAULT_COMPUTE_UNITS));
+        let (message, _, _) = crate::provision::create_transaction_and_proposal_message(
+            &context,
+            create_feature_activation_transaction_message(feature_id),
+        )
+        .unwrap();
+        let message = VersionedMessage::V0(message);
+        let member_index = message
+            .static_account_keys()
+            .iter()
+            .position(|key| *key == member.pubkey())
+            .unwrap();
+
+        // The fee payer is the message payer; the member signs but is never
+        // debited, so it doesn't need lamports
+        assert_eq!(message.static_account_keys()[0], fee_payer_pubkey);
+        assert!(message.is_signer(member_index));
+        assert!(!message.is_maybe_writable(member_index, None));
+    }
+
+    #[test]
+    fn test_fee_payer_as_member_signs_once_for_every_role() {
+        let fee_payer = Keypair::new();
+        let member = setup_member(fee_payer.pubkey(), true);
+        assert_eq!(member.permissions, Permissions::from_actions(&Permission::ALL));
+        assert_eq!(
+            setup_member(fee_payer.pubkey(), false).permissions,
+            Permissions::from_actions(&[Permission::Initiate])
+        );
+
+        // The fee payer is passed as both the fee payer and the setup member
+        let fee_payer_option = Some(fee_payer.insecure_clone());
+        let roles = SignerRoles::new(&fee_payer_option, &fee_payer);
+        assert!(roles.is_shared());
+
+        let feature_id = Pubkey::new_unique();
+        let context = crate::provision::TransactionBuildContext::new(
+            Pubkey::new_unique(),
+            1,
+            roles.member.pubkey(),
+            solana_hash::Hash::default(),
+        )
+        .with_fee_payer(roles.fee_payer.pubkey())
+        .with_compute_budget(Some(1), Some(DEFAULT_COMPUTE_UNITS));
+        let (message, _, _) = crate::provision::create_transaction_and_proposal_message(
+            &context,
+            create_feature_activation_transaction_message(feature_id),
+        )
+        .unwrap();
+        let message = VersionedMessage::V0(message);
+        assert_eq!(message.header().num_required_signatures, 1);
+        assert_eq!(message.static_account_keys()[0], fee_payer.pubkey());
+
+        // One signature covers paying, creating the transaction and proposing
+        let transaction = VersionedTransaction::try_new(message, &roles.signers()).unwrap();
+        assert_eq!(transaction.signatures.len(), 1);
+    }
+
+    #[test]
+    fn test_sort_members_orders_by_pubkey() {
+        let setup = Member {
+            key: Pubkey::new_from_array([9; 32]),
+            permissions: Permissions::from_actions(&[Permission::Initiate]),
+        };
+        let voter = |byte| Member {
+            key: Pubkey::new_from_array([byte; 32]),
+            permissions: Permissions::from_actions(&Permission::ALL),
+        };
+        let mut members = vec![setup.clone(), voter(5), voter(1), voter(12)];
+
+        sort_members(&mut members);
+        let keys: Vec<Pubkey> = members.iter().map(|member| member.key).collect();
+        assert_eq!(
+            keys,
+            [1, 5, 9, 12].map(|byte| Pubkey::new_from_array([byte; 32])).to_vec()
+        );
+        assert!(members[2] == setup);
+    }
+
+    #[test]
+    fn test_saved_members_round_trip_permissions() {
+        let members = vec![
+            Member {
+                key: Pubkey::new_unique(),
+                permissions: Permissions::from_actions(&Permission::ALL),
+            },
+            Member {
+                key: Pubkey::new_unique(),
+                permissions: Permissions::from_actions(&[Permission::Vote]),
+            },
+        ];
+        let mut config = Config {
+            members: members.iter().map(|member| format_saved_member(member, 7)).collect(),
+            ..Config::default()
+        };
+        assert!(!config.members[0].contains(','));
+        assert_eq!(config.members[1], format!("{},2", members[1].key));
+
+        config.members.push("not-a-pubkey,7".to_string());
+        config.members.push(format!("{},notamask", Pubkey::new_unique()));
+        assert!(parse_saved_members(&config) == members);
+
+        // Plain members follow the configured default, explicit masks don't
+        config.default_member_permissions = 6;
+        let parsed = parse_saved_members(&config);
+        assert_eq!(parsed[0].permissions.mask, 6);
+        assert_eq!(parsed[1].permissions.mask, 2);
+        assert_eq!(format_saved_member(&parsed[0], 6), members[0].key.to_string());
+    }
+
+    #[test]
+    fn test_parse_config_validates_default_member_permissions() {
+        let config = parse_config(r#"{"threshold": 1}"#).unwrap();
+        assert_eq!(config.default_member_permissions, 7);
+        let config = parse_config(r#"{"threshold": 1, "default_member_permissions": 6}"#).unwrap();
+        assert_eq!(config.default_member_permissions, 6);
+        assert!(parse_config(r#"{"threshold": 1, "default_member_permissions": 8}"#).is_err());
+    }
+
+    #[test]
+    fn test_member_preview() {
+        let (line, valid) = member_preview(1, 2);
+        assert!(!valid);
+        assert_eq!(
+            line,
+            "members: 1, current threshold default: 2 (invalid: needs 1 more member(s))"
+        );
+        assert_eq!(
+            member_preview(2, 2),
+            ("members: 2, current threshold default: 2 (valid)".to_string(), true)
+        );
+        assert!(!member_preview(3, 0).1);
+    }
+
+    #[test]
+    fn test_parse_config_validates_rps() {
+        assert_eq!(parse_config(r#"{"threshold": 1}"#).unwrap().rps, None);
+        assert_eq!(parse_config(r#"{"threshold": 1, "rps": 5}"#).unwrap().rps, Some(5));
+        assert!(parse_config(r#"{"threshold": 1, "rps": 0}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_pubkey_trims_whitespace() {
+        let pubkey = Pubkey::new_unique();
+        assert_eq!(parse_pubkey(&format!("  {}\n", pubkey)).unwrap(), pubkey);
+        assert_eq!(parse_pubkey(&format!("\t{} ", pubkey)).unwrap(), pubkey);
+
+        let err = parse_pubkey(" not-a-pubkey \n").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid public key: 'not-a-pubkey'");
+    }
+
+    #[test]
+    fn test_config_round_trip_drops_legacy_network() {
+        let stale = r#"{"threshold": 2, "network": "https://stale.example.com",
+            "networks": ["https://api.testnet.solana.com"]}"#;
+        let config = parse_config(stale).unwrap();
+        assert_eq!(config.networks, vec!["https://api.testnet.solana.com"]);
+
+        let saved = serde_json::to_string(&config).unwrap();
+        assert!(!saved.contains("stale.example.com"));
+        let reloaded = parse_config(&saved).unwrap();
+        assert_eq!(reloaded.networks, config.networks);
+
+        // Older configs only had the single network, which becomes the list
+        let legacy = r#"{"threshold": 1, "network": "https://api.devnet.solana.com"}"#;
+        assert_eq!(
+            parse_config(legacy).unwrap().networks,
+            vec!["https://api.devnet.solana.com"]
+        );
+        let empty = r#"{"threshold": 1, "networks": []}"#;
+        assert_eq!(parse_config(empty).unwrap().networks, vec![DEFAULT_DEVNET_URL]);
+    }
+
+    #[test]
+    fn test_parse_config_drops_duplicate_networks() {
+        let config = r#"{"threshold": 1, "networks": ["https://api.devnet.solana.com",
+            "http://localhost:8899", "https://api.devnet.solana.com/", "http://localhost:8899"]}"#;
+        assert_eq!(
+            parse_config(config).unwrap().networks,
+            vec!["https://api.devnet.solana.com", "http://localhost:8899"]
+        );
+    }
+
+    #[test]
+    fn test_read_keypair_file_round_trip() {
+        let keypair = Keypair::new();
+        let path = std::env::temp_dir().join(format!("fgm-keypair-{}.json", keypair.pubkey()));
+        let bytes: Vec<u8> = keypair.to_bytes().to_vec();
+        fs::write(&path, serde_json::to_string(&bytes).unwrap()).unwrap();
+
+        let loaded = read_keypair_file(path.to_str().unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.pubkey(), keypair.pubkey());
+    }
+
+    #[test]
+    fn test_load_fee_payer_keypairs_from_dir_sorted() {
+        let dir = std::env::temp_dir().join(format!("fgm-fee-payers-{}", Pubkey::new_unique()));
+        fs::create_dir_all(&dir).unwrap();
+        let keypairs = [Keypair::new(), Keypair::new()];
+        for (name, keypair) in ["b.json", "a.json"].iter().zip(&keypairs) {
+            let bytes: Vec<u8> = keypair.to_bytes().to_vec();
+            fs::write(dir.join(name), serde_json::to_string(&bytes).unwrap()).unwrap();
+        }
+        fs::write(dir.join("notes.txt"), "not a keypair").unwrap();
+
+        let loaded = load_fee_payer_keypairs_from_dir(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let loaded: Vec<Pubkey> = loaded.iter().map(|k| k.pubkey()).collect();
+        assert_eq!(loaded, vec![keypairs[1].pubkey(), keypairs[0].pubkey()]);
+        assert!(load_fee_payer_keypairs_from_dir(&dir).is_err());
+    }
+
+    #[test]
+    fn test_decode_versioned_transaction() {
+        use base64::Engine;
+
+        let fee_payer = Keypair::new();
+        let message = solana_message::v0::Message::try_compile(
+            &fee_payer.pubkey(),
+            &[Instruction::new_with_bytes(Pubkey::new_unique(), &[7], vec![])],
+            &[],
+            solana_hash::Hash::new_unique(),
+        )
+        .unwrap();
+        let transaction =
+            VersionedTransaction::try_new(VersionedMessage::V0(message), &[&fee_payer]).unwrap();
+        let bytes = bincode::serialize(&transaction).unwrap();
+
+        let base64 = base64::engine::general_purpose::STANDARD.encode(&bytes);
+        let base58 = bs58::encode(&bytes).into_string();
+        assert_eq!(decode_versioned_transaction(&base64, false).unwrap(), transaction);
+        assert_eq!(decode_versioned_transaction(&base58, true).unwrap(), transaction);
+        assert!(decode_versioned_transaction(&base58, false).is_err());
+        assert!(decode_versioned_transaction("AAAA", false).is_err());
+    }
+
+    #[test]
+    fn test_transaction_message_from_base64_v0() {
+        use base64::Engine;
+        use solana_instruction::AccountMeta;
+
+        let vault = Pubkey::new_unique();
+        let readonly_signer = Pubkey::new_unique();
+        let writable = Pubkey::new_unique();
+        let readonly = Pubkey::new_unique();
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1, 2, 3],
+            vec![
+                AccountMeta::new(vault, true),
+                AccountMeta::new_readonly(readonly_signer, true),
+                AccountMeta::new(writable, false),
+                AccountMeta::new_readonly(readonly, false),
+            ],
+        );
+        let compiled = solana_message::v0::Message::try_compile(
+            &vault,
+            std::slice::from_ref(&instruction),
+            &[],
+            solana_hash::Hash::default(),
+        )
+        .unwrap();
+        let encoded = base64::engine::general_purpose::STANDARD
+            .encode(bincode::serialize(&VersionedMessage::V0(compiled)).unwrap());
+
+        let message = transaction_message_from_base64(&encoded).unwrap();
+
+        assert_compiled_matches(&message, &[instruction]);
+        assert_eq!(message.account_keys[0], vault);
+        assert_eq!(message.num_signers, 2);
+        assert_eq!(message.num_writable_signers, 1);
+        assert_eq!(message.num_writable_non_signers, 1);
+        assert!(message.address_table_lookups.is_empty());
+    }
+
+    #[test]
+    fn test_transaction_message_from_base64_rejects_garbage() {
+        assert!(transaction_message_from_base64("not base64!").is_err());
+        assert!(transaction_message_from_base64("AAAA").is_err());
+    }
+
+    #[test]
+    fn test_revocation_message_account_indexes_match_instructions() {
+        let feature_id = Pubkey::new_unique();
+        for destination in [INCINERATOR_ID, Pubkey::new_unique()] {
+            let message = create_feature_revocation_transaction_message(feature_id, destination);
+            let instruction = revoke_pending_activation(&feature_id, &destination);
+
+            assert_compiled_matches(&message, &[instruction]);
+            message.validate().unwrap();
+            assert_eq!(message.account_keys[0], feature_id);
+            assert_eq!(message.account_keys[1], destination);
+        }
+    }
+
+    #[test]
+    fn test_feature_gate_messages_match_hand_built() {
+        use crate::squads::SmallVec;
+        use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+
+        // Every signer in these messages is writable
+        fn hand_built(
+            num_signers: u8,
+            num_writable_non_signers: u8,
+            account_keys: Vec<Pubkey>,
+            instructions: Vec<(u8, Vec<u8>, Vec<u8>)>,
+        ) -> TransactionMessage {
+            TransactionMessage {
+                num_signers,
+                num_writable_signers: num_signers,
+                num_writable_non_signers,
+                account_keys: SmallVec::from(account_keys),
+                instructions: SmallVec::from(
+                    instructions
+                        .into_iter()
+                        .map(|(program_id_index, account_indexes, data)| CompiledInstruction {
+                            program_id_index,
+                            account_indexes: SmallVec::from(account_indexes),
+                            data: SmallVec::from(data),
+                        })
+                        .collect::<Vec<_>>(),
+                ),
+                address_table_lookups: SmallVec::from(vec![]),
+            }
+        }
+
+        let bytes = |message: &TransactionMessage| borsh::to_vec(message).unwrap();
+        let data = |instructions: Vec<solana_instruction::Instruction>| {
+            instructions.into_iter().map(|instruction| instruction.data).collect::<Vec<_>>()
+        };
+        let (feature_id, destination) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        // Self-funded: allocate and assign, both run by the system program
+        let [allocate, assign]: [Vec<u8>; 2] =
+            data(activate_feature_funded(&feature_id)).try_into().unwrap();
+        assert_eq!(
+            bytes(&create_feature_activation_transaction_message(feature_id)),
+            bytes(&hand_built(
+                1,
+                0,
+                vec![feature_id, SYSTEM_PROGRAM_ID, FEATURE_GATE_PROGRAM_ID],
+                vec![(1, vec![0], allocate), (1, vec![0], assign)],
+            ))
+        );
+
+        assert_eq!(
+            bytes(&create_feature_revocation_transaction_message(feature_id, destination)),
+            bytes(&hand_built(
+                1,
+                1,
+                vec![feature_id, destination, SYSTEM_PROGRAM_ID, FEATURE_GATE_PROGRAM_ID],
+                vec![(3, vec![0, 1, 2], vec![0])],
+            ))
+        );
+    }
+
+    #[test]
+    fn test_merge_config_unions_and_settles_conflicts() {
+        let shared = Pubkey::new_unique().to_string();
+        let local = Config {
+            threshold: 2,
+            members: vec![shared.clone()],
+            networks: vec!["https://a.example".to_string()],
+            fee_payer_path: Some("/home/me/id.json".to_string()),
+            multisigs: vec![SavedMultisig {
+                address: "local".to_string(),
+                label: Some("rollout".to_string()),
+                network: "https://a.example".to_string(),
+            }],
+            ..Config::default()
+        };
+        let new_member = Pubkey::new_unique().to_string();
+        let imported = Config {
+            threshold: 3,
+            members: vec![format!("{},3", shared), new_member.clone()],
+            networks: vec!["https://a.example".to_string(), "https://b.example".to_string()],
+            fee_payer_path: None,
+            multisigs: vec![SavedMultisig {
+                address: "imported".to_string(),
+                label: Some("rollout".to_string()),
+                network: "https://b.example".to_string(),
+            }],
+            ..Config::default()
+        };
+
+        let mut conflicts = Vec::new();
+        let merged = merge_config(&local, imported, |conflict| {
+            conflicts.push(format!("{:?}", conflict));
+            matches!(conflict, ConfigConflict::MemberPermissions { .. })
+        });
+
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(merged.threshold, 3);
+        assert_eq!(merged.members, vec![format!("{},3", shared), new_member]);
+        assert_eq!(merged.networks.len(), 2);
+        assert_eq!(merged.fee_payer_path.as_deref(), Some("/home/me/id.json"));
+        // Local label kept; the imported multisig is saved without it
+        assert_eq!(merged.multisigs[0].label.as_deref(), Some("rollout"));
+        assert_eq!(merged.multisigs[1].address, "imported");
+        assert_eq!(merged.multisigs[1].label, None);
+
+        // Plain members of a config with another default keep their permissions
+        let other_default = Pubkey::new_unique().to_string();
+        let imported = Config {
+            members: vec![other_default.clone()],
+            default_member_permissions: 6,
+            ..Config::default()
+        };
+        let merged = merge_config(&local, imported, |_| false);
+        assert_eq!(merged.default_member_permissions, 7);
+        assert_eq!(merged.members[1], format!("{},6", other_default));
+    }
+
+    #[test]
+    fn test_feature_gate_proposal_types_keep_index_order() {
+        assert_eq!(
+            feature_gate_proposal_types(true, true),
+            vec![TransactionType::Activation, TransactionType::Revocation]
+        );
+        assert_eq!(feature_gate_proposal_types(false, true), vec![TransactionType::Revocation]);
+        assert_eq!(feature_gate_proposal_types(true, false), vec![TransactionType::Activation]);
+        assert!(feature_gate_proposal_types(false, false).is_empty());
+    }
+}